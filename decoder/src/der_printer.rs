@@ -1,8 +1,9 @@
 use rx509::der::ParseHandler;
-use rx509::der::{ASNError, ASNType};
+use rx509::der::{to_hex, ASNError, ASNType, OidNameTable};
 
 pub struct ParsePrinter {
     indent: usize,
+    names: OidNameTable,
 }
 
 impl ParsePrinter {
@@ -12,8 +13,8 @@ impl ParsePrinter {
         }
     }
 
-    pub fn new() -> ParsePrinter {
-        ParsePrinter { indent: 0 }
+    pub fn new(names: OidNameTable) -> ParsePrinter {
+        ParsePrinter { indent: 0, names }
     }
 }
 
@@ -28,18 +29,19 @@ impl ParseHandler for ParsePrinter {
 
     fn on_type(&mut self, asn: &ASNType) {
         self.print_indent();
-        println!("{}", asn);
+        match asn {
+            ASNType::ObjectIdentifier(wrapper) => println!(
+                "ObjectIdentifier: {}",
+                wrapper.value.describe_with(&self.names)
+            ),
+            _ => println!("{}", asn),
+        }
         if let ASNType::BitString(wrapper) = asn {
             if let Some(octets) = wrapper.value.octets() {
                 self.indent += 1;
                 for chunk in octets.chunks(16) {
                     self.print_indent();
-                    if let Some((last, first)) = chunk.split_last() {
-                        for byte in first {
-                            print!("{:02X}:", byte)
-                        }
-                        println!("{:02X}", last)
-                    }
+                    println!("{}", to_hex(chunk));
                 }
                 self.indent -= 1;
             }
@@ -5,7 +5,8 @@ use std::fs::File;
 use std::io::prelude::*;
 use std::process;
 
-use rx509::der::parse_all;
+use rx509::der::{parse_all, OidNameTable};
+use rx509::x509::csr::CertificationRequest;
 use rx509::x509::printer::{ConsoleLinePrinter, Printable};
 use rx509::x509::Certificate;
 
@@ -17,8 +18,8 @@ fn get_bytes(file: &str) -> Result<Vec<u8>, std::io::Error> {
 }
 
 pub fn main() -> Result<(), std::io::Error> {
-    fn parse_der(bytes: &[u8]) -> Result<(), std::io::Error> {
-        parse_all(bytes, &mut der_printer::ParsePrinter::new()).or_else(|err| {
+    fn parse_der(bytes: &[u8], names: OidNameTable) -> Result<(), std::io::Error> {
+        parse_all(bytes, &mut der_printer::ParsePrinter::new(names)).or_else(|err| {
             eprintln!("Error: {}", err);
             Ok(())
         })
@@ -33,16 +34,56 @@ pub fn main() -> Result<(), std::io::Error> {
         Ok(())
     }
 
+    fn parse_x509_json(bytes: &[u8]) -> Result<(), std::io::Error> {
+        match Certificate::parse(bytes) {
+            Ok(cert) => println!("{}", cert.to_json()),
+            Err(err) => eprintln!("Error: {}", err),
+        };
+
+        Ok(())
+    }
+
+    fn parse_csr(bytes: &[u8]) -> Result<(), std::io::Error> {
+        match CertificationRequest::parse(bytes) {
+            Ok(csr) => csr.print(&mut ConsoleLinePrinter::new()),
+            Err(err) => eprintln!("Error: {}", err),
+        };
+
+        Ok(())
+    }
+
+    fn get_names(args: &[String]) -> Result<OidNameTable, std::io::Error> {
+        match args {
+            [] => Ok(OidNameTable::new()),
+            [flag, file] if flag == "--oid-names" => {
+                OidNameTable::parse(&std::fs::read_to_string(file)?).map_err(|err| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+                })
+            }
+            _ => {
+                eprintln!("{}", USAGE);
+                process::exit(-1);
+            }
+        }
+    }
+
+    const USAGE: &str =
+        "usage: decoder <--der | --certs | --json | --csr> <filename> [--oid-names <filename>]";
+
     let args: Vec<String> = env::args().collect();
 
-    if args.len() != 3 {
-        eprintln!("requires exactly 2 arguments: decoder <--der | --certs> <filename>");
+    if args.len() < 3 {
+        eprintln!("{}", USAGE);
         process::exit(-1);
     }
 
+    let names = get_names(&args[3..])?;
+
     match args[1].as_str() {
-        "--der" => parse_der(&get_bytes(&args[2])?),
+        "--der" => parse_der(&get_bytes(&args[2])?, names),
         "--certs" => parse_x509(&get_bytes(&args[2])?),
+        "--json" => parse_x509_json(&get_bytes(&args[2])?),
+        "--csr" => parse_csr(&get_bytes(&args[2])?),
         unknown => {
             eprintln!("Unknown flag: {}", unknown);
             process::exit(-1);
@@ -0,0 +1,100 @@
+//! Allocator pressure of parsing a large bundle of certificates and their extensions, on the
+//! heap and in an arena reset between certificates
+//!
+//! Run with `cargo bench -p rx509 --features arena`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use bumpalo::Bump;
+use rx509::x509::Certificate;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const COPIES: usize = 10_000;
+
+fn bundle() -> Vec<&'static [u8]> {
+    let certs: [&'static [u8]; 4] = [
+        include_bytes!("../../certs/tls_server.der"),
+        include_bytes!("../../certs/aki_issuer_serial.der"),
+        include_bytes!("../../certs/smartcard_upn.der"),
+        include_bytes!("../../certs/ec_p256_cert.der"),
+    ];
+    certs
+        .iter()
+        .copied()
+        .cycle()
+        .take(COPIES * certs.len())
+        .collect()
+}
+
+fn parse_on_heap(bundle: &[&[u8]]) -> usize {
+    let mut count = 0;
+    for der in bundle {
+        let cert = Certificate::parse(der).unwrap();
+        if let Some(extensions) = &cert.tbs_certificate.value.extensions {
+            count += extensions.parse().unwrap().len();
+        }
+    }
+    count
+}
+
+fn parse_in_arena(bundle: &[&[u8]]) -> usize {
+    let mut arena = Bump::new();
+    let mut count = 0;
+    for der in bundle {
+        {
+            let cert = Certificate::parse_in(der, &arena).unwrap();
+            if let Some(extensions) = &cert.tbs_certificate.value.extensions {
+                count += extensions.parse_in(&arena).unwrap().len();
+            }
+        }
+        arena.reset();
+    }
+    count
+}
+
+fn measure(name: &str, bundle: &[&[u8]], parse: fn(&[&[u8]]) -> usize) -> usize {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let extensions = parse(bundle);
+    let elapsed: Duration = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+    println!(
+        "{:<6} {:>8} certs {:>9} allocations ({:.2} per cert) {:>10.2?} ({:.0} ns per cert)",
+        name,
+        bundle.len(),
+        allocations,
+        allocations as f64 / bundle.len() as f64,
+        elapsed,
+        elapsed.as_nanos() as f64 / bundle.len() as f64,
+    );
+    extensions
+}
+
+fn main() {
+    let bundle = bundle();
+    // warm up, so that neither run pays for the first touch of the inputs
+    parse_on_heap(&bundle);
+
+    let heap = measure("heap", &bundle, parse_on_heap);
+    let arena = measure("arena", &bundle, parse_in_arena);
+    assert_eq!(heap, arena);
+}
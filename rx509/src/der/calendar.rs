@@ -65,6 +65,51 @@ pub(crate) fn time_from_ymdhms_utc(
     Ok(UtcTime::from_seconds_since_epoch(seconds_since_unix_epoch))
 }
 
+/// inverse of `time_from_ymdhms_utc`, returns (year, month, day_of_month, hours, minutes, seconds)
+pub(crate) fn ymdhms_utc_from_time(time: UtcTime) -> (u64, u64, u64, u64, u64, u64) {
+    const DAYS_PER_400_YEARS: u64 = 146_097;
+    const DAYS_PER_100_YEARS: u64 = 36_524;
+    const DAYS_PER_4_YEARS: u64 = 1_461;
+
+    let seconds_of_day = time.value % (24 * 60 * 60);
+    // days since January 1, 1 AD, from which the Gregorian calendar repeats every 400 years
+    let mut days = time.value / (24 * 60 * 60) + DAYS_BEFORE_UNIX_EPOCH_AD;
+
+    let cycles = days / DAYS_PER_400_YEARS;
+    days %= DAYS_PER_400_YEARS;
+    // only the last century of a cycle ends with a leap year
+    let centuries = (days / DAYS_PER_100_YEARS).min(3);
+    days -= centuries * DAYS_PER_100_YEARS;
+    let quadrennia = days / DAYS_PER_4_YEARS;
+    days %= DAYS_PER_4_YEARS;
+    // only the last year of four is a leap year
+    let years = (days / 365).min(3);
+    days -= years * 365;
+
+    let year = 1 + 400 * cycles + 100 * centuries + 4 * quadrennia + years;
+
+    let mut month = 1;
+    while days >= days_in_month(year, month) {
+        days -= days_in_month(year, month);
+        month += 1;
+    }
+
+    (
+        year,
+        month,
+        days + 1,
+        seconds_of_day / (60 * 60),
+        (seconds_of_day / 60) % 60,
+        seconds_of_day % 60,
+    )
+}
+
+/// The first second of `year`, which must not be before 1970
+pub(crate) fn start_of_year(year: u64) -> Result<UtcTime, ASNErrorVariant> {
+    let days = days_before_year_since_unix_epoch(year)?;
+    Ok(UtcTime::from_seconds_since_epoch(days * 24 * 60 * 60))
+}
+
 fn days_before_year_since_unix_epoch(year: u64) -> Result<u64, ASNErrorVariant> {
     // We don't support dates before January 1, 1970 because that is the
     // Unix epoch. It is likely that other software won't deal well with
@@ -94,7 +139,7 @@ pub(crate) fn days_in_month(year: u64, month: u64) -> u64 {
 }
 
 fn days_in_feb(year: u64) -> u64 {
-    if (year % 4 == 0) && ((year % 100 != 0) || (year % 400 == 0)) {
+    if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) {
         29
     } else {
         28
@@ -161,4 +206,41 @@ mod tests {
             time_from_ymdhms_utc(2016, 4, 17, 17, 12, 42).unwrap()
         );
     }
+
+    #[test]
+    fn test_ymdhms_utc_from_time() {
+        use super::*;
+
+        for (year, month, day, hours, minutes, seconds) in [
+            (1970, 1, 1, 0, 0, 0),
+            (2016, 2, 29, 12, 30, 45),
+            (2016, 12, 31, 23, 59, 59),
+            (2017, 1, 1, 0, 0, 0),
+            (2000, 2, 29, 0, 0, 0),
+            (2000, 12, 31, 23, 59, 59),
+            (2100, 2, 28, 23, 59, 59),
+            (2100, 3, 1, 1, 2, 3),
+            (2400, 12, 31, 12, 0, 0),
+            (2401, 1, 1, 0, 0, 0),
+            (9999, 12, 31, 23, 59, 59),
+        ] {
+            let time = time_from_ymdhms_utc(year, month, day, hours, minutes, seconds).unwrap();
+            assert_eq!(
+                ymdhms_utc_from_time(time),
+                (year, month, day, hours, minutes, seconds)
+            );
+        }
+    }
+
+    #[test]
+    fn converts_the_largest_time_without_iterating_over_years() {
+        use super::*;
+
+        let (year, ..) = ymdhms_utc_from_time(UtcTime::from_seconds_since_epoch(u64::MAX));
+        assert_eq!(year, 584_554_051_223);
+        assert_eq!(
+            start_of_year(10_000),
+            Ok(UtcTime::from_seconds_since_epoch(253_402_300_800))
+        );
+    }
 }
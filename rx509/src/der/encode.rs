@@ -0,0 +1,660 @@
+use crate::der::calendar;
+use crate::der::types::*;
+
+/// Total number of bytes (identifier, length, and contents) that `encode` produces for a value
+///
+/// Fails for the same values as `encode`.
+pub fn encoded_len(ty: &ASNType) -> Result<usize, ASNError> {
+    validate(ty)?;
+    Ok(element_len(ty))
+}
+
+fn element_len(ty: &ASNType) -> usize {
+    let contents = contents_len(ty);
+    identifier_len(ty) + length_len(contents) + contents
+}
+
+/// Append the DER encoding of a value to `out`
+///
+/// `Sequence`, `Set`, and `ExplicitTag` values hold already encoded contents which are copied as-is.
+/// Fails without writing anything if the value has no encoding, e.g. a UTCTime outside of
+/// 1950 through 2049 or an OID rejected by [`ASNObjectIdentifier::encode`].
+pub fn encode(ty: &ASNType, out: &mut Vec<u8>) -> Result<(), ASNError> {
+    validate(ty)?;
+    write_element(ty, out);
+    Ok(())
+}
+
+/// Append the encoding of a value under an `[tag] IMPLICIT` context-specific tag
+///
+/// The identifier of `ty` is replaced by the tag, keeping its primitive or constructed form,
+/// and the length and contents are unchanged.
+pub fn encode_implicit(tag: u32, ty: &ASNType, out: &mut Vec<u8>) -> Result<(), ASNError> {
+    validate(ty)?;
+    let constructed = matches!(
+        ty,
+        ASNType::Sequence(_) | ASNType::Set(_) | ASNType::ExplicitTag(_)
+    );
+    let contents = contents_len(ty);
+    out.reserve(context_identifier_len(tag) + length_len(contents) + contents);
+    write_context_identifier(constructed, tag, out);
+    write_length(contents, out);
+    write_contents(ty, out);
+    Ok(())
+}
+
+fn write_element(ty: &ASNType, out: &mut Vec<u8>) {
+    out.reserve(element_len(ty));
+    write_identifier(ty, out);
+    write_length(contents_len(ty), out);
+    write_contents(ty, out);
+}
+
+// values that can be represented but not encoded, checked before anything is written
+fn validate(ty: &ASNType) -> Result<(), ASNErrorVariant> {
+    // times start in 1970, so only the last year that can be encoded needs to be checked
+    fn check_year(time: UtcTime, kind: ASNTypeId, last_year: u64) -> Result<(), ASNErrorVariant> {
+        if time < calendar::start_of_year(last_year + 1)? {
+            Ok(())
+        } else {
+            let (year, ..) = calendar::ymdhms_utc_from_time(time);
+            Err(ASNErrorVariant::YearOutOfRange(kind, year))
+        }
+    }
+
+    match ty {
+        // the two digit year maps 50 through 99 onto the 1900s (RFC 5280 4.1.2.5.1)
+        ASNType::UTCTime(time) => check_year(*time, ASNTypeId::UTCTime, 2049),
+        ASNType::GeneralizedTime(time) => check_year(*time, ASNTypeId::GeneralizedTime, 9999),
+        ASNType::ObjectIdentifier(wrapper) => check_arcs(&wrapper.value),
+        // the contents are Latin-1, the inverse of how they are decoded
+        ASNType::TeletexString(wrapper) => match wrapper.value.chars().find(|c| *c > '\u{FF}') {
+            Some(c) => Err(ASNErrorVariant::NonLatin1Char(c)),
+            None => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Sort already encoded elements into the canonical order of a DER SET OF
+///
+/// X.690 11.6 orders the encodings ascending as octet strings, with the shorter of two
+/// encodings padded at its trailing end with zero octets for the comparison.
+pub fn sort_set_of(elements: &mut [Vec<u8>]) {
+    elements.sort_by(|a, b| {
+        let len = a.len().max(b.len());
+        let padded = |x: &[u8], i: usize| x.get(i).copied().unwrap_or(0);
+        (0..len)
+            .map(|i| padded(a, i).cmp(&padded(b, i)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(core::cmp::Ordering::Equal)
+    });
+}
+
+/// Number of bytes used by the definite form of a length
+pub(crate) fn length_len(length: usize) -> usize {
+    if length < 128 {
+        1
+    } else {
+        1 + length_bytes(length)
+    }
+}
+
+/// Append the minimal definite form of a length
+pub(crate) fn write_length(length: usize, out: &mut Vec<u8>) {
+    if length < 128 {
+        out.push(length as u8);
+    } else {
+        let count = length_bytes(length);
+        out.push(0b1000_0000 | count as u8);
+        for i in (0..count).rev() {
+            out.push((length >> (8 * i)) as u8);
+        }
+    }
+}
+
+// minimum number of bytes needed to represent the long form of a length
+fn length_bytes(length: usize) -> usize {
+    let mut count = 1;
+    while count < core::mem::size_of::<usize>() && (length >> (8 * count)) != 0 {
+        count += 1;
+    }
+    count
+}
+
+// tag numbers from 31 on use the high-tag-number form
+const LONG_FORM: u8 = 0b0001_1111;
+
+fn identifier_len(ty: &ASNType) -> usize {
+    match ty {
+        ASNType::ExplicitTag(wrapper) => context_identifier_len(wrapper.value.value),
+        _ => 1,
+    }
+}
+
+fn context_identifier_len(tag: u32) -> usize {
+    if tag >= u32::from(LONG_FORM) {
+        1 + base128_len(u128::from(tag))
+    } else {
+        1
+    }
+}
+
+fn write_context_identifier(constructed: bool, tag: u32, out: &mut Vec<u8>) {
+    let class = if constructed {
+        0b1010_0000
+    } else {
+        0b1000_0000
+    };
+    match u8::try_from(tag) {
+        Ok(tag) if tag < LONG_FORM => out.push(class | tag),
+        _ => {
+            out.push(class | LONG_FORM);
+            write_base128(u128::from(tag), out);
+        }
+    }
+}
+
+fn write_identifier(ty: &ASNType, out: &mut Vec<u8>) {
+    let first = match ty {
+        ASNType::Boolean(_) => 0x01,
+        ASNType::Integer(_) => 0x02,
+        ASNType::BitString(_) => 0x03,
+        ASNType::OctetString(_) => 0x04,
+        ASNType::Null => 0x05,
+        ASNType::ObjectIdentifier(_) => 0x06,
+        ASNType::UTF8String(_) => 0x0C,
+        ASNType::BMPString(_) => 0x1E,
+        ASNType::TeletexString(_) => 0x14,
+        ASNType::PrintableString(_) => 0x13,
+        ASNType::IA5String(_) => 0x16,
+        ASNType::NumericString(_) => 0x12,
+        ASNType::VisibleString(_) => 0x1A,
+        ASNType::UTCTime(_) => 0x17,
+        ASNType::GeneralizedTime(_) => 0x18,
+        ASNType::Sequence(_) => 0x30,
+        ASNType::Set(_) => 0x31,
+        // an explicit tag always uses the constructed form
+        ASNType::ExplicitTag(wrapper) => {
+            write_context_identifier(true, wrapper.value.value, out);
+            return;
+        }
+    };
+    out.push(first);
+}
+
+impl ASNObjectIdentifier<'_> {
+    /// Contents of the DER encoding of the OID, without the identifier and length
+    ///
+    /// Fails if there are fewer than two arcs, the first arc is above 2, the second arc is
+    /// 40 or more under a first arc of 0 or 1, or the two combined don't fit in 128 bits.
+    pub fn encode(&self) -> Result<Vec<u8>, ASNError> {
+        check_arcs(self)?;
+        let mut out = Vec::new();
+        for_each_subidentifier(self, |x| write_base128(x, &mut out));
+        Ok(out)
+    }
+}
+
+fn contents_len(ty: &ASNType) -> usize {
+    match ty {
+        ASNType::Boolean(_) => 1,
+        ASNType::Integer(wrapper) => wrapper.value.bytes.len(),
+        ASNType::BitString(wrapper) => 1 + wrapper.value.bytes.len(),
+        ASNType::OctetString(wrapper) => wrapper.value.len(),
+        ASNType::Null => 0,
+        ASNType::ObjectIdentifier(wrapper) => {
+            let mut count = 0;
+            for_each_subidentifier(&wrapper.value, |x| count += base128_len(x));
+            count
+        }
+        ASNType::UTF8String(wrapper) => wrapper.value.len(),
+        ASNType::BMPString(wrapper) => 2 * wrapper.value.encode_utf16().count(),
+        ASNType::TeletexString(wrapper) => wrapper.value.chars().count(),
+        ASNType::PrintableString(wrapper) => wrapper.value.len(),
+        ASNType::IA5String(wrapper) => wrapper.value.len(),
+        ASNType::NumericString(wrapper) => wrapper.value.len(),
+        ASNType::VisibleString(wrapper) => wrapper.value.len(),
+        // YYMMDDHHMMSSZ
+        ASNType::UTCTime(_) => 13,
+        // YYYYMMDDHHMMSSZ
+        ASNType::GeneralizedTime(_) => 15,
+        ASNType::Sequence(wrapper) => wrapper.value.len(),
+        ASNType::Set(wrapper) => wrapper.value.len(),
+        ASNType::ExplicitTag(wrapper) => wrapper.value.contents.len(),
+    }
+}
+
+fn write_contents(ty: &ASNType, out: &mut Vec<u8>) {
+    match ty {
+        ASNType::Boolean(wrapper) => out.push(if wrapper.value { 0xFF } else { 0x00 }),
+        ASNType::Integer(wrapper) => out.extend_from_slice(wrapper.value.bytes),
+        ASNType::BitString(wrapper) => {
+            out.push(wrapper.value.unused_bits);
+            out.extend_from_slice(wrapper.value.bytes);
+        }
+        ASNType::OctetString(wrapper) => out.extend_from_slice(wrapper.value),
+        ASNType::Null => {}
+        ASNType::ObjectIdentifier(wrapper) => {
+            for_each_subidentifier(&wrapper.value, |x| write_base128(x, out))
+        }
+        ASNType::UTF8String(wrapper) => out.extend_from_slice(wrapper.value.as_bytes()),
+        ASNType::BMPString(wrapper) => {
+            for unit in wrapper.value.encode_utf16() {
+                out.extend_from_slice(&unit.to_be_bytes());
+            }
+        }
+        // validate rejects characters outside of Latin-1, the others are a single byte
+        ASNType::TeletexString(wrapper) => out.extend(wrapper.value.chars().map(|c| c as u8)),
+        ASNType::PrintableString(wrapper) => out.extend_from_slice(wrapper.value.as_bytes()),
+        ASNType::IA5String(wrapper) => out.extend_from_slice(wrapper.value.as_bytes()),
+        ASNType::NumericString(wrapper) => out.extend_from_slice(wrapper.value.as_bytes()),
+        ASNType::VisibleString(wrapper) => out.extend_from_slice(wrapper.value.as_bytes()),
+        ASNType::UTCTime(time) => write_time(*time, false, out),
+        ASNType::GeneralizedTime(time) => write_time(*time, true, out),
+        ASNType::Sequence(wrapper) => out.extend_from_slice(wrapper.value),
+        ASNType::Set(wrapper) => out.extend_from_slice(wrapper.value),
+        ASNType::ExplicitTag(wrapper) => out.extend_from_slice(wrapper.value.contents),
+    }
+}
+
+// the first two arcs are combined into a single subidentifier, None if they can't be
+fn first_subidentifier(arcs: &[u128]) -> Option<u128> {
+    match arcs {
+        [first @ 0..=1, second @ 0..=39, ..] | [first @ 2, second, ..] => {
+            first.checked_mul(40)?.checked_add(*second)
+        }
+        _ => None,
+    }
+}
+
+// shared by both encoders so that an OID is never written with arcs it can't hold
+fn check_arcs(oid: &ASNObjectIdentifier) -> Result<(), ASNErrorVariant> {
+    match first_subidentifier(oid.values()) {
+        Some(_) => Ok(()),
+        None => Err(ASNErrorVariant::BadOidArcs(oid.clone().into_owned())),
+    }
+}
+
+// only called once the arcs are checked
+fn for_each_subidentifier<F: FnMut(u128)>(oid: &ASNObjectIdentifier, mut f: F) {
+    if let Some(first) = first_subidentifier(oid.values()) {
+        f(first);
+        for value in &oid.values()[2..] {
+            f(*value);
+        }
+    }
+}
+
+fn base128_len(value: u128) -> usize {
+    let bits = (u128::BITS - value.leading_zeros()) as usize;
+    bits.div_ceil(7).max(1)
+}
+
+fn write_base128(value: u128, out: &mut Vec<u8>) {
+    let count = base128_len(value);
+    for i in (0..count).rev() {
+        let byte = ((value >> (7 * i)) & 0b0111_1111) as u8;
+        if i == 0 {
+            out.push(byte);
+        } else {
+            out.push(byte | 0b1000_0000);
+        }
+    }
+}
+
+fn write_time(time: UtcTime, generalized: bool, out: &mut Vec<u8>) {
+    fn write_two_digits(value: u64, out: &mut Vec<u8>) {
+        out.push(b'0' + (value / 10 % 10) as u8);
+        out.push(b'0' + (value % 10) as u8);
+    }
+
+    let (year, month, day, hours, minutes, seconds) = calendar::ymdhms_utc_from_time(time);
+    if generalized {
+        write_two_digits(year / 100, out);
+    }
+    write_two_digits(year % 100, out);
+    for value in [month, day, hours, minutes, seconds] {
+        write_two_digits(value, out);
+    }
+    out.push(b'Z');
+}
+
+/// Builds the encoding of a SEQUENCE from its elements
+#[derive(Debug, Default)]
+pub struct SequenceBuilder {
+    contents: Vec<u8>,
+}
+
+impl SequenceBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an element to the sequence, fails if the element can't be encoded
+    pub fn add(&mut self, ty: &ASNType) -> Result<&mut Self, ASNError> {
+        encode(ty, &mut self.contents)?;
+        Ok(self)
+    }
+
+    /// Append an element under an `[tag] IMPLICIT` tag, see [`encode_implicit`]
+    pub fn add_implicit(&mut self, tag: u32, ty: &ASNType) -> Result<&mut Self, ASNError> {
+        encode_implicit(tag, ty, &mut self.contents)?;
+        Ok(self)
+    }
+
+    /// Append an element that is already encoded, its bytes are copied as-is
+    pub fn add_der(&mut self, der: &[u8]) -> &mut Self {
+        self.contents.extend_from_slice(der);
+        self
+    }
+
+    /// Append a nested sequence
+    pub fn add_sequence(&mut self, inner: &SequenceBuilder) -> &mut Self {
+        // the contents were encoded by the inner builder and are copied as-is
+        write_element(&Sequence::asn(&inner.contents), &mut self.contents);
+        self
+    }
+
+    /// Total number of bytes the encoded sequence will occupy
+    pub fn encoded_len(&self) -> usize {
+        1 + length_len(self.contents.len()) + self.contents.len()
+    }
+
+    /// Produce the encoding of the sequence in a single allocation
+    pub fn build(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.encoded_len());
+        out.push(0x30);
+        write_length(self.contents.len(), &mut out);
+        out.extend_from_slice(&self.contents);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::der::parser::Parser;
+
+    fn encode_to_vec(ty: &ASNType) -> Vec<u8> {
+        let mut out = Vec::new();
+        encode(ty, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn encoded_len_of_boolean_matches_encoding() {
+        let value = Boolean::asn(true);
+        let bytes = encode_to_vec(&value);
+        assert_eq!(bytes, [0x01, 0x01, 0xFF]);
+        assert_eq!(encoded_len(&value).unwrap(), bytes.len());
+    }
+
+    #[test]
+    fn encoded_len_of_long_octet_string_includes_length_of_length() {
+        let contents = [0xAB; 200];
+        let value = OctetString::asn(&contents);
+        let bytes = encode_to_vec(&value);
+        assert_eq!(&bytes[..3], &[0x04, 0x81, 200]);
+        assert_eq!(encoded_len(&value).unwrap(), 203);
+        assert_eq!(encoded_len(&value).unwrap(), bytes.len());
+    }
+
+    #[test]
+    fn encodes_high_tag_numbers_in_long_form() {
+        for (tag, identifier) in [
+            (30, &[0xBE][..]),
+            (31, &[0xBF, 0x1F]),
+            (128, &[0xBF, 0x81, 0x00]),
+        ] {
+            let value = ExplicitTag::asn(ASNExplicitTag::new(tag, &[0x05, 0x00]));
+            let bytes = encode_to_vec(&value);
+            assert_eq!(&bytes[..identifier.len()], identifier);
+            assert_eq!(encoded_len(&value).unwrap(), bytes.len());
+            assert_eq!(
+                Parser::new(&bytes).expect::<ExplicitTag>().unwrap().value,
+                tag
+            );
+        }
+    }
+
+    #[test]
+    fn replaces_the_identifier_of_implicitly_tagged_values() {
+        let mut out = Vec::new();
+        encode_implicit(1, &BitString::asn(ASNBitString::new(0, &[0xAB])), &mut out).unwrap();
+        encode_implicit(2, &Sequence::asn(&[0x05, 0x00]), &mut out).unwrap();
+        encode_implicit(31, &ASNType::Null, &mut out).unwrap();
+        assert_eq!(
+            out,
+            [0x81, 0x02, 0x00, 0xAB, 0xA2, 0x02, 0x05, 0x00, 0x9F, 0x1F, 0x00]
+        );
+    }
+
+    #[test]
+    fn encoded_len_of_nested_sequence_matches_encoding() {
+        let mut inner = SequenceBuilder::new();
+        inner
+            .add(&Integer::asn(ASNInteger::new(&[0x05])))
+            .unwrap()
+            .add(&ASNType::Null)
+            .unwrap();
+
+        let mut outer = SequenceBuilder::new();
+        outer
+            .add_sequence(&inner)
+            .add(&Boolean::asn(false))
+            .unwrap();
+
+        let bytes = outer.build();
+        assert_eq!(
+            bytes,
+            [0x30, 0x0A, 0x30, 0x05, 0x02, 0x01, 0x05, 0x05, 0x00, 0x01, 0x01, 0x00]
+        );
+        assert_eq!(outer.encoded_len(), bytes.len());
+        assert_eq!(
+            encoded_len(&Sequence::asn(&bytes[2..])).unwrap(),
+            bytes.len()
+        );
+    }
+
+    #[test]
+    fn sorts_set_of_avas_regardless_of_input_order() {
+        fn ava(oid: &[u128], value: &'static str) -> Vec<u8> {
+            let mut ava = SequenceBuilder::new();
+            ava.add(&ObjectIdentifier::asn(ASNObjectIdentifier::new(
+                oid.to_vec(),
+            )))
+            .unwrap()
+            .add(&UTF8String::asn(value))
+            .unwrap();
+            ava.build()
+        }
+
+        // organizationName (2.5.4.10) sorts after commonName (2.5.4.3) on the final OID byte
+        let cn = ava(&[2, 5, 4, 3], "zzz");
+        let o = ava(&[2, 5, 4, 10], "aaa");
+
+        for mut elements in [vec![cn.clone(), o.clone()], vec![o.clone(), cn.clone()]] {
+            sort_set_of(&mut elements);
+            assert_eq!(elements, [cn.clone(), o.clone()]);
+        }
+    }
+
+    #[test]
+    fn set_of_pads_shorter_elements_with_zeros() {
+        let mut elements = vec![vec![0x04, 0x02, 0x01, 0x00], vec![0x04, 0x01, 0xFF]];
+        sort_set_of(&mut elements);
+        // the second length octet decides the order before the contents are compared
+        assert_eq!(
+            elements,
+            [vec![0x04, 0x01, 0xFF], vec![0x04, 0x02, 0x01, 0x00]]
+        );
+
+        let mut elements = vec![vec![0x01, 0x00, 0x01], vec![0x01]];
+        sort_set_of(&mut elements);
+        assert_eq!(elements, [vec![0x01], vec![0x01, 0x00, 0x01]]);
+    }
+
+    #[test]
+    fn writes_minimal_lengths() {
+        for (length, expected) in [
+            (0, &[0x00][..]),
+            (127, &[0x7F]),
+            (128, &[0x81, 0x80]),
+            (255, &[0x81, 0xFF]),
+            (256, &[0x82, 0x01, 0x00]),
+            (65536, &[0x83, 0x01, 0x00, 0x00]),
+        ] {
+            let mut out = Vec::new();
+            write_length(length, &mut out);
+            assert_eq!(out, expected);
+            assert_eq!(length_len(length), expected.len());
+        }
+    }
+
+    #[test]
+    fn rejects_years_a_utc_time_cannot_hold() {
+        // 2049-12-31T23:59:59Z and 2050-01-01T00:00:00Z
+        let last = UtcTime::from_seconds_since_epoch(2524607999);
+        let first = UtcTime::from_seconds_since_epoch(2524608000);
+
+        assert_eq!(
+            encode_to_vec(&ASNType::UTCTime(last)),
+            b"\x17\x0D491231235959Z"
+        );
+        let mut out = Vec::new();
+        assert_eq!(
+            encode(&ASNType::UTCTime(first), &mut out)
+                .unwrap_err()
+                .variant,
+            ASNErrorVariant::YearOutOfRange(ASNTypeId::UTCTime, 2050)
+        );
+        assert!(out.is_empty());
+
+        // the same time is fine as a GeneralizedTime
+        assert_eq!(
+            encode_to_vec(&GeneralizedTime::asn(first.value)),
+            b"\x18\x0F20500101000000Z"
+        );
+    }
+
+    #[test]
+    fn rejects_years_beyond_four_digits() {
+        // 10000-01-01T00:00:00Z
+        let time = GeneralizedTime::asn(253402300800);
+        assert_eq!(
+            encode(&time, &mut Vec::new()).unwrap_err().variant,
+            ASNErrorVariant::YearOutOfRange(ASNTypeId::GeneralizedTime, 10000)
+        );
+
+        let time = GeneralizedTime::asn(u64::MAX);
+        assert_eq!(
+            encode(&time, &mut Vec::new()).unwrap_err().variant,
+            ASNErrorVariant::YearOutOfRange(ASNTypeId::GeneralizedTime, 584_554_051_223)
+        );
+    }
+
+    #[test]
+    fn rejects_teletex_strings_outside_of_latin_1() {
+        assert_eq!(
+            encode_to_vec(&TeletexString::asn("café".to_string())),
+            b"\x14\x04caf\xE9"
+        );
+
+        let mut out = Vec::new();
+        for ty in [
+            TeletexString::asn("naïve ✓".to_string()),
+            TeletexString::asn("€".to_string()),
+        ] {
+            let err = encode(&ty, &mut out).unwrap_err();
+            assert!(matches!(
+                err.variant,
+                ASNErrorVariant::NonLatin1Char('✓' | '€')
+            ));
+            assert_eq!(encoded_len(&ty).unwrap_err(), err);
+        }
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn round_trips_primitive_values() {
+        let inputs: [&[u8]; 6] = [
+            &[0x02, 0x02, 0x00, 0x80],
+            &[0x03, 0x02, 0x01, 0x06],
+            &[0x06, 0x07, 0x2B, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37],
+            &[0x0C, 0x02, b'h', b'i'],
+            b"\x17\x0D990102052345Z",
+            b"\x18\x0F21230710232640Z",
+        ];
+        for input in inputs {
+            let value = Parser::new(input).expect_any().unwrap();
+            assert_eq!(encode_to_vec(&value), input);
+            assert_eq!(encoded_len(&value).unwrap(), input.len());
+        }
+    }
+
+    #[test]
+    fn object_identifiers_round_trip_through_their_encoding() {
+        for (arcs, contents) in [
+            // commonName
+            (&[2, 5, 4, 3][..], &[0x55, 0x04, 0x03][..]),
+            // sha256WithRSAEncryption
+            (
+                &[1, 2, 840, 113549, 1, 1, 11],
+                &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B],
+            ),
+            // Microsoft: szOID_REQUEST_CLIENT_INFO
+            (
+                &[1, 3, 6, 1, 4, 1, 311, 21, 20],
+                &[0x2B, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x15, 0x14],
+            ),
+            // a second arc above 47 under joint-iso-itu-t
+            (&[2, 999, 3], &[0x88, 0x37, 0x03]),
+            (&[0, 0], &[0x00]),
+            // a UUID arc, f81d4fae-7dec-11d0-a765-00a0c91e6bf6
+            (
+                &[2, 25, 0xf81d4fae_7dec_11d0_a765_00a0c91e6bf6],
+                &[
+                    0x69, 0x83, 0xF0, 0x9D, 0xA7, 0xEB, 0xCF, 0xDE, 0xE0, 0xC7, 0xA1, 0xA7, 0xB2,
+                    0xC0, 0x94, 0x8C, 0xC8, 0xF9, 0xD7, 0x76,
+                ],
+            ),
+        ] {
+            let oid = ASNObjectIdentifier::new(arcs.to_vec());
+            let encoded = oid.encode().unwrap();
+            assert_eq!(encoded, contents);
+            assert_eq!(ASNObjectIdentifier::from_encoded(&encoded).unwrap(), oid);
+        }
+    }
+
+    #[test]
+    fn rejects_object_identifiers_that_cannot_be_encoded() {
+        for arcs in [
+            &[3, 1][..],
+            &[7, 1],
+            &[0, 40],
+            &[1, 40, 1],
+            &[2],
+            &[],
+            // 80 + the second arc overflows
+            &[2, u128::MAX],
+        ] {
+            let oid = ASNObjectIdentifier::new(arcs.to_vec());
+            let expected = ASNErrorVariant::BadOidArcs(oid.clone());
+            assert_eq!(oid.encode().unwrap_err().variant, expected);
+
+            let value = ObjectIdentifier::asn(oid);
+            let mut out = Vec::new();
+            assert_eq!(encode(&value, &mut out).unwrap_err().variant, expected);
+            assert!(out.is_empty());
+            assert_eq!(encoded_len(&value).unwrap_err().variant, expected);
+        }
+
+        // the largest second arc that fits
+        let oid = ASNObjectIdentifier::new(vec![2, u128::MAX - 80]);
+        assert_eq!(
+            ASNObjectIdentifier::from_encoded(&oid.encode().unwrap()),
+            Ok(oid)
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use core::fmt::Write;
+
+/// Uppercase hex digits of each byte separated by colons, e.g. `0D:FA`
+///
+/// The form in which the printers show key identifiers, serial numbers, and fingerprints.
+pub fn to_hex(bytes: &[u8]) -> String {
+    write_hex(bytes, Some(':'))
+}
+
+/// Uppercase hex digits of each byte without separators, e.g. `0DFA`
+pub(crate) fn to_hex_digits(bytes: &[u8]) -> String {
+    write_hex(bytes, None)
+}
+
+fn write_hex(bytes: &[u8], separator: Option<char>) -> String {
+    let mut out = String::with_capacity(3 * bytes.len());
+    for (i, byte) in bytes.iter().enumerate() {
+        if let (Some(separator), true) = (separator, i != 0) {
+            out.push(separator);
+        }
+        let _ = write!(out, "{:02X}", byte);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separates_bytes_with_colons() {
+        assert_eq!(to_hex(&[]), "");
+        assert_eq!(to_hex(&[0x0D]), "0D");
+        assert_eq!(to_hex(&[0x0D, 0xFA, 0x00]), "0D:FA:00");
+    }
+
+    #[test]
+    fn writes_digits_without_separators() {
+        assert_eq!(to_hex_digits(&[]), "");
+        assert_eq!(to_hex_digits(&[0x0D, 0xFA, 0x00]), "0DFA00");
+    }
+}
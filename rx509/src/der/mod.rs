@@ -1,9 +1,26 @@
 mod calendar;
+/// DER encoding of values
+pub mod encode;
+mod hex;
+#[cfg(feature = "display")]
 mod oid;
+#[cfg(feature = "display")]
+mod oid_table;
+mod options;
 mod parse_all;
+mod stream;
 mod types;
+/// Incremental DER writer built on [`encode`]
+pub mod writer;
 
-pub use parse_all::{parse_all, ParseHandler};
+pub use hex::to_hex;
+pub(crate) use hex::to_hex_digits;
+#[cfg(feature = "display")]
+pub use oid_table::{OidNameTable, OidNameTableError};
+pub use options::{ParseOptions, DEFAULT_MAX_DEPTH};
+pub use parse_all::{parse_all, parse_all_with_options, DepthFirstParser, ParseHandler};
+pub use parser::Parser;
+pub use stream::{StreamError, StreamParser, StreamStatus};
 pub use types::*;
 
 pub(crate) mod parser;
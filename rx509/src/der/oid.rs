@@ -1,15 +1,49 @@
 pub enum AlgorithmID {
     Ed25519,
+    Ed448,
+    X25519,
+    X448,
     SHA1WithRSASignature,
+    SHA256WithRSASignature,
+    SHA384WithRSASignature,
+    SHA512WithRSASignature,
+    ECDSAWithSHA256,
+    ECDSAWithSHA384,
     RSAEncryption,
+    ECPublicKey,
 }
 
 impl AlgorithmID {
     pub fn to_str(&self) -> &str {
         match self {
             AlgorithmID::Ed25519 => "Ed25519 Signature",
+            AlgorithmID::Ed448 => "Ed448 Signature",
+            AlgorithmID::X25519 => "X25519 Key Agreement",
+            AlgorithmID::X448 => "X448 Key Agreement",
             AlgorithmID::SHA1WithRSASignature => "SHA1 with RSA Signature",
+            AlgorithmID::SHA256WithRSASignature => "SHA256 with RSA Signature",
+            AlgorithmID::SHA384WithRSASignature => "SHA384 with RSA Signature",
+            AlgorithmID::SHA512WithRSASignature => "SHA512 with RSA Signature",
+            AlgorithmID::ECDSAWithSHA256 => "ECDSA with SHA256 Signature",
+            AlgorithmID::ECDSAWithSHA384 => "ECDSA with SHA384 Signature",
             AlgorithmID::RSAEncryption => "RSA Encryption",
+            AlgorithmID::ECPublicKey => "EC Public Key",
+        }
+    }
+}
+
+pub enum CurveID {
+    Secp256r1,
+    Secp384r1,
+    Secp521r1,
+}
+
+impl CurveID {
+    pub fn to_str(&self) -> &str {
+        match self {
+            CurveID::Secp256r1 => "secp256r1 (P-256)",
+            CurveID::Secp384r1 => "secp384r1 (P-384)",
+            CurveID::Secp521r1 => "secp521r1 (P-521)",
         }
     }
 }
@@ -22,7 +56,9 @@ pub enum KnownOID {
     StateOrProvinceName,
     LocalityName,
     EmailAddress,
+    AnyPolicy,
     Algorithm(AlgorithmID),
+    Curve(CurveID),
 }
 
 impl KnownOID {
@@ -35,18 +71,38 @@ impl KnownOID {
             KnownOID::StateOrProvinceName => "State or Province Name",
             KnownOID::LocalityName => "Locality Name",
             KnownOID::EmailAddress => "Email Address",
+            KnownOID::AnyPolicy => "Any Policy",
             KnownOID::Algorithm(id) => id.to_str(),
+            KnownOID::Curve(id) => id.to_str(),
         }
     }
 }
 
-pub fn get_oid(id: &[u32]) -> Option<KnownOID> {
+pub fn get_oid(id: &[u128]) -> Option<KnownOID> {
     match id {
         [1, 2, 840, 113_549, 1, 1, 1] => Some(KnownOID::Algorithm(AlgorithmID::RSAEncryption)),
         [1, 2, 840, 113_549, 1, 1, 5] => {
             Some(KnownOID::Algorithm(AlgorithmID::SHA1WithRSASignature))
         }
+        [1, 2, 840, 113_549, 1, 1, 11] => {
+            Some(KnownOID::Algorithm(AlgorithmID::SHA256WithRSASignature))
+        }
+        [1, 2, 840, 113_549, 1, 1, 12] => {
+            Some(KnownOID::Algorithm(AlgorithmID::SHA384WithRSASignature))
+        }
+        [1, 2, 840, 113_549, 1, 1, 13] => {
+            Some(KnownOID::Algorithm(AlgorithmID::SHA512WithRSASignature))
+        }
+        [1, 2, 840, 10045, 2, 1] => Some(KnownOID::Algorithm(AlgorithmID::ECPublicKey)),
+        [1, 2, 840, 10045, 4, 3, 2] => Some(KnownOID::Algorithm(AlgorithmID::ECDSAWithSHA256)),
+        [1, 2, 840, 10045, 4, 3, 3] => Some(KnownOID::Algorithm(AlgorithmID::ECDSAWithSHA384)),
+        [1, 2, 840, 10045, 3, 1, 7] => Some(KnownOID::Curve(CurveID::Secp256r1)),
+        [1, 3, 132, 0, 34] => Some(KnownOID::Curve(CurveID::Secp384r1)),
+        [1, 3, 132, 0, 35] => Some(KnownOID::Curve(CurveID::Secp521r1)),
+        [1, 3, 101, 110] => Some(KnownOID::Algorithm(AlgorithmID::X25519)),
+        [1, 3, 101, 111] => Some(KnownOID::Algorithm(AlgorithmID::X448)),
         [1, 3, 101, 112] => Some(KnownOID::Algorithm(AlgorithmID::Ed25519)),
+        [1, 3, 101, 113] => Some(KnownOID::Algorithm(AlgorithmID::Ed448)),
         [2, 5, 4, 3] => Some(KnownOID::CommonName),
         [2, 5, 4, 6] => Some(KnownOID::CountryName),
         [2, 5, 4, 7] => Some(KnownOID::LocalityName),
@@ -54,6 +110,7 @@ pub fn get_oid(id: &[u32]) -> Option<KnownOID> {
         [2, 5, 4, 11] => Some(KnownOID::OrganizationalUnitName),
         [2, 5, 4, 8] => Some(KnownOID::StateOrProvinceName),
         [1, 2, 840, 113_549, 1, 9, 1] => Some(KnownOID::EmailAddress),
+        [2, 5, 29, 32, 0] => Some(KnownOID::AnyPolicy),
 
         _ => None,
     }
@@ -0,0 +1,115 @@
+use crate::der::ASNObjectIdentifier;
+
+/// User supplied friendly names for object identifiers, e.g. a private enterprise number tree
+#[derive(Debug, Default, Clone)]
+pub struct OidNameTable {
+    entries: Vec<(Vec<u128>, String)>,
+}
+
+/// A line of an OID names file that is not of the form `name = 1.2.3`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidNameTableError {
+    /// line number starting at 1
+    pub line: usize,
+}
+
+impl core::fmt::Display for OidNameTableError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "expected 'name = dotted.oid' on line {}", self.line)
+    }
+}
+
+impl std::error::Error for OidNameTableError {}
+
+impl OidNameTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load entries of the form `name = 1.2.3.4`, one per line
+    ///
+    /// Blank lines and lines starting with '#' are ignored. A later entry for the same OID
+    /// replaces an earlier one.
+    pub fn parse(text: &str) -> Result<Self, OidNameTableError> {
+        let mut table = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = OidNameTableError { line: index + 1 };
+            let (name, oid) = line.split_once('=').ok_or_else(|| error.clone())?;
+            let (name, oid) = (name.trim(), oid.trim());
+            let arcs: Vec<u128> = oid
+                .split('.')
+                .map(|arc| arc.parse::<u128>())
+                .collect::<Result<_, _>>()
+                .map_err(|_| error.clone())?;
+            if name.is_empty() || arcs.len() < 2 {
+                return Err(error);
+            }
+            table.insert(arcs, name);
+        }
+        Ok(table)
+    }
+
+    pub fn insert(&mut self, oid: Vec<u128>, name: &str) {
+        match self.entries.iter_mut().find(|(x, _)| *x == oid) {
+            Some(entry) => entry.1 = name.to_string(),
+            None => self.entries.push((oid, name.to_string())),
+        }
+    }
+
+    pub fn get(&self, oid: &[u128]) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(x, _)| x == oid)
+            .map(|(_, name)| name.as_str())
+    }
+}
+
+impl ASNObjectIdentifier<'_> {
+    /// Friendly name from `table`, falling back to the built-in names and then the dotted form
+    pub fn describe_with(&self, table: &OidNameTable) -> String {
+        match table.get(self.values()) {
+            Some(name) => name.to_string(),
+            None => self.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMES: &str = "
+        # Example Corp private arc
+        exampleDeviceRole = 1.3.6.1.4.1.99999.1
+        exampleFirmwareVersion = 1.3.6.1.4.1.99999.2
+    ";
+
+    #[test]
+    fn describes_custom_oids_before_built_in_names() {
+        let table = OidNameTable::parse(NAMES).unwrap();
+        let role = ASNObjectIdentifier::new(vec![1, 3, 6, 1, 4, 1, 99999, 1]);
+        assert_eq!(role.describe_with(&table), "exampleDeviceRole");
+
+        let unknown = ASNObjectIdentifier::new(vec![1, 3, 6, 1, 4, 1, 99999, 3]);
+        assert_eq!(unknown.describe_with(&table), "1.3.6.1.4.1.99999.3");
+
+        let common_name = ASNObjectIdentifier::new(vec![2, 5, 4, 3]);
+        assert_eq!(common_name.describe_with(&table), "Common Name");
+    }
+
+    #[test]
+    fn reports_line_of_malformed_entry() {
+        assert_eq!(
+            OidNameTable::parse("a = 1.2\nb = 1.x\n").unwrap_err(),
+            OidNameTableError { line: 2 }
+        );
+        assert_eq!(
+            OidNameTable::parse("no separator").unwrap_err(),
+            OidNameTableError { line: 1 }
+        );
+    }
+}
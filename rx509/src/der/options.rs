@@ -0,0 +1,82 @@
+/// The nesting limit of every constructor, deep enough for any certificate
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// Checks applied while decoding DER
+///
+/// The default matches the behavior of [`parse_all`](crate::der::parse_all). The `parse`
+/// functions of certificates, CRLs and requests also reject trailing data, and their
+/// `parse_with_options` functions take any other selection. Turning a flag off lets
+/// non-canonical encodings through, e.g. when recovering what can be read from a malformed file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// reject a long-form length with a leading zero byte or a value below 128
+    pub minimal_lengths: bool,
+    /// reject the BER indefinite-length form, otherwise a SEQUENCE, SET, or context-specific
+    /// tag may hold elements followed by a 00 00 end-of-contents marker
+    pub definite_lengths: bool,
+    /// reject an OID sub-identifier that starts with a 0x80 byte
+    pub minimal_oids: bool,
+    /// reject an IA5String byte above 0x7F, otherwise the contents only need to be UTF-8
+    pub ia5_character_set: bool,
+    /// reject a PrintableString character outside A-Z, a-z, 0-9, space and '()+,-./:=?,
+    /// otherwise the contents only need to be UTF-8
+    pub printable_character_set: bool,
+    /// reject any bytes following the first top-level element, otherwise the input may
+    /// hold several concatenated elements
+    pub reject_trailing_data: bool,
+    /// the number of constructed values that may enclose an element, which bounds the
+    /// recursion on crafted input
+    pub max_depth: usize,
+}
+
+impl ParseOptions {
+    /// Every check enabled
+    pub fn strict() -> Self {
+        Self {
+            minimal_lengths: true,
+            definite_lengths: true,
+            minimal_oids: true,
+            ia5_character_set: true,
+            printable_character_set: true,
+            reject_trailing_data: true,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Every check disabled, except for the nesting limit
+    pub fn lenient() -> Self {
+        Self {
+            minimal_lengths: false,
+            definite_lengths: false,
+            minimal_oids: false,
+            ia5_character_set: false,
+            printable_character_set: false,
+            reject_trailing_data: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl ParseOptions {
+    // the checks of the certificate, CRL and request parsers that expect a single value
+    pub(crate) fn default_single_value() -> Self {
+        Self {
+            reject_trailing_data: true,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            minimal_lengths: true,
+            definite_lengths: true,
+            minimal_oids: true,
+            ia5_character_set: true,
+            printable_character_set: false,
+            reject_trailing_data: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
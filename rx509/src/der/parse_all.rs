@@ -1,3 +1,4 @@
+use crate::der::options::ParseOptions;
 use crate::der::parser::Parser;
 use crate::der::types::{ASNError, ASNType};
 
@@ -9,43 +10,146 @@ pub trait ParseHandler {
 }
 
 pub fn parse_all(input: &[u8], handler: &mut dyn ParseHandler) -> Result<(), ASNError> {
-    for result in Parser::new(input) {
+    parse_all_with_options(input, ParseOptions::default(), handler)
+}
+
+/// Like [`parse_all`], but applying the checks selected by `options`
+pub fn parse_all_with_options(
+    input: &[u8],
+    options: ParseOptions,
+    handler: &mut dyn ParseHandler,
+) -> Result<(), ASNError> {
+    match Parser::new_non_empty(input) {
+        Ok(_) => parse_each(input, Parser::with_options(input, options), true, handler),
+        Err(err) => Err(report(input, err, handler)),
+    }
+}
+
+// constructed values may legitimately be empty, so only the top level is checked
+fn parse_each(
+    input: &[u8],
+    mut parser: Parser,
+    top_level: bool,
+    handler: &mut dyn ParseHandler,
+) -> Result<(), ASNError> {
+    let options = parser.options();
+    while let Some(result) = parser.next_element() {
         match result {
-            Err(err) => {
-                let err = err.into();
-                handler.on_error(&err);
-                return Err(err);
-            }
+            Err(err) => return Err(report(input, err, handler)),
             Ok(asn) => {
                 handler.on_type(&asn);
-                match asn {
-                    ASNType::Sequence(wrapper) => {
-                        handler.begin_constructed();
-                        parse_all(wrapper.value, handler)?;
-                        handler.end_constructed();
-                    }
-                    ASNType::ExplicitTag(wrapper) => {
-                        handler.begin_constructed();
-                        parse_all(wrapper.value.contents, handler)?;
-                        handler.end_constructed();
-                    }
-                    ASNType::Set(wrapper) => {
-                        handler.begin_constructed();
-                        parse_all(wrapper.value, handler)?;
-                        handler.end_constructed();
-                    }
-                    _ => (),
+                let contents = match asn {
+                    ASNType::Sequence(wrapper) => Some(wrapper.value),
+                    ASNType::ExplicitTag(wrapper) => Some(wrapper.value.contents),
+                    ASNType::Set(wrapper) => Some(wrapper.value),
+                    _ => None,
+                };
+                if let Some(contents) = contents {
+                    let nested = parser
+                        .nested(contents)
+                        .map_err(|err| report(input, ASNError::at(err, contents), handler))?;
+                    handler.begin_constructed();
+                    parse_each(input, nested, false, handler)?;
+                    handler.end_constructed();
                 }
             }
         }
+
+        if top_level && options.reject_trailing_data {
+            return parser
+                .expect_end()
+                .map_err(|err| report(input, err, handler));
+        }
     }
 
     Ok(())
 }
 
+fn report(input: &[u8], err: ASNError, handler: &mut dyn ParseHandler) -> ASNError {
+    let err = err.relative_to(input);
+    handler.on_error(&err);
+    err
+}
+
+/// Iterates every element of the input in depth-first order along with its nesting depth
+///
+/// A pull-based alternative to [`parse_all`]. The contents of SEQUENCE, SET and context-specific
+/// tags are descended into automatically, right after the element itself is yielded. Top-level
+/// elements have a depth of 0. An error is always the last item and its offset is relative to
+/// the input.
+pub struct DepthFirstParser<'a> {
+    input: &'a [u8],
+    // parsers of the enclosing constructed values, the last one being the innermost
+    stack: Vec<Parser<'a>>,
+    // an error to yield before anything else, i.e. an empty input or a too deep element
+    error: Option<ASNError>,
+}
+
+impl<'a> DepthFirstParser<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        match Parser::new_non_empty(input) {
+            Ok(parser) => Self {
+                input,
+                stack: vec![parser],
+                error: None,
+            },
+            Err(err) => Self {
+                input,
+                stack: Vec::new(),
+                error: Some(err.relative_to(input)),
+            },
+        }
+    }
+}
+
+impl<'a> Iterator for DepthFirstParser<'a> {
+    type Item = Result<(usize, ASNType<'a>), ASNError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.error.take() {
+            return Some(Err(err));
+        }
+
+        loop {
+            let depth = self.stack.len().checked_sub(1)?;
+            match self.stack.last_mut()?.next_element() {
+                None => {
+                    self.stack.pop();
+                }
+                Some(Err(err)) => {
+                    self.stack.clear();
+                    return Some(Err(err.relative_to(self.input)));
+                }
+                Some(Ok(asn)) => {
+                    let contents = match &asn {
+                        ASNType::Sequence(wrapper) => Some(wrapper.value),
+                        ASNType::ExplicitTag(wrapper) => Some(wrapper.value.contents),
+                        ASNType::Set(wrapper) => Some(wrapper.value),
+                        _ => None,
+                    };
+                    if let Some(contents) = contents {
+                        match self.stack.last().map(|parser| parser.nested(contents)) {
+                            Some(Ok(parser)) => self.stack.push(parser),
+                            // the element itself is still yielded, the error comes next
+                            Some(Err(err)) => {
+                                self.stack.clear();
+                                self.error =
+                                    Some(ASNError::at(err, contents).relative_to(self.input));
+                            }
+                            None => {}
+                        }
+                    }
+                    return Some(Ok((depth, asn)));
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::der::DEFAULT_MAX_DEPTH;
 
     struct MockHandler {}
 
@@ -59,6 +163,127 @@ mod tests {
         fn on_error(&mut self, _: &ASNError) {}
     }
 
+    #[test]
+    fn rejects_empty_input() {
+        let err = parse_all(&[], &mut MockHandler {}).unwrap_err();
+        assert_eq!(err.variant, crate::der::ASNErrorVariant::EmptyInput);
+    }
+
+    #[test]
+    fn reports_offset_of_truncated_element() {
+        // SEQUENCE { INTEGER 5, SEQUENCE { INTEGER with no content byte } }
+        let err = parse_all(
+            &[0x30, 0x07, 0x02, 0x01, 0x05, 0x30, 0x02, 0x02, 0x01],
+            &mut MockHandler {},
+        )
+        .unwrap_err();
+        assert_eq!(err.variant, crate::der::ASNErrorVariant::EndOfStream);
+        assert_eq!(err.offset(), Some(7));
+        assert_eq!(
+            err.to_string(),
+            "Consumed all input before parsing required fields at byte 7"
+        );
+    }
+
+    #[test]
+    fn rejects_trailing_data_only_when_asked() {
+        // INTEGER 5 followed by NULL
+        let input = [0x02, 0x01, 0x05, 0x05, 0x00];
+        parse_all(&input, &mut MockHandler {}).unwrap();
+
+        let err = parse_all_with_options(&input, ParseOptions::strict(), &mut MockHandler {})
+            .unwrap_err();
+        assert_eq!(
+            err.variant,
+            crate::der::ASNErrorVariant::ExpectedEnd(crate::der::ASNTypeId::Null)
+        );
+        assert_eq!(err.offset(), Some(3));
+    }
+
+    // SEQUENCE { SEQUENCE { ... SEQUENCE {} } }
+    fn nested_sequences(count: usize) -> Vec<u8> {
+        let mut der = Vec::new();
+        for _ in 0..count {
+            let mut outer = Vec::new();
+            crate::der::encode::encode(&crate::der::Sequence::asn(&der), &mut outer).unwrap();
+            der = outer;
+        }
+        der
+    }
+
+    #[test]
+    fn limits_the_nesting_depth() {
+        parse_all(&nested_sequences(DEFAULT_MAX_DEPTH), &mut MockHandler {}).unwrap();
+
+        let input = nested_sequences(DEFAULT_MAX_DEPTH + 1);
+        let err = parse_all(&input, &mut MockHandler {}).unwrap_err();
+        assert_eq!(
+            err.variant,
+            crate::der::ASNErrorVariant::MaxDepthExceeded(DEFAULT_MAX_DEPTH)
+        );
+        // the contents of the innermost SEQUENCE
+        assert_eq!(err.offset(), Some(input.len()));
+
+        // far deeper than the stack would allow without a limit
+        let err = parse_all(&nested_sequences(1000), &mut MockHandler {}).unwrap_err();
+        assert_eq!(
+            err.variant,
+            crate::der::ASNErrorVariant::MaxDepthExceeded(DEFAULT_MAX_DEPTH)
+        );
+
+        let items: Vec<_> = DepthFirstParser::new(&input).collect();
+        assert_eq!(items.len(), DEFAULT_MAX_DEPTH + 2);
+        assert!(matches!(
+            items[DEFAULT_MAX_DEPTH],
+            Ok((DEFAULT_MAX_DEPTH, ASNType::Sequence(_)))
+        ));
+        assert!(items[DEFAULT_MAX_DEPTH + 1].is_err());
+    }
+
+    #[test]
+    fn walks_elements_with_their_depth() {
+        let mut walk =
+            DepthFirstParser::new(include_bytes!("../../../certs/512b-rsa-example-cert.der"));
+        assert!(matches!(walk.next(), Some(Ok((0, ASNType::Sequence(_))))));
+        assert!(matches!(walk.next(), Some(Ok((1, ASNType::Sequence(_))))));
+        // the serial number, as a v1 certificate has no version
+        match walk.next() {
+            Some(Ok((depth, ASNType::Integer(serial)))) => {
+                assert_eq!(depth, 2);
+                assert_eq!(serial.value.bytes, [0x0D, 0xFA]);
+            }
+            other => panic!("unexpected item: {:?}", other),
+        }
+        assert!(walk.all(|item| item.is_ok()));
+    }
+
+    #[test]
+    fn ends_the_walk_with_the_first_error() {
+        // SEQUENCE { INTEGER 5, SEQUENCE { INTEGER with no content byte } }, INTEGER 6
+        let input = [
+            0x30, 0x07, 0x02, 0x01, 0x05, 0x30, 0x02, 0x02, 0x01, 0x02, 0x01, 0x06,
+        ];
+        let items: Vec<_> = DepthFirstParser::new(&input).collect();
+        assert_eq!(items.len(), 4);
+        assert!(matches!(items[2], Ok((1, ASNType::Sequence(_)))));
+        match &items[3] {
+            Err(err) => {
+                assert_eq!(err.variant, crate::der::ASNErrorVariant::EndOfStream);
+                assert_eq!(err.offset(), Some(7));
+            }
+            other => panic!("unexpected item: {:?}", other),
+        }
+
+        let mut walk = DepthFirstParser::new(&[]);
+        assert!(matches!(walk.next(), Some(Err(_))));
+        assert!(walk.next().is_none());
+    }
+
+    #[test]
+    fn accepts_empty_nested_sequence() {
+        parse_all(&[0x30, 0x00], &mut MockHandler {}).unwrap();
+    }
+
     #[test]
     fn parses_rsa_x509_without_error() {
         // just checking that an error doesn't occur
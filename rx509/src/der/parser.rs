@@ -1,21 +1,32 @@
 use core::str;
 
 use crate::der::calendar;
+use crate::der::options::ParseOptions;
 use crate::der::reader::Reader;
 use crate::der::types::ASNErrorVariant;
 use crate::der::types::*;
 
+#[cfg(feature = "arena")]
+use bumpalo::Bump;
+
+// stands in for the arena, which no parser has without the arena feature
+#[cfg(not(feature = "arena"))]
+enum Bump {}
+
 type ASNResult<'a> = Result<ASNType<'a>, ASNErrorVariant>;
 
-fn parse_seq(contents: &[u8]) -> ASNResult {
+// a parsed value along with its complete encoding
+type WithDer<'a, T> = (T, &'a [u8]);
+
+fn parse_seq(contents: &[u8]) -> ASNResult<'_> {
     Ok(Sequence::asn(contents))
 }
 
-fn parse_set(contents: &[u8]) -> ASNResult {
+fn parse_set(contents: &[u8]) -> ASNResult<'_> {
     Ok(Set::asn(contents))
 }
 
-fn parse_null(contents: &[u8]) -> ASNResult {
+fn parse_null(contents: &[u8]) -> ASNResult<'_> {
     if contents.is_empty() {
         Ok(ASNType::Null)
     } else {
@@ -23,7 +34,7 @@ fn parse_null(contents: &[u8]) -> ASNResult {
     }
 }
 
-fn parse_boolean(contents: &[u8]) -> ASNResult {
+fn parse_boolean(contents: &[u8]) -> ASNResult<'_> {
     match contents {
         [0xFF] => Ok(Boolean::asn(true)),
         [0x00] => Ok(Boolean::asn(false)),
@@ -32,7 +43,7 @@ fn parse_boolean(contents: &[u8]) -> ASNResult {
     }
 }
 
-fn parse_integer(contents: &[u8]) -> ASNResult {
+fn parse_integer(contents: &[u8]) -> ASNResult<'_> {
     if contents.is_empty() {
         Err(ASNErrorVariant::ZeroLengthInteger)
     } else {
@@ -46,11 +57,11 @@ enum TimeType {
     Generalized,
 }
 
-fn parse_utc_time(contents: &[u8]) -> ASNResult {
+fn parse_utc_time(contents: &[u8]) -> ASNResult<'_> {
     parse_time(contents, TimeType::Utc)
 }
 
-fn parse_generalized_time(contents: &[u8]) -> ASNResult {
+fn parse_generalized_time(contents: &[u8]) -> ASNResult<'_> {
     parse_time(contents, TimeType::Generalized)
 }
 
@@ -58,6 +69,7 @@ impl TimeType {
     fn parse_year(self, reader: &mut Reader) -> Result<u64, ASNErrorVariant> {
         let (year_hi, year_lo) = match self {
             TimeType::Utc => {
+                // RFC 5280 4.1.2.5.1, 50 through 99 are 19YY and 00 through 49 are 20YY
                 let lo = read_two_digits(reader, 0, 99)?;
                 let hi = if lo >= 50 { 19 } else { 20 };
                 (hi, lo)
@@ -91,7 +103,7 @@ fn read_two_digits(inner: &mut Reader, min: u64, max: u64) -> Result<u64, ASNErr
     Ok(value)
 }
 
-fn parse_time(contents: &[u8], time_type: TimeType) -> ASNResult {
+fn parse_time(contents: &[u8], time_type: TimeType) -> ASNResult<'_> {
     // This code is highly inspired from webpki available here:
     // https://github.com/briansmith/webpki/blob/18cda8a5e32dfc2723930018853a984bd634e667/src/der.rs#L113-L166
 
@@ -127,25 +139,118 @@ fn parse_time(contents: &[u8], time_type: TimeType) -> ASNResult {
     let minutes = read_two_digits(&mut reader, 0, 59)?;
     let seconds = read_two_digits(&mut reader, 0, 59)?;
 
-    let time_zone = reader
-        .read_byte()
-        .map_err(|_| ASNErrorVariant::BadUTCTime)?;
-    if time_zone != b'Z' {
-        return Err(ASNErrorVariant::BadUTCTime);
+    let mut next = read_time_byte(&mut reader)?;
+
+    // GeneralizedTime may carry a fraction of a second which is truncated
+    if time_type == TimeType::Generalized && (next == b'.' || next == b',') {
+        next = read_time_byte(&mut reader)?;
+        if !next.is_ascii_digit() {
+            return Err(ASNErrorVariant::BadUTCTime);
+        }
+        while next.is_ascii_digit() {
+            next = read_time_byte(&mut reader)?;
+        }
+    }
+
+    let time = calendar::time_from_ymdhms_utc(year, month, day_of_month, hours, minutes, seconds)?;
+
+    let time = match next {
+        b'Z' => time,
+        // GeneralizedTime may be local time with a differential from UTC
+        b'+' | b'-' if time_type == TimeType::Generalized => {
+            let offset_hours = read_two_digits(&mut reader, 0, 23)?;
+            let offset_minutes = read_two_digits(&mut reader, 0, 59)?;
+            let offset = (offset_hours * 60 + offset_minutes) * 60;
+            let value = if next == b'+' {
+                time.value.checked_sub(offset)
+            } else {
+                time.value.checked_add(offset)
+            };
+            UtcTime::from_seconds_since_epoch(value.ok_or(ASNErrorVariant::BadUTCTime)?)
+        }
+        _ => return Err(ASNErrorVariant::BadUTCTime),
+    };
+
+    Ok(match time_type {
+        TimeType::Utc => ASNType::UTCTime(time),
+        TimeType::Generalized => ASNType::GeneralizedTime(time),
+    })
+}
+
+// big-endian UCS-2, also accepting the surrogate pairs that some encoders emit
+fn parse_bmp_string<'a>(contents: &[u8]) -> ASNResult<'a> {
+    let chunks = contents.chunks_exact(2);
+    if !chunks.remainder().is_empty() {
+        return Err(ASNErrorVariant::BadBMPString);
     }
 
-    calendar::time_from_ymdhms_utc(year, month, day_of_month, hours, minutes, seconds)
-        .map(ASNType::UTCTime)
+    let code_units = chunks.map(|x| u16::from_be_bytes([x[0], x[1]]));
+    let value = char::decode_utf16(code_units)
+        .collect::<Result<String, _>>()
+        .map_err(|_| ASNErrorVariant::BadBMPString)?;
+
+    Ok(BMPString::asn(value))
 }
 
-fn parse_string<T: Fn(&str) -> ASNType>(contents: &[u8], create: T) -> ASNResult {
+// each byte is taken as its Latin-1 code point, ignoring the T.61 escape sequences
+fn parse_teletex_string<'a>(contents: &[u8]) -> ASNResult<'a> {
+    Ok(TeletexString::asn(
+        contents.iter().map(|x| char::from(*x)).collect(),
+    ))
+}
+
+fn read_time_byte(reader: &mut Reader) -> Result<u8, ASNErrorVariant> {
+    reader.read_byte().map_err(|_| ASNErrorVariant::BadUTCTime)
+}
+
+fn parse_string<T: Fn(&str) -> ASNType<'_>>(contents: &[u8], create: T) -> ASNResult<'_> {
     match str::from_utf8(contents) {
         Ok(x) => Ok(create(x)),
         Err(x) => Err(ASNErrorVariant::BadUTF8(x)),
     }
 }
 
-fn parse_bit_string(contents: &[u8]) -> ASNResult {
+// IA5 is 7-bit ASCII, a subset of UTF-8
+fn parse_ia5_string(contents: &[u8]) -> ASNResult<'_> {
+    if let Some(byte) = contents.iter().find(|x| !x.is_ascii()) {
+        return Err(ASNErrorVariant::NonAsciiIA5String(*byte));
+    }
+    parse_string(contents, |s| IA5String::asn(s))
+}
+
+// letters, digits, space and '()+,-./:=?
+fn is_printable_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b" '()+,-./:=?".contains(&byte)
+}
+
+/// PrintableString contents restricted to its character set, which the generic parser
+/// doesn't enforce so that names written by non-conforming CAs still decode
+pub(crate) fn parse_printable_string(contents: &[u8]) -> ASNResult<'_> {
+    if let Some(byte) = contents.iter().find(|x| !is_printable_char(**x)) {
+        return Err(ASNErrorVariant::BadPrintableChar(*byte));
+    }
+    parse_string(contents, |s| PrintableString::asn(s))
+}
+
+fn parse_numeric_string(contents: &[u8]) -> ASNResult<'_> {
+    if let Some(byte) = contents
+        .iter()
+        .find(|x| !(x.is_ascii_digit() || **x == b' '))
+    {
+        return Err(ASNErrorVariant::BadNumericChar(*byte));
+    }
+    parse_string(contents, |s| NumericString::asn(s))
+}
+
+// ISO 646 graphic characters and space, i.e. printable ASCII
+fn parse_visible_string(contents: &[u8]) -> ASNResult<'_> {
+    if let Some(byte) = contents.iter().find(|x| !(0x20..=0x7E).contains(*x)) {
+        return Err(ASNErrorVariant::BadVisibleChar(*byte));
+    }
+    parse_string(contents, |s| VisibleString::asn(s))
+}
+
+fn parse_bit_string(contents: &[u8]) -> ASNResult<'_> {
     if contents.is_empty() {
         return Err(ASNErrorVariant::EndOfStream);
     }
@@ -155,54 +260,130 @@ fn parse_bit_string(contents: &[u8]) -> ASNResult {
         return Err(ASNErrorVariant::BitStringUnusedBitsTooLarge(unused_bits));
     }
 
+    // an empty bit string has no final octet in which bits could be unused
+    if contents.len() == 1 && unused_bits != 0 {
+        return Err(ASNErrorVariant::EmptyBitStringWithUnusedBits(unused_bits));
+    }
+
     Ok(BitString::asn(ASNBitString::new(
         unused_bits,
         &contents[1..],
     )))
 }
 
-fn parse_object_identifier(contents: &[u8]) -> ASNResult {
-    fn parse_one(reader: &mut Reader) -> Result<u32, ASNErrorVariant> {
-        let mut sum: u32 = 0;
-        let mut count: u32 = 0;
+#[cfg_attr(not(feature = "arena"), allow(unused_variables))]
+fn parse_object_identifier<'a>(
+    contents: &'a [u8],
+    options: ParseOptions,
+    arena: Option<&'a Bump>,
+) -> ASNResult<'a> {
+    let mut arcs = EncodedArcs::new(contents);
+    arcs.minimal = options.minimal_oids;
+    #[cfg(feature = "arena")]
+    if let Some(arena) = arena {
+        let mut items = bumpalo::collections::Vec::new_in(arena);
+        for arc in arcs {
+            items.push(arc?);
+        }
+        return Ok(ObjectIdentifier::asn(ASNObjectIdentifier::from_slice(
+            items.into_bump_slice(),
+        )));
+    }
+    let items = arcs.collect::<Result<Vec<u128>, _>>()?;
+    Ok(ObjectIdentifier::asn(ASNObjectIdentifier::new(items)))
+}
+
+/// Arcs of the contents of an encoded OBJECT IDENTIFIER, decoded lazily
+///
+/// Iteration ends after the first error.
+pub(crate) struct EncodedArcs<'a> {
+    reader: Reader<'a>,
+    started: bool,
+    // the second arc is decoded from the leading byte along with the first
+    second: Option<u128>,
+    // reject sub-identifiers padded with a leading 0x80 byte
+    minimal: bool,
+}
+
+impl<'a> EncodedArcs<'a> {
+    pub(crate) fn new(contents: &'a [u8]) -> Self {
+        Self {
+            reader: Reader::new(contents),
+            started: false,
+            second: None,
+            minimal: true,
+        }
+    }
+
+    fn parse_one(&mut self) -> Result<u128, ASNErrorVariant> {
+        let mut sum: u128 = 0;
+        let mut first = true;
         loop {
-            // only allow 4*7 = 28 bits so that we don't overflow u32
-            if count > 3 {
+            // the arc must fit in 128 bits
+            if sum > (u128::MAX >> 7) {
                 return Err(ASNErrorVariant::BadOidLength);
             };
 
-            let next_byte = reader.read_byte()?;
+            let next_byte = self.reader.read_byte()?;
+            // a leading 0x80 is a redundant zero group, which DER forbids
+            if self.minimal && first && next_byte == 0b1000_0000 {
+                return Err(ASNErrorVariant::NonMinimalOid);
+            }
+            first = false;
             let has_next: bool = (next_byte & 0b1000_0000) != 0;
-            let value: u32 = (next_byte & 0b0111_1111) as u32;
+            let value = u128::from(next_byte & 0b0111_1111);
 
             sum <<= 7;
             sum += value;
 
-            count += 1;
-
             if !has_next {
                 return Ok(sum);
             }
         }
     }
+}
 
-    let mut reader = Reader::new(contents);
+impl<'a> Iterator for EncodedArcs<'a> {
+    type Item = Result<u128, ASNErrorVariant>;
 
-    let mut items: Vec<u32> = Vec::new();
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            // X.690 8.19.4, only joint-iso-itu-t (2) may have a second arc of 40 or more, so
+            // the first sub-identifier can span several bytes
+            let first = match self.parse_one() {
+                Ok(first) => first,
+                Err(err) => {
+                    self.reader.clear();
+                    return Some(Err(err));
+                }
+            };
+            let (first, second) = match first {
+                0..=39 => (0, first),
+                40..=79 => (1, first - 40),
+                _ => (2, first - 80),
+            };
+            self.second = Some(second);
+            return Some(Ok(first));
+        }
 
-    let first_byte = reader.read_byte()?;
+        if let Some(second) = self.second.take() {
+            return Some(Ok(second));
+        }
 
-    items.push((first_byte / 40) as u32);
-    items.push((first_byte % 40) as u32);
+        if self.reader.is_empty() {
+            return None;
+        }
 
-    while !reader.is_empty() {
-        items.push(parse_one(&mut reader)?);
+        let result = self.parse_one();
+        if result.is_err() {
+            self.reader.clear();
+        }
+        Some(result)
     }
-
-    Ok(ObjectIdentifier::asn(ASNObjectIdentifier::new(items)))
 }
 
-fn parse_length(reader: &mut Reader) -> Result<usize, ASNErrorVariant> {
+fn parse_length(reader: &mut Reader, options: ParseOptions) -> Result<usize, ASNErrorVariant> {
     let first_byte = reader.read_byte()?;
 
     let top_bit = first_byte & 0b1000_0000;
@@ -212,47 +393,76 @@ fn parse_length(reader: &mut Reader) -> Result<usize, ASNErrorVariant> {
         return Ok(count_of_bytes as usize);
     }
 
-    // DER only allows a single encoding for any particular
-    // value. For a given count of bytes, the encoded
-    // length must fit into the minimum length representation
-    let min_value_for_count: u64 = match count_of_bytes {
+    match count_of_bytes {
         0 => return Err(ASNErrorVariant::UnsupportedIndefiniteLength),
         127 => return Err(ASNErrorVariant::ReservedLengthValue),
-        // anything < these numbers indicate the value should have been encoded with fewer bytes
-        1 => 128,
-        2 => 256,
-        3 => 65536,
-        4 => 16777216,
+        // the value is accumulated in a u64
+        1..=8 => {}
         _ => return Err(ASNErrorVariant::UnsupportedLengthByteCount(count_of_bytes)),
-    };
+    }
 
-    let mut value: usize = 0;
+    let mut value: u64 = 0;
 
-    for _ in 0..count_of_bytes {
+    for i in 0..count_of_bytes {
+        let byte = reader.read_byte()?;
+        // DER requires the minimum number of length bytes, so no zero padding
+        if options.minimal_lengths && i == 0 && byte == 0 && count_of_bytes > 1 {
+            return Err(ASNErrorVariant::NonMinimalLength);
+        }
         value <<= 8;
-        value |= reader.read_byte()? as usize;
+        value |= byte as u64;
     }
 
-    if (value as u64) < min_value_for_count {
-        return Err(ASNErrorVariant::BadLengthEncoding(count_of_bytes, value));
+    let length = narrow_length::<usize>(value)?;
+
+    // DER only allows a single encoding for any particular value,
+    // so values < 128 must use the short form
+    if options.minimal_lengths && value < 128 {
+        return Err(ASNErrorVariant::BadLengthEncoding(count_of_bytes, length));
     }
 
-    Ok(value)
+    Ok(length)
+}
+
+// the length may not fit on targets with a narrow usize, generic so that the failure can be
+// tested on any target
+fn narrow_length<T: TryFrom<u64>>(value: u64) -> Result<T, ASNErrorVariant> {
+    T::try_from(value).map_err(|_| ASNErrorVariant::LengthExceedsPlatform(value))
 }
 
-fn parse_one_type<'a>(reader: &mut Reader<'a>) -> ASNResult<'a> {
-    let id = Identifier::from(reader.read_byte()?);
+fn parse_one_type<'a>(
+    reader: &mut Reader<'a>,
+    options: ParseOptions,
+    arena: Option<&'a Bump>,
+) -> ASNResult<'a> {
+    let id = Identifier::read(reader)?;
 
     match read_type(&id) {
         Some((asn_type, tag)) => {
-            let contents = get_contents(reader)?;
-            parse_content(&asn_type, tag, contents)
+            let contents = get_contents(reader, &id, options)?;
+            parse_content(&asn_type, tag, contents, options, arena)
+        }
+        None if has_wrong_encoding_form(&id) => {
+            Err(ASNErrorVariant::PrimitiveConstructedTypeMismatch(id))
         }
         None => Err(ASNErrorVariant::UnsupportedId(id)),
     }
 }
 
-fn read_type(id: &Identifier) -> Option<(ASNTypeId, u8)> {
+// a supported universal type whose primitive/constructed bit is the opposite of what DER requires
+fn has_wrong_encoding_form(id: &Identifier) -> bool {
+    let flipped = Identifier::new(
+        TagClass::Universal,
+        match id.pc {
+            PC::Primitive => PC::Constructed,
+            PC::Constructed => PC::Primitive,
+        },
+        id.tag,
+    );
+    id.class == TagClass::Universal && read_type(&flipped).is_some()
+}
+
+fn read_type(id: &Identifier) -> Option<(ASNTypeId, u32)> {
     match id {
         Identifier {
             class: TagClass::Universal,
@@ -266,7 +476,11 @@ fn read_type(id: &Identifier) -> Option<(ASNTypeId, u8)> {
             0x05 => Some((ASNTypeId::Null, *tag)),
             0x06 => Some((ASNTypeId::ObjectIdentifier, *tag)),
             0x0C => Some((ASNTypeId::UTF8String, *tag)),
+            0x12 => Some((ASNTypeId::NumericString, *tag)),
+            0x1A => Some((ASNTypeId::VisibleString, *tag)),
+            0x1E => Some((ASNTypeId::BMPString, *tag)),
             0x13 => Some((ASNTypeId::PrintableString, *tag)),
+            0x14 => Some((ASNTypeId::TeletexString, *tag)),
             0x16 => Some((ASNTypeId::IA5String, *tag)),
             0x17 => Some((ASNTypeId::UTCTime, *tag)),
             0x18 => Some((ASNTypeId::GeneralizedTime, *tag)),
@@ -294,22 +508,88 @@ fn read_type(id: &Identifier) -> Option<(ASNTypeId, u8)> {
     }
 }
 
-fn get_contents<'a>(reader: &mut Reader<'a>) -> Result<&'a [u8], ASNErrorVariant> {
-    let length = parse_length(reader)?;
+/// The length of the first element of `input`, identifier and length octets included
+///
+/// Only the header is decoded, along with the headers nested in an indefinite-length value, so
+/// `EndOfStream` means that the input ends before the element does.
+pub(crate) fn element_len(input: &[u8], options: ParseOptions) -> Result<usize, ASNErrorVariant> {
+    let mut reader = Reader::new(input);
+    let id = Identifier::read(&mut reader)?;
+    get_contents(&mut reader, &id, options)?;
+    Ok(input.len() - reader.remainder().len())
+}
+
+fn get_contents<'a>(
+    reader: &mut Reader<'a>,
+    id: &Identifier,
+    options: ParseOptions,
+) -> Result<&'a [u8], ASNErrorVariant> {
+    get_nested_contents(reader, id, options, 0)
+}
+
+// the depth counts the indefinite-length values being walked, which recurse
+fn get_nested_contents<'a>(
+    reader: &mut Reader<'a>,
+    id: &Identifier,
+    options: ParseOptions,
+    depth: usize,
+) -> Result<&'a [u8], ASNErrorVariant> {
+    // BER only allows the indefinite form for constructed values
+    if !options.definite_lengths && id.pc == PC::Constructed {
+        let mut peek = *reader;
+        if let Ok(0x80) = peek.read_byte() {
+            *reader = peek;
+            return take_indefinite_contents(reader, options, depth);
+        }
+    }
+    let length = parse_length(reader, options)?;
     Ok(reader.take(length)?)
 }
 
-fn parse_content<'a>(type_id: &ASNTypeId, tag: u8, contents: &'a [u8]) -> ASNResult<'a> {
+// X.690 8.1.3.6, elements up to an end-of-contents marker of two zero bytes, which is consumed
+fn take_indefinite_contents<'a>(
+    reader: &mut Reader<'a>,
+    options: ParseOptions,
+    depth: usize,
+) -> Result<&'a [u8], ASNErrorVariant> {
+    if depth >= options.max_depth {
+        return Err(ASNErrorVariant::MaxDepthExceeded(options.max_depth));
+    }
+    let start = reader.remainder();
+    while !reader.remainder().starts_with(&[0x00, 0x00]) {
+        let id = Identifier::read(reader)?;
+        get_nested_contents(reader, &id, options, depth + 1)?;
+    }
+    let contents = &start[..start.len() - reader.remainder().len()];
+    reader.take(2)?;
+    Ok(contents)
+}
+
+fn parse_content<'a>(
+    type_id: &ASNTypeId,
+    tag: u32,
+    contents: &'a [u8],
+    options: ParseOptions,
+    arena: Option<&'a Bump>,
+) -> ASNResult<'a> {
     match type_id {
         ASNTypeId::Boolean => parse_boolean(contents),
         ASNTypeId::Integer => parse_integer(contents),
         ASNTypeId::BitString => parse_bit_string(contents),
         ASNTypeId::OctetString => Ok(OctetString::asn(contents)),
         ASNTypeId::Null => parse_null(contents),
-        ASNTypeId::ObjectIdentifier => parse_object_identifier(contents),
+        ASNTypeId::ObjectIdentifier => parse_object_identifier(contents, options, arena),
         ASNTypeId::UTF8String => parse_string(contents, |s| UTF8String::asn(s)),
+        ASNTypeId::BMPString => parse_bmp_string(contents),
+        ASNTypeId::TeletexString => parse_teletex_string(contents),
+        ASNTypeId::PrintableString if options.printable_character_set => {
+            parse_printable_string(contents)
+        }
         ASNTypeId::PrintableString => parse_string(contents, |s| PrintableString::asn(s)),
+        ASNTypeId::IA5String if options.ia5_character_set => parse_ia5_string(contents),
         ASNTypeId::IA5String => parse_string(contents, |s| IA5String::asn(s)),
+        ASNTypeId::NumericString => parse_numeric_string(contents),
+        ASNTypeId::VisibleString => parse_visible_string(contents),
         ASNTypeId::UTCTime => parse_utc_time(contents),
         ASNTypeId::GeneralizedTime => parse_generalized_time(contents),
 
@@ -320,14 +600,26 @@ fn parse_content<'a>(type_id: &ASNTypeId, tag: u8, contents: &'a [u8]) -> ASNRes
     }
 }
 
-pub(crate) struct Parser<'a> {
+/// Reads the DER elements of an input one at a time
+///
+/// The contents of a constructed element are read by a nested parser, e.g. from
+/// [`Parser::expect_sequence_contents`]. Elements of unknown types can be inspected with
+/// [`Parser::peek_id`] and passed over with [`Parser::skip`], which allows forward-compatible
+/// parsing of extensible SEQUENCEs.
+pub struct Parser<'a> {
     reader: Reader<'a>,
+    // inherited by the parsers of constructed contents
+    options: ParseOptions,
+    // the number of constructed values enclosing the input
+    depth: usize,
+    // holds the arcs of the OIDs read by this parser and the parsers of constructed contents
+    arena: Option<&'a Bump>,
 }
 
 impl<'a> Parser<'a> {
-    pub(crate) fn parse_all<'b, T: 'b, F>(input: &'b [u8], parse: F) -> Result<T, ASNErrorVariant>
+    pub(crate) fn parse_all<'b, T: 'b, F>(input: &'b [u8], parse: F) -> Result<T, ASNError>
     where
-        F: FnOnce(&mut Parser<'b>) -> Result<T, ASNErrorVariant>,
+        F: FnOnce(&mut Parser<'b>) -> Result<T, ASNError>,
     {
         let mut parser = Parser::new(input);
         let value = parse(&mut parser)?;
@@ -335,54 +627,259 @@ impl<'a> Parser<'a> {
         Ok(value)
     }
 
-    pub(crate) fn new(input: &'a [u8]) -> Parser {
+    /// Parser over the complete input of a top-level entry point, which must not be empty
+    ///
+    /// Distinguishes an empty input from one that ends partway through a value.
+    pub(crate) fn new_non_empty(input: &'a [u8]) -> Result<Parser<'a>, ASNError> {
+        Parser::non_empty_with_options(input, ParseOptions::default())
+    }
+
+    /// Like [`Parser::new_non_empty`], but applying the checks selected by `options`
+    pub(crate) fn non_empty_with_options(
+        input: &'a [u8],
+        options: ParseOptions,
+    ) -> Result<Parser<'a>, ASNError> {
+        if input.is_empty() {
+            return Err(ASNError::at(ASNErrorVariant::EmptyInput, input));
+        }
+        Ok(Parser::with_options(input, options))
+    }
+
+    /// Parser over `input` with the default checks
+    pub fn new(input: &'a [u8]) -> Parser<'a> {
+        Parser::with_options(input, ParseOptions::default())
+    }
+
+    /// Parser over `input` applying the checks selected by `options`, which are inherited by
+    /// the parsers of constructed contents
+    pub fn with_options(input: &'a [u8], options: ParseOptions) -> Parser<'a> {
         Parser {
             reader: Reader::new(input),
+            options,
+            depth: 0,
+            arena: None,
         }
     }
 
-    pub(crate) fn unwrap_outer_sequence(input: &'a [u8]) -> Result<Parser, ASNErrorVariant> {
-        let mut parser = Parser::new(input);
-        let bytes = parser.expect::<Sequence>()?;
+    /// Parser whose OIDs are decoded into `arena` instead of the heap
+    #[cfg(feature = "arena")]
+    pub(crate) fn new_in(input: &'a [u8], arena: &'a Bump) -> Parser<'a> {
+        Parser {
+            arena: Some(arena),
+            ..Parser::new(input)
+        }
+    }
+
+    /// Like [`Parser::non_empty_with_options`], but decoding OIDs into `arena`
+    #[cfg(feature = "arena")]
+    pub(crate) fn non_empty_in(
+        input: &'a [u8],
+        options: ParseOptions,
+        arena: &'a Bump,
+    ) -> Result<Parser<'a>, ASNError> {
+        Ok(Parser {
+            arena: Some(arena),
+            ..Parser::non_empty_with_options(input, options)?
+        })
+    }
+
+    pub(crate) fn options(&self) -> ParseOptions {
+        self.options
+    }
+
+    pub(crate) fn unwrap_outer_sequence(input: &'a [u8]) -> Result<Parser<'a>, ASNError> {
+        Parser::new(input).only_sequence_contents()
+    }
+
+    /// Parser of the contents of the SEQUENCE that makes up the rest of the input, which must
+    /// hold nothing else
+    pub(crate) fn only_sequence_contents(mut self) -> Result<Parser<'a>, ASNError> {
+        let inner = self.expect_sequence_contents()?;
+        self.expect_end()?;
+        Ok(inner)
+    }
+
+    /// Whether all of the input has been consumed
+    pub fn is_empty(&self) -> bool {
+        self.reader.is_empty()
+    }
+
+    /// The input that hasn't been consumed yet, e.g. opaque bytes following a value
+    pub fn remaining(&self) -> &'a [u8] {
+        self.reader.remainder()
+    }
+
+    /// Type of the next element without consuming it, or `None` at the end of the input
+    pub fn peek_id(&self) -> Option<Result<ASNTypeId, ASNError>> {
+        if self.reader.is_empty() {
+            return None;
+        }
+
+        let mut peek = self.reader;
+        let result = match Identifier::read(&mut peek) {
+            Ok(id) => match read_type(&id) {
+                Some((type_id, _)) => Ok(type_id),
+                None => Err(ASNErrorVariant::UnsupportedId(id)),
+            },
+            Err(err) => Err(err),
+        };
+        Some(result.map_err(|err| ASNError::at(err, self.reader.remainder())))
+    }
+
+    /// Consume exactly one element without decoding its contents, which may be of any type
+    pub fn skip(&mut self) -> Result<(), ASNError> {
+        let element = self.reader.remainder();
+        let mut reader = self.reader;
+        Identifier::read(&mut reader)
+            .and_then(|id| get_contents(&mut reader, &id, self.options))
+            .map_err(|err| ASNError::at(err, element))?;
+        self.reader = reader;
+        Ok(())
+    }
+
+    /// Expect a SEQUENCE and return a new parser scoped to its contents
+    ///
+    /// Named like the other `expect_*` methods rather than as an `into_*` conversion, since it
+    /// borrows this parser and leaves it positioned after the SEQUENCE.
+    pub fn expect_sequence_contents(&mut self) -> Result<Parser<'a>, ASNError> {
+        let (contents, der) = self.expect_with_der::<Sequence>()?;
+        self.nested(contents).map_err(|err| ASNError::at(err, der))
+    }
+
+    /// Expect a SEQUENCE OF `T` and map each of its elements with `f`, in order
+    pub(crate) fn expect_sequence_of<T, R, F>(&mut self, mut f: F) -> Result<Vec<R>, ASNError>
+    where
+        T: ASNWrapperType<'a>,
+        F: FnMut(T::Item) -> Result<R, ASNError>,
+    {
+        let mut parser = self.expect_sequence_contents()?;
+        let mut values = Vec::new();
+        while !parser.is_empty() {
+            values.push(f(parser.expect::<T>()?)?);
+        }
+        Ok(values)
+    }
+
+    /// Expect an INTEGER and map its value onto an enum with `f`
+    ///
+    /// Fails with `BadEnumValue` if `f` returns None, or with `IntegerTooLarge` if the value
+    /// doesn't fit in an i32.
+    pub(crate) fn expect_enum<T, F>(&mut self, name: &'static str, f: F) -> Result<T, ASNError>
+    where
+        F: Fn(i32) -> Option<T>,
+    {
+        let (value, der) = self.expect_with_der::<Integer>()?;
+        let err = match value.as_i32() {
+            Some(x) => match f(x) {
+                Some(value) => return Ok(value),
+                None => ASNErrorVariant::BadEnumValue(name, x),
+            },
+            None => ASNErrorVariant::IntegerTooLarge(value.bytes.len()),
+        };
+        Err(ASNError::at(err, der))
+    }
+
+    /// Expect a SET and return a new parser scoped to its contents, see
+    /// [`Parser::expect_sequence_contents`]
+    pub fn expect_set_contents(&mut self) -> Result<Parser<'a>, ASNError> {
+        let (contents, der) = self.expect_with_der::<Set>()?;
+        self.nested(contents).map_err(|err| ASNError::at(err, der))
+    }
+
+    /// Parser of the contents of a constructed value read by this parser
+    ///
+    /// Fails with `MaxDepthExceeded` once the contents would be nested deeper than the options
+    /// allow.
+    pub(crate) fn nested(&self, contents: &'a [u8]) -> Result<Parser<'a>, ASNErrorVariant> {
+        if self.depth >= self.options.max_depth {
+            return Err(ASNErrorVariant::MaxDepthExceeded(self.options.max_depth));
+        }
+        Ok(Parser {
+            reader: Reader::new(contents),
+            options: self.options,
+            depth: self.depth + 1,
+            arena: self.arena,
+        })
+    }
+
+    /// Like [`Parser::parse_all`] over the contents of a constructed value read by this parser,
+    /// which inherit its options
+    pub(crate) fn parse_nested<T, F>(&self, contents: &'a [u8], parse: F) -> Result<T, ASNError>
+    where
+        F: FnOnce(&mut Parser<'a>) -> Result<T, ASNError>,
+    {
+        let mut parser = self
+            .nested(contents)
+            .map_err(|err| ASNError::at(err, contents))?;
+        let value = parse(&mut parser)?;
         parser.expect_end()?;
-        Ok(Parser::new(bytes))
+        Ok(value)
     }
-    pub(crate) fn get_optional_explicit_tag_value<T: ASNWrapperType<'a>>(
+
+    /// Runs `f` over the contents of the next element if it is an EXPLICIT tag matching `tag`,
+    /// the contents must hold nothing else, otherwise leaves the parser untouched
+    pub(crate) fn get_optional_explicit<F, R>(
         &mut self,
-        tag: u8,
-    ) -> Result<Option<T::Item>, ASNErrorVariant> {
+        tag: u32,
+        f: F,
+    ) -> Result<Option<R>, ASNError>
+    where
+        F: FnOnce(&mut Parser<'a>) -> Result<R, ASNError>,
+    {
         match self.get_optional_explicit_tag(tag)? {
             Some(tag) => {
-                let mut parser = Parser::new(tag.contents);
-                Ok(Some(parser.expect::<T>()?))
+                let mut parser = self
+                    .nested(tag.contents)
+                    .map_err(|err| ASNError::at(err, tag.contents))?;
+                let value = f(&mut parser)?;
+                parser.expect_end()?;
+                Ok(Some(value))
             }
             None => Ok(None),
         }
     }
 
-    pub(crate) fn get_optional_explicit_tag(
+    // the context-specific tag shared by the helpers above and below, tag numbers are u32 so
+    // that the long form of the identifier is supported
+    fn get_optional_explicit_tag(
         &mut self,
-        tag: u8,
-    ) -> Result<Option<ASNExplicitTag<'a>>, ASNErrorVariant> {
+        tag: u32,
+    ) -> Result<Option<ASNExplicitTag<'a>>, ASNError> {
         if self.reader.is_empty() {
             return Ok(None);
         }
 
-        let id = Identifier::from(self.reader.peek_byte()?);
+        let mut peek = self.reader;
+        let id = Identifier::read(&mut peek)?;
 
         match read_type(&id) {
             Some((ASNTypeId::ExplicitTag, actual_tag)) if tag == actual_tag => {
                 Ok(Some(self.expect::<ExplicitTag>()?))
             }
             Some(_) => Ok(None),
-            None => Err(ASNErrorVariant::UnsupportedId(id)),
+            None => Err(ASNError::at(
+                ASNErrorVariant::UnsupportedId(id),
+                self.reader.remainder(),
+            )),
         }
     }
 
+    /// Returns the raw contents of the next element if it is a context-specific tag (primitive
+    /// or constructed) matching `tag`, otherwise leaves the parser untouched and returns `None`
+    ///
+    /// Meant for IMPLICIT tags, whose contents are those of the underlying type rather than a
+    /// complete element. Use [`Parser::get_optional_explicit`] for EXPLICIT tags.
+    pub(crate) fn get_optional_context_field(
+        &mut self,
+        tag: u32,
+    ) -> Result<Option<&'a [u8]>, ASNError> {
+        Ok(self.get_optional_explicit_tag(tag)?.map(|tag| tag.contents))
+    }
+
     pub(crate) fn get_optional_or_default<T: ASNWrapperType<'a>>(
         &mut self,
         default: T::Item,
-    ) -> Result<T::Item, ASNErrorVariant> {
+    ) -> Result<T::Item, ASNError> {
         match self.get_optional::<T>()? {
             Some(value) => Ok(value),
             None => Ok(default),
@@ -391,91 +888,149 @@ impl<'a> Parser<'a> {
 
     pub(crate) fn get_optional<T: ASNWrapperType<'a>>(
         &mut self,
-    ) -> Result<Option<T::Item>, ASNErrorVariant> {
-        if self.reader.is_empty() {
-            return Ok(None);
-        }
-
-        let id = Identifier::from(self.reader.peek_byte()?);
-
-        match read_type(&id) {
-            Some((ref id, _)) if *id == T::get_id() => Ok(Some(self.expect::<T>()?)),
-            Some(_) => Ok(None),
-            None => Err(ASNErrorVariant::UnsupportedId(id)),
+    ) -> Result<Option<T::Item>, ASNError> {
+        match self.peek_id().transpose()? {
+            Some(id) if id == T::get_id() => Ok(Some(self.expect::<T>()?)),
+            _ => Ok(None),
         }
     }
 
-    pub(crate) fn parse_implicit<T: ASNWrapperType<'a>>(
-        &mut self,
-    ) -> Result<T::Item, ASNErrorVariant> {
-        let result = match T::get_value(parse_content(&T::get_id(), 0, self.reader.remainder())?) {
+    /// Parse the rest of the input as the contents of `T`'s universal type
+    ///
+    /// Used for an `[n] IMPLICIT` field whose context-specific identifier and length were
+    /// already stripped, e.g. by [`Parser::get_optional_context_field`]. The encoding doesn't
+    /// carry the base type, so the caller must know it from the schema. Any universal type is
+    /// supported, constructed ones yield their raw contents as they would with their own tag.
+    pub(crate) fn parse_implicit<T: ASNWrapperType<'a>>(&mut self) -> Result<T::Item, ASNError> {
+        let contents = self.reader.take_remaining();
+        let value = parse_content(&T::get_id(), 0, contents, self.options, self.arena)
+            .map_err(|err| ASNError::at(err, contents))?;
+        match T::get_value(value) {
             Some(value) => Ok(value),
             None => panic!("Wrapper should have returned a {:?}!", T::get_id()),
-        };
-        self.reader.clear();
-        result
+        }
     }
 
-    pub(crate) fn expect<T: ASNWrapperType<'a>>(&mut self) -> Result<T::Item, ASNErrorVariant> {
+    /// Expect the next element to be of `T`'s type and return its value
+    pub fn expect<T: ASNWrapperType<'a>>(&mut self) -> Result<T::Item, ASNError> {
+        let element = self.reader.remainder();
         match self.expect_any() {
             Ok(asn_type) => {
                 let id = asn_type.get_id();
                 match T::get_value(asn_type) {
                     Some(value) => Ok(value),
-                    None => Err(ASNErrorVariant::UnexpectedType(T::get_id(), id)),
+                    None => Err(ASNError::at(
+                        ASNErrorVariant::UnexpectedType(T::get_id(), id),
+                        element,
+                    )),
                 }
             }
             Err(err) => Err(err),
         }
     }
 
+    /// Like `expect`, but also returns the complete encoding (tag, length, and contents) of the value
+    pub(crate) fn expect_with_der<T: ASNWrapperType<'a>>(
+        &mut self,
+    ) -> Result<WithDer<'a, T::Item>, ASNError> {
+        self.with_der(|parser| parser.expect::<T>())
+    }
+
+    /// Like `expect_any`, but also returns the complete encoding of the value
+    pub(crate) fn expect_any_with_der(&mut self) -> Result<WithDer<'a, ASNType<'a>>, ASNError> {
+        self.with_der(|parser| parser.expect_any())
+    }
+
+    fn with_der<T, F>(&mut self, read: F) -> Result<WithDer<'a, T>, ASNError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, ASNError>,
+    {
+        let before = self.reader.remainder();
+        let value = read(self)?;
+        let consumed = before.len() - self.reader.remainder().len();
+        Ok((value, &before[..consumed]))
+    }
+
+    /// Like `get_optional`, but also returns the complete encoding of the value if present
+    pub(crate) fn get_optional_with_der<T: ASNWrapperType<'a>>(
+        &mut self,
+    ) -> Result<Option<WithDer<'a, T::Item>>, ASNError> {
+        let before = self.reader.remainder();
+        match self.get_optional::<T>()? {
+            Some(value) => {
+                let consumed = before.len() - self.reader.remainder().len();
+                Ok(Some((value, &before[..consumed])))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) fn expect_null(&mut self) -> Result<(), ASNError> {
+        let element = self.reader.remainder();
+        match self.expect_any()? {
+            ASNType::Null => Ok(()),
+            other => Err(ASNError::at(
+                ASNErrorVariant::UnexpectedType(ASNTypeId::Null, other.get_id()),
+                element,
+            )),
+        }
+    }
+
     pub(crate) fn expect_or_end<T: ASNWrapperType<'a>>(
         &mut self,
-    ) -> Result<Option<T::Item>, ASNErrorVariant> {
+    ) -> Result<Option<T::Item>, ASNError> {
         match self.expect::<T>() {
             Ok(value) => Ok(Some(value)),
-            Err(ASNErrorVariant::EndOfStream) => Ok(None),
+            Err(err) if err.variant == ASNErrorVariant::EndOfStream => Ok(None),
             Err(err) => Err(err),
         }
     }
 
-    pub(crate) fn expect_any(&mut self) -> Result<ASNType<'a>, ASNErrorVariant> {
-        match self.next() {
+    /// Expect an element of any type
+    pub fn expect_any(&mut self) -> Result<ASNType<'a>, ASNError> {
+        match self.next_element() {
             Some(Ok(asn)) => Ok(asn),
             Some(Err(err)) => Err(err),
-            None => Err(ASNErrorVariant::EndOfStream),
+            None => Err(ASNError::at(
+                ASNErrorVariant::EndOfStream,
+                self.reader.remainder(),
+            )),
         }
     }
 
-    pub(crate) fn expect_any_or_end(&mut self) -> Result<Option<ASNType<'a>>, ASNErrorVariant> {
-        match self.next() {
+    /// Like [`Parser::expect_any`], but returning `None` at the end of the input
+    pub fn expect_any_or_end(&mut self) -> Result<Option<ASNType<'a>>, ASNError> {
+        match self.next_element() {
             Some(Ok(asn)) => Ok(Some(asn)),
             Some(Err(err)) => Err(err),
             None => Ok(None),
         }
     }
 
-    pub(crate) fn expect_end(&mut self) -> Result<(), ASNErrorVariant> {
-        match self.next() {
+    /// Expect the input to be fully consumed
+    pub fn expect_end(&mut self) -> Result<(), ASNError> {
+        let element = self.reader.remainder();
+        match self.next_element() {
             None => Ok(()),
             Some(Err(err)) => Err(err),
-            Some(Ok(asn)) => Err(ASNErrorVariant::ExpectedEnd(asn.get_id())),
+            Some(Ok(asn)) => Err(ASNError::at(
+                ASNErrorVariant::ExpectedEnd(asn.get_id()),
+                element,
+            )),
         }
     }
-}
 
-impl<'a> Iterator for Parser<'a> {
-    type Item = Result<ASNType<'a>, ASNErrorVariant>;
-
-    fn next(&mut self) -> Option<Self::Item> {
+    // the next element, or None at the end of the input, the input is dropped after an error
+    pub(crate) fn next_element(&mut self) -> Option<Result<ASNType<'a>, ASNError>> {
         if self.reader.is_empty() {
             return None;
         }
 
-        match parse_one_type(&mut self.reader) {
+        let element = self.reader.remainder();
+        match parse_one_type(&mut self.reader, self.options, self.arena) {
             Err(e) => {
                 self.reader.clear();
-                Some(Err(e))
+                Some(Err(ASNError::at(e, element)))
             }
             Ok(token) => Some(Ok(token)),
         }
@@ -490,20 +1045,23 @@ mod tests {
 
     fn test_parse_length(bytes: &[u8]) -> Result<usize, ASNErrorVariant> {
         let mut reader = Reader::new(bytes);
-        parse_length(&mut reader)
+        parse_length(&mut reader, ParseOptions::default())
     }
 
     #[test]
     fn decode_length_on_empty_bytes_fails() {
         let mut reader = Reader::new(&[]);
-        assert_eq!(parse_length(&mut reader), Err(ASNErrorVariant::EndOfStream));
+        assert_eq!(
+            parse_length(&mut reader, ParseOptions::default()),
+            Err(ASNErrorVariant::EndOfStream)
+        );
     }
 
     #[test]
     fn detects_indefinite_length() {
         let mut reader = Reader::new(&[0x80]);
         assert_eq!(
-            parse_length(&mut reader),
+            parse_length(&mut reader, ParseOptions::default()),
             Err(ASNErrorVariant::UnsupportedIndefiniteLength)
         )
     }
@@ -512,7 +1070,7 @@ mod tests {
     fn detects_reserved_length_of_127() {
         let mut reader = Reader::new(&[0xFF]);
         assert_eq!(
-            parse_length(&mut reader),
+            parse_length(&mut reader, ParseOptions::default()),
             Err(ASNErrorVariant::ReservedLengthValue)
         )
     }
@@ -520,7 +1078,7 @@ mod tests {
     #[test]
     fn decode_length_on_single_byte_returns_valid_result() {
         let mut reader = Reader::new(&[127, 0xDE, 0xAD]);
-        assert_eq!(parse_length(&mut reader), Ok(127));
+        assert_eq!(parse_length(&mut reader, ParseOptions::default()), Ok(127));
         assert_eq!(reader.remainder(), &[0xDE, 0xAD]);
     }
 
@@ -538,7 +1096,7 @@ mod tests {
         assert_eq!(test_parse_length(&[TOP_BIT | 2, 0x01, 0x00]), Ok(256));
         assert_eq!(
             test_parse_length(&[TOP_BIT | 2, 0x00, 0xFF]),
-            Err(ASNErrorVariant::BadLengthEncoding(2, 255))
+            Err(ASNErrorVariant::NonMinimalLength)
         );
     }
 
@@ -550,7 +1108,7 @@ mod tests {
         );
         assert_eq!(
             test_parse_length(&[TOP_BIT | 3, 0x00, 0xFF, 0xFF]),
-            Err(ASNErrorVariant::BadLengthEncoding(3, 65535))
+            Err(ASNErrorVariant::NonMinimalLength)
         );
     }
 
@@ -562,69 +1120,176 @@ mod tests {
         );
         assert_eq!(
             test_parse_length(&[TOP_BIT | 4, 0x00, 0xFF, 0xFF, 0xFF]),
-            Err(ASNErrorVariant::BadLengthEncoding(4, 16777215))
+            Err(ASNErrorVariant::NonMinimalLength)
         );
     }
 
     #[test]
     fn decode_length_on_count_of_one_succeeds_if_value_greater_than_127() {
         let mut reader = Reader::new(&[TOP_BIT | 1, 128]);
-        assert_eq!(parse_length(&mut reader), Ok(128));
+        assert_eq!(parse_length(&mut reader, ParseOptions::default()), Ok(128));
         assert!(reader.is_empty());
     }
 
     #[test]
     fn decode_length_on_count_of_two_succeeds() {
         let mut reader = Reader::new(&[TOP_BIT | 2, 0x01, 0x02, 0x03]);
-        assert_eq!(parse_length(&mut reader), Ok(0x0102));
+        assert_eq!(
+            parse_length(&mut reader, ParseOptions::default()),
+            Ok(0x0102)
+        );
         assert_eq!(reader.remainder(), &[0x03]);
     }
 
     #[test]
     fn decode_length_on_count_of_three_succeeds() {
         let mut reader = Reader::new(&[TOP_BIT | 3, 0x01, 0x02, 0x03, 0x04]);
-        assert_eq!(parse_length(&mut reader), Ok(0x010203));
+        assert_eq!(
+            parse_length(&mut reader, ParseOptions::default()),
+            Ok(0x010203)
+        );
         assert_eq!(reader.remainder(), &[0x04]);
     }
 
     #[test]
     fn decode_length_on_count_of_four_succeeds() {
         let mut reader = Reader::new(&[TOP_BIT | 4, 0x01, 0x02, 0x03, 0x04, 0x05]);
-        assert_eq!(parse_length(&mut reader), Ok(0x01020304));
+        assert_eq!(
+            parse_length(&mut reader, ParseOptions::default()),
+            Ok(0x01020304)
+        );
         assert_eq!(reader.remainder(), &[0x05]);
     }
 
     #[test]
-    fn decode_length_on_count_of_five_fails() {
-        let mut reader = Reader::new(&[TOP_BIT | 5, 0x01, 0x02, 0x03, 0x04, 0x05]);
+    #[cfg(target_pointer_width = "32")]
+    fn maximum_length_on_32_bit_target_does_not_overflow() {
+        let mut reader = Reader::new(&[TOP_BIT | 4, 0xFF, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(
+            parse_length(&mut reader, ParseOptions::default()),
+            Ok(0xFFFF_FFFF)
+        );
+
+        let mut reader = Reader::new(&[0x04, TOP_BIT | 4, 0xFF, 0xFF, 0xFF, 0xFF, 0x00]);
+        assert_eq!(
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Err(ASNErrorVariant::EndOfStream)
+        );
+    }
+
+    #[test]
+    fn length_exceeding_the_platform_size_fails() {
+        // the limits of 16 and 32 bit targets
+        assert_eq!(narrow_length::<u16>(0xFFFF), Ok(0xFFFF));
+        assert_eq!(
+            narrow_length::<u16>(0x01_0000),
+            Err(ASNErrorVariant::LengthExceedsPlatform(0x01_0000))
+        );
+        assert_eq!(narrow_length::<u32>(0xFFFF_FFFF), Ok(0xFFFF_FFFF));
+        assert_eq!(
+            narrow_length::<u32>(0x01_0000_0000),
+            Err(ASNErrorVariant::LengthExceedsPlatform(0x01_0000_0000))
+        );
+    }
+
+    #[test]
+    fn non_empty_parser_rejects_empty_input() {
+        assert!(matches!(
+            Parser::new_non_empty(&[]),
+            Err(ASNError {
+                variant: ASNErrorVariant::EmptyInput,
+                ..
+            })
+        ));
+        assert_eq!(
+            Parser::new_non_empty(&[0x30])
+                .unwrap()
+                .expect_any()
+                .unwrap_err()
+                .variant,
+            ASNErrorVariant::EndOfStream
+        );
+    }
+
+    #[test]
+    fn decode_length_on_count_of_five_succeeds_if_it_fits_in_usize() {
+        let mut reader = Reader::new(&[TOP_BIT | 5, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        assert_eq!(
+            parse_length(&mut reader, ParseOptions::default()),
+            usize::try_from(0x01_0203_0405u64)
+                .map_err(|_| ASNErrorVariant::LengthExceedsPlatform(0x01_0203_0405))
+        );
+        assert_eq!(reader.remainder(), &[0x06]);
+
+        assert_eq!(
+            test_parse_length(&[TOP_BIT | 5, 0x00, 0x01, 0x02, 0x03, 0x04]),
+            Err(ASNErrorVariant::NonMinimalLength)
+        );
+    }
+
+    #[test]
+    fn decode_length_on_count_of_eight_overflows_narrow_usize() {
+        let bytes = [TOP_BIT | 8, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        assert_eq!(
+            test_parse_length(&bytes),
+            usize::try_from(1u64 << 56)
+                .map_err(|_| ASNErrorVariant::LengthExceedsPlatform(1 << 56))
+        );
+    }
+
+    #[test]
+    fn decode_length_on_count_of_nine_fails() {
+        let mut reader = Reader::new(&[TOP_BIT | 9, 0x01, 0x02, 0x03, 0x04, 0x05]);
         assert_eq!(
-            parse_length(&mut reader),
-            Err(ASNErrorVariant::UnsupportedLengthByteCount(5))
+            parse_length(&mut reader, ParseOptions::default()),
+            Err(ASNErrorVariant::UnsupportedLengthByteCount(9))
         )
     }
 
+    #[test]
+    fn parse_one_fails_for_primitive_sequence() {
+        let mut reader = Reader::new(&[0x10, 0x00]);
+        assert_eq!(
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Err(ASNErrorVariant::PrimitiveConstructedTypeMismatch(
+                Identifier::new(TagClass::Universal, PC::Primitive, 0x10)
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_one_fails_for_constructed_integer() {
+        let mut reader = Reader::new(&[0x22, 0x03, 0x02, 0x01, 0x05]);
+        assert_eq!(
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Err(ASNErrorVariant::PrimitiveConstructedTypeMismatch(
+                Identifier::new(TagClass::Universal, PC::Constructed, 0x02)
+            ))
+        );
+    }
+
     #[test]
     fn parse_one_fails_for_non_universal_type() {
-        let mut reader = Reader::new(&[0xFF]);
+        let mut reader = Reader::new(&[0xFE]);
         assert_eq!(
-            parse_one_type(&mut reader),
+            parse_one_type(&mut reader, ParseOptions::default(), None),
             Err(ASNErrorVariant::UnsupportedId(Identifier::new(
                 TagClass::Private,
                 PC::Constructed,
-                0x1F
+                0x1E
             )))
         )
     }
 
     #[test]
     fn parse_one_fails_for_unknown_universal_type() {
-        let mut reader = Reader::new(&[0x1F, 0x00]);
+        let mut reader = Reader::new(&[0x1D, 0x00]);
         assert_eq!(
-            parse_one_type(&mut reader),
+            parse_one_type(&mut reader, ParseOptions::default(), None),
             Err(ASNErrorVariant::UnsupportedId(Identifier::new(
                 TagClass::Universal,
                 PC::Primitive,
-                0x1F
+                0x1D
             )))
         )
     }
@@ -633,7 +1298,7 @@ mod tests {
     fn parses_sequence_correctly() {
         let mut reader = Reader::new(&[0x30, 0x03, 0x02, 0x03, 0x04, 0x05, 0x06]);
         assert_eq!(
-            parse_one_type(&mut reader),
+            parse_one_type(&mut reader, ParseOptions::default(), None),
             Ok(Sequence::asn(&[0x02, 0x03, 0x04]))
         );
         assert_eq!(reader.remainder(), &[0x05, 0x06]);
@@ -643,36 +1308,379 @@ mod tests {
     fn parse_sequence_fails_if_insufficient_bytes() {
         let mut reader = Reader::new(&[0x30, 0x0F, 0xDE, 0xAD]);
         assert_eq!(
-            parse_one_type(&mut reader),
+            parse_one_type(&mut reader, ParseOptions::default(), None),
             Err(ASNErrorVariant::EndOfStream)
         );
     }
 
     #[test]
-    fn parses_explicit_tag() {
-        let mut reader = Reader::new(&[0xA1, 0x02, 0xCA, 0xFE]);
+    fn parses_empty_bit_string() {
+        let mut reader = Reader::new(&[0x03, 0x01, 0x00]);
+        let bit_string = match parse_one_type(&mut reader, ParseOptions::default(), None) {
+            Ok(ASNType::BitString(wrapper)) => wrapper.value,
+            other => panic!("unexpected result: {:?}", other),
+        };
+        assert_eq!(bit_string.size(), 0);
+        assert_eq!(bit_string.octets(), Some(&[][..]));
+        assert_eq!(bit_string.iter().count(), 0);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn rejects_empty_bit_string_with_unused_bits() {
+        let mut reader = Reader::new(&[0x03, 0x01, 0x07]);
         assert_eq!(
-            parse_one_type(&mut reader),
-            Ok(ExplicitTag::asn(ASNExplicitTag::new(1, &[0xCA, 0xFE])))
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Err(ASNErrorVariant::EmptyBitStringWithUnusedBits(7))
         );
     }
 
     #[test]
-    fn parses_utc_time() {
-        // UTC time in the 20th century
+    fn rejects_bit_string_without_unused_bits_octet() {
+        let mut reader = Reader::new(&[0x03, 0x00]);
         assert_eq!(
-            parse_utc_time("990102052345Z".as_bytes()),
-            Ok(UtcTime::asn(915254625))
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Err(ASNErrorVariant::EndOfStream)
         );
+    }
 
-        // UTC time in the 21th century.
-        // On October 9th 2001, Leonard Cohen and Sharon Robinson released "Ten New Songs".
-        //
-        // "The ponies run, the girls are young,
-        // The odds are there to beat.
-        // You win a while, and then it's done
-        // Your little winning streak.
-        // And summoned now to deal
+    #[test]
+    fn parses_empty_octet_string() {
+        let mut reader = Reader::new(&[0x04, 0x00]);
+        assert_eq!(
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Ok(OctetString::asn(&[]))
+        );
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn parses_explicit_tag() {
+        let mut reader = Reader::new(&[0xA1, 0x02, 0xCA, 0xFE]);
+        assert_eq!(
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Ok(ExplicitTag::asn(ASNExplicitTag::new(1, &[0xCA, 0xFE])))
+        );
+    }
+
+    #[test]
+    fn parses_high_tag_numbers() {
+        // [31] uses the long form with a single subsequent byte
+        let mut reader = Reader::new(&[0xBF, 0x1F, 0x01, 0x00]);
+        assert_eq!(
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Ok(ExplicitTag::asn(ASNExplicitTag::new(31, &[0x00])))
+        );
+
+        // [128] needs two base 128 digits
+        let mut reader = Reader::new(&[0x9F, 0x81, 0x00, 0x01, 0xAB]);
+        assert_eq!(
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Ok(ExplicitTag::asn(ASNExplicitTag::new(128, &[0xAB])))
+        );
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn rejects_truncated_high_tag_number() {
+        let mut reader = Reader::new(&[0xBF, 0x81]);
+        assert_eq!(
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Err(ASNErrorVariant::EndOfStream)
+        );
+    }
+
+    #[test]
+    fn rejects_non_minimal_high_tag_numbers() {
+        // leading digit of zero
+        let mut reader = Reader::new(&[0xBF, 0x80, 0x1F, 0x00]);
+        assert_eq!(
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Err(ASNErrorVariant::BadTagEncoding)
+        );
+
+        // [30] fits in the single byte form
+        let mut reader = Reader::new(&[0xBF, 0x1E, 0x00]);
+        assert_eq!(
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Err(ASNErrorVariant::BadTagEncoding)
+        );
+
+        let mut reader = Reader::new(&[0xBF, 0x90, 0x80, 0x80, 0x80, 0x00, 0x00]);
+        assert_eq!(
+            parse_one_type(&mut reader, ParseOptions::default(), None),
+            Err(ASNErrorVariant::TagNumberTooLarge)
+        );
+    }
+
+    #[test]
+    fn optional_explicit_tag_matches_high_tag_number() {
+        let mut parser = Parser::new(&[0xBF, 0x81, 0x00, 0x01, 0xAB]);
+        assert_eq!(parser.get_optional_context_field(1), Ok(None));
+        assert_eq!(
+            parser.get_optional_context_field(128),
+            Ok(Some(&[0xAB][..]))
+        );
+        assert!(parser.is_empty());
+    }
+
+    #[test]
+    fn returns_the_complete_encoding_of_each_value() {
+        // SEQUENCE { INTEGER 5 }, [1] { NULL }, UTF8String "hi"
+        let input = [
+            0x30, 0x03, 0x02, 0x01, 0x05, 0xA1, 0x02, 0x05, 0x00, 0x0C, 0x02, 0x68, 0x69,
+        ];
+        let mut parser = Parser::new(&input);
+
+        let (contents, der) = parser.expect_with_der::<Sequence>().unwrap();
+        assert_eq!(contents, [0x02, 0x01, 0x05]);
+        assert_eq!(der, &input[..5]);
+
+        let mut consumed = der.len();
+        while !parser.is_empty() {
+            let (value, der) = parser.expect_any_with_der().unwrap();
+            assert_eq!(der.as_ptr(), input[consumed..].as_ptr());
+            consumed += der.len();
+
+            let mut reader = Reader::new(der);
+            assert_eq!(
+                parse_one_type(&mut reader, ParseOptions::default(), None),
+                Ok(value)
+            );
+            assert!(reader.is_empty());
+        }
+        assert_eq!(consumed, input.len());
+    }
+
+    #[test]
+    fn descends_into_nested_sequence() {
+        // SEQUENCE { SEQUENCE { INTEGER 5, NULL }, SET { BOOLEAN TRUE } }
+        let mut parser = Parser::new(&[
+            0x30, 0x0C, 0x30, 0x05, 0x02, 0x01, 0x05, 0x05, 0x00, 0x31, 0x03, 0x01, 0x01, 0xFF,
+        ]);
+        let mut outer = parser.expect_sequence_contents().unwrap();
+        assert!(parser.is_empty());
+
+        let mut inner = outer.expect_sequence_contents().unwrap();
+        assert_eq!(inner.expect::<Integer>().unwrap().as_i32(), Some(5));
+        assert_eq!(inner.expect_any(), Ok(ASNType::Null));
+        assert_eq!(inner.expect_end(), Ok(()));
+
+        let mut set = outer.expect_set_contents().unwrap();
+        assert_eq!(set.expect::<Boolean>(), Ok(true));
+        assert_eq!(outer.expect_end(), Ok(()));
+    }
+
+    #[test]
+    fn exposes_the_bytes_after_a_value() {
+        // AlgorithmIdentifier { ed25519 } followed by an opaque trailer
+        let input = [0x30, 0x05, 0x06, 0x03, 0x2B, 0x65, 0x70, 0xDE, 0xAD];
+        let mut parser = Parser::new(&input);
+        assert_eq!(parser.remaining(), input);
+
+        let mut algorithm = parser.expect_sequence_contents().unwrap();
+        assert_eq!(
+            algorithm.expect::<ObjectIdentifier>().unwrap().values(),
+            [1, 3, 101, 112]
+        );
+        assert!(algorithm.remaining().is_empty());
+        assert_eq!(parser.remaining(), [0xDE, 0xAD]);
+    }
+
+    #[test]
+    fn maps_each_element_of_a_sequence_of() {
+        // SEQUENCE { OID 2.5.4.3, OID 1.2 }, NULL
+        let mut parser = Parser::new(&[
+            0x30, 0x08, 0x06, 0x03, 0x55, 0x04, 0x03, 0x06, 0x01, 0x2A, 0x05, 0x00,
+        ]);
+        let oids = parser
+            .expect_sequence_of::<ObjectIdentifier, _, _>(|oid| Ok(oid.to_dotted_string()))
+            .unwrap();
+        assert_eq!(oids, ["2.5.4.3", "1.2"]);
+        assert_eq!(parser.expect_any(), Ok(ASNType::Null));
+
+        let mut parser = Parser::new(&[0x30, 0x00]);
+        let oids = parser
+            .expect_sequence_of::<ObjectIdentifier, _, _>(Ok)
+            .unwrap();
+        assert!(oids.is_empty());
+        assert!(parser.is_empty());
+
+        // SEQUENCE { OID 1.2, INTEGER 5 }
+        let mut parser = Parser::new(&[0x30, 0x06, 0x06, 0x01, 0x2A, 0x02, 0x01, 0x05]);
+        assert_eq!(
+            parser
+                .expect_sequence_of::<ObjectIdentifier, _, _>(Ok)
+                .unwrap_err()
+                .variant,
+            ASNErrorVariant::UnexpectedType(ASNTypeId::ObjectIdentifier, ASNTypeId::Integer)
+        );
+    }
+
+    #[test]
+    fn maps_integers_onto_enums() {
+        fn version(value: i32) -> Option<&'static str> {
+            match value {
+                0 => Some("v1"),
+                1 => Some("v2"),
+                2 => Some("v3"),
+                _ => None,
+            }
+        }
+
+        // INTEGER 2, INTEGER 7
+        let input = [0x02, 0x01, 0x02, 0x02, 0x01, 0x07];
+        let mut parser = Parser::new(&input);
+        assert_eq!(parser.expect_enum("version", version), Ok("v3"));
+        let err = parser.expect_enum("version", version).unwrap_err();
+        assert_eq!(err.variant, ASNErrorVariant::BadEnumValue("version", 7));
+        assert_eq!(err.relative_to(&input).offset(), Some(3));
+
+        // INTEGER 2^32
+        let mut parser = Parser::new(&[0x02, 0x05, 0x01, 0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(
+            parser.expect_enum("version", version).unwrap_err().variant,
+            ASNErrorVariant::IntegerTooLarge(5)
+        );
+    }
+
+    #[test]
+    fn expect_sequence_contents_fails_on_other_types() {
+        let mut parser = Parser::new(&[0x31, 0x00]);
+        assert_eq!(
+            parser
+                .expect_sequence_contents()
+                .err()
+                .map(|err| err.variant),
+            Some(ASNErrorVariant::UnexpectedType(
+                ASNTypeId::Sequence,
+                ASNTypeId::Set
+            ))
+        );
+    }
+
+    #[test]
+    fn skips_unknown_element_before_integer() {
+        // [PRIVATE 1] 0xAA, SEQUENCE { INTEGER 7 }, INTEGER 5
+        let mut parser = Parser::new(&[
+            0xC1, 0x01, 0xAA, 0x30, 0x03, 0x02, 0x01, 0x07, 0x02, 0x01, 0x05,
+        ]);
+        assert!(matches!(
+            parser.peek_id(),
+            Some(Err(ASNError {
+                variant: ASNErrorVariant::UnsupportedId(_),
+                ..
+            }))
+        ));
+        assert_eq!(parser.skip(), Ok(()));
+        assert_eq!(parser.peek_id(), Some(Ok(ASNTypeId::Sequence)));
+        // peeking doesn't consume the element
+        assert_eq!(parser.peek_id(), Some(Ok(ASNTypeId::Sequence)));
+        assert_eq!(parser.skip(), Ok(()));
+        assert_eq!(parser.expect::<Integer>().unwrap().as_i32(), Some(5));
+        assert_eq!(parser.peek_id(), None);
+        assert_eq!(
+            parser.skip().unwrap_err().variant,
+            ASNErrorVariant::EndOfStream
+        );
+    }
+
+    #[test]
+    fn skip_fails_on_truncated_element() {
+        let mut parser = Parser::new(&[0xC1, 0x02, 0xAA]);
+        assert_eq!(
+            parser.skip().unwrap_err().variant,
+            ASNErrorVariant::EndOfStream
+        );
+        // the parser is left at the failing element
+        assert!(!parser.is_empty());
+    }
+
+    #[test]
+    fn expect_null_consumes_null() {
+        let mut parser = Parser::new(&[0x05, 0x00, 0x02, 0x01, 0x00]);
+        assert_eq!(parser.expect_null(), Ok(()));
+        assert_eq!(
+            parser.expect_null().unwrap_err().variant,
+            ASNErrorVariant::UnexpectedType(ASNTypeId::Null, ASNTypeId::Integer)
+        );
+        assert_eq!(
+            parser.expect_null().unwrap_err().variant,
+            ASNErrorVariant::EndOfStream
+        );
+    }
+
+    #[test]
+    fn reads_optional_context_fields() {
+        // [0] primitive, [2] constructed wrapping an INTEGER, followed by a NULL
+        let mut parser = Parser::new(&[0x80, 0x01, 0xAA, 0xA2, 0x03, 0x02, 0x01, 0x05, 0x05, 0x00]);
+        assert_eq!(parser.get_optional_context_field(0), Ok(Some(&[0xAA][..])));
+        assert_eq!(parser.get_optional_context_field(1), Ok(None));
+        assert_eq!(
+            parser.get_optional_context_field(2),
+            Ok(Some(&[0x02, 0x01, 0x05][..]))
+        );
+        assert_eq!(parser.get_optional_context_field(3), Ok(None));
+        assert_eq!(parser.expect_any(), Ok(ASNType::Null));
+        assert_eq!(parser.get_optional_context_field(3), Ok(None));
+    }
+
+    #[test]
+    fn runs_over_the_contents_of_an_optional_explicit_tag() {
+        // [0] wrapping a SEQUENCE of an INTEGER, followed by a NULL
+        let input = [0xA0, 0x05, 0x30, 0x03, 0x02, 0x01, 0x05, 0x05, 0x00];
+        let mut parser = Parser::new(&input);
+        let value = parser.get_optional_explicit(0, |parser| {
+            let seq = parser.expect::<Sequence>()?;
+            Parser::parse_all(seq, |parser| parser.expect::<Integer>())
+        });
+        assert_eq!(value.unwrap().unwrap().bytes, [0x05]);
+        assert_eq!(parser.expect_any(), Ok(ASNType::Null));
+    }
+
+    #[test]
+    fn skips_an_absent_explicit_tag() {
+        let mut parser = Parser::new(&[0x05, 0x00]);
+        let mut called = false;
+        let value = parser.get_optional_explicit(0, |parser| {
+            called = true;
+            parser.expect_any()
+        });
+        assert_eq!(value, Ok(None));
+        assert!(!called);
+        assert_eq!(parser.expect_any(), Ok(ASNType::Null));
+    }
+
+    #[test]
+    fn rejects_trailing_data_inside_an_explicit_tag() {
+        // [0] wrapping a NULL followed by an INTEGER
+        let input = [0xA0, 0x05, 0x05, 0x00, 0x02, 0x01, 0x05];
+        let err = Parser::new(&input)
+            .get_optional_explicit(0, |parser| parser.expect_any())
+            .unwrap_err();
+        assert_eq!(
+            err.variant,
+            ASNErrorVariant::ExpectedEnd(ASNTypeId::Integer)
+        );
+        assert_eq!(err.relative_to(&input).offset(), Some(4));
+    }
+
+    #[test]
+    fn parses_utc_time() {
+        // UTC time in the 20th century
+        assert_eq!(
+            parse_utc_time("990102052345Z".as_bytes()),
+            Ok(UtcTime::asn(915254625))
+        );
+
+        // UTC time in the 21th century.
+        // On October 9th 2001, Leonard Cohen and Sharon Robinson released "Ten New Songs".
+        //
+        // "The ponies run, the girls are young,
+        // The odds are there to beat.
+        // You win a while, and then it's done
+        // Your little winning streak.
+        // And summoned now to deal
         // With your invincible defeat,
         // You live your life as if it's real,
         // A Thousand Kisses Deep."
@@ -682,12 +1690,421 @@ mod tests {
         );
     }
 
+    #[test]
+    fn applies_the_utc_time_century_window() {
+        assert_eq!(
+            parse_utc_time("000101000000Z".as_bytes()),
+            Ok(UtcTime::asn(946684800))
+        );
+        assert_eq!(
+            parse_utc_time("491231235959Z".as_bytes()),
+            Ok(UtcTime::asn(2524607999))
+        );
+        assert_eq!(
+            parse_utc_time("991231235959Z".as_bytes()),
+            Ok(UtcTime::asn(946684799))
+        );
+        // 1950 rather than 2050, which is before the epoch and can't be represented
+        assert_eq!(
+            parse_utc_time("500101000000Z".as_bytes()),
+            Err(ASNErrorVariant::BadUTCTime)
+        );
+    }
+
     #[test]
     fn parses_generalized_time() {
         // UTC time in the 20th century
         assert_eq!(
             parse_generalized_time("19990102052345Z".as_bytes()),
-            Ok(UtcTime::asn(915254625))
+            Ok(GeneralizedTime::asn(915254625))
+        );
+
+        assert_eq!(
+            parse_generalized_time("20160609000000Z".as_bytes()),
+            Ok(GeneralizedTime::asn(1465430400))
+        );
+    }
+
+    #[test]
+    fn truncates_generalized_time_fractional_seconds() {
+        assert_eq!(
+            parse_generalized_time("19990102052345.678Z".as_bytes()),
+            Ok(GeneralizedTime::asn(915254625))
+        );
+        assert_eq!(
+            parse_generalized_time("19990102052345.Z".as_bytes()),
+            Err(ASNErrorVariant::BadUTCTime)
+        );
+        // UTCTime has no fractional seconds
+        assert_eq!(
+            parse_utc_time("990102052345.6Z".as_bytes()),
+            Err(ASNErrorVariant::BadUTCTime)
+        );
+    }
+
+    #[test]
+    fn converts_generalized_time_offsets_to_utc() {
+        assert_eq!(
+            parse_generalized_time("19990102072345+0200".as_bytes()),
+            Ok(GeneralizedTime::asn(915254625))
+        );
+        assert_eq!(
+            parse_generalized_time("19990101233845.5-0545".as_bytes()),
+            Ok(GeneralizedTime::asn(915254625))
+        );
+        assert_eq!(
+            parse_generalized_time("19990102052345+2400".as_bytes()),
+            Err(ASNErrorVariant::BadUTCTime)
+        );
+        assert_eq!(
+            parse_generalized_time("19700101000000+0100".as_bytes()),
+            Err(ASNErrorVariant::BadUTCTime)
+        );
+        assert_eq!(
+            parse_utc_time("990102072345+0200".as_bytes()),
+            Err(ASNErrorVariant::BadUTCTime)
+        );
+    }
+
+    #[cfg(feature = "arena")]
+    #[test]
+    fn decodes_object_identifier_arcs_into_the_arena() {
+        let arena = Bump::with_capacity(1024);
+        let capacity = arena.chunk_capacity();
+        // 1.2.840.113549 inside a SEQUENCE, then an OID whose last group isn't terminated
+        let input = [
+            0x30, 0x08, 0x06, 0x06, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x06, 0x02, 0x2A, 0x86,
+        ];
+        let mut parser = Parser::new_in(&input, &arena);
+        let oid = parser
+            .expect_sequence_contents()
+            .unwrap()
+            .expect::<ObjectIdentifier>()
+            .unwrap();
+        assert_eq!(oid.values(), [1, 2, 840, 113549]);
+        assert!(arena.chunk_capacity() <= capacity - 4 * size_of::<u128>());
+
+        assert_eq!(
+            parser.expect::<ObjectIdentifier>().unwrap_err().variant,
+            ASNErrorVariant::EndOfStream
+        );
+    }
+
+    #[test]
+    fn parses_bmp_string() {
+        assert_eq!(
+            parse_bmp_string(&[0x00, 0x5A, 0x00, 0x6F, 0x00, 0xEB, 0x27, 0x13]),
+            Ok(BMPString::asn("Zoë✓".to_string()))
+        );
+        assert_eq!(parse_bmp_string(&[]), Ok(BMPString::asn(String::new())));
+    }
+
+    #[test]
+    fn rejects_malformed_bmp_string() {
+        assert_eq!(
+            parse_bmp_string(&[0x00, 0x5A, 0x00]),
+            Err(ASNErrorVariant::BadBMPString)
+        );
+        // unpaired high surrogate
+        assert_eq!(
+            parse_bmp_string(&[0xD8, 0x00, 0x00, 0x41]),
+            Err(ASNErrorVariant::BadBMPString)
+        );
+    }
+
+    #[test]
+    fn parses_teletex_string_as_latin1() {
+        assert_eq!(
+            parse_teletex_string(&[0x4D, 0xFC, 0x6C, 0x6C, 0x65, 0x72]),
+            Ok(TeletexString::asn("Müller".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_ia5_string() {
+        assert_eq!(
+            parse_ia5_string(b"user@example.com"),
+            Ok(IA5String::asn("user@example.com"))
+        );
+    }
+
+    #[test]
+    fn rejects_non_ascii_ia5_string() {
+        // "usér" with the é encoded as valid UTF-8
+        assert_eq!(
+            parse_ia5_string(&[0x75, 0x73, 0xC3, 0xA9, 0x72]),
+            Err(ASNErrorVariant::NonAsciiIA5String(0xC3))
+        );
+    }
+
+    #[test]
+    fn parses_printable_country_code() {
+        assert_eq!(
+            parse_printable_string(b"US"),
+            Ok(PrintableString::asn("US"))
+        );
+        assert_eq!(
+            parse_printable_string(b"A-z 0'()+,./:=?"),
+            Ok(PrintableString::asn("A-z 0'()+,./:=?"))
+        );
+    }
+
+    #[test]
+    fn rejects_underscore_in_printable_string() {
+        assert_eq!(
+            parse_printable_string(b"host_name"),
+            Err(ASNErrorVariant::BadPrintableChar(b'_'))
+        );
+    }
+
+    #[test]
+    fn parses_numeric_string() {
+        let input = [0x12, 0x07, b'0', b'1', b'2', b' ', b'3', b'4', b'5'];
+        assert_eq!(
+            Parser::new(&input).expect::<NumericString>().unwrap(),
+            "012 345"
+        );
+        assert_eq!(
+            parse_numeric_string(b"12-34"),
+            Err(ASNErrorVariant::BadNumericChar(b'-'))
+        );
+    }
+
+    #[test]
+    fn rejects_control_byte_in_visible_string() {
+        let input = [0x1A, 0x04, b'a', b'~', b' ', b'z'];
+        assert_eq!(
+            Parser::new(&input).expect::<VisibleString>().unwrap(),
+            "a~ z"
+        );
+
+        let input = [0x1A, 0x03, b'a', 0x09, b'z'];
+        assert_eq!(
+            Parser::new(&input)
+                .expect::<VisibleString>()
+                .unwrap_err()
+                .variant,
+            ASNErrorVariant::BadVisibleChar(0x09)
+        );
+    }
+
+    #[test]
+    fn non_minimal_encodings_are_only_accepted_leniently() {
+        // SEQUENCE { INTEGER 5 with a long-form length, OID 1.2.1 padded with 0x80 }
+        let input = [
+            0x30, 0x09, 0x02, 0x81, 0x01, 0x05, 0x06, 0x03, 0x2A, 0x80, 0x01,
+        ];
+
+        let mut parser = Parser::new(&input).expect_sequence_contents().unwrap();
+        assert_eq!(
+            parser.expect::<Integer>().unwrap_err().variant,
+            ASNErrorVariant::BadLengthEncoding(1, 1)
+        );
+
+        // the options carry over to the parser of the SEQUENCE contents
+        let mut parser = Parser::with_options(&input, ParseOptions::lenient())
+            .expect_sequence_contents()
+            .unwrap();
+        assert_eq!(parser.expect::<Integer>().unwrap().as_i32(), Some(5));
+        assert_eq!(
+            parser.expect::<ObjectIdentifier>().unwrap().values(),
+            [1, 2, 1]
+        );
+    }
+
+    #[test]
+    fn parses_indefinite_lengths_only_when_allowed() {
+        // SEQUENCE { INTEGER 5, SEQUENCE { NULL } } with both SEQUENCEs of indefinite length
+        let input = [
+            0x30, 0x80, 0x02, 0x01, 0x05, 0x30, 0x80, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        assert_eq!(
+            Parser::new(&input)
+                .expect::<Sequence>()
+                .unwrap_err()
+                .variant,
+            ASNErrorVariant::UnsupportedIndefiniteLength
+        );
+
+        let mut parser = Parser::with_options(&input, ParseOptions::lenient());
+        let mut contents = parser.expect_sequence_contents().unwrap();
+        assert!(parser.is_empty());
+        assert_eq!(contents.expect::<Integer>().unwrap().as_i32(), Some(5));
+        let mut nested = contents.expect_sequence_contents().unwrap();
+        assert!(contents.is_empty());
+        assert_eq!(nested.expect_any(), Ok(ASNType::Null));
+        assert!(nested.is_empty());
+
+        // the end-of-contents marker is missing
+        let mut parser = Parser::with_options(&input[..11], ParseOptions::lenient());
+        assert_eq!(
+            parser.expect::<Sequence>().unwrap_err().variant,
+            ASNErrorVariant::EndOfStream
+        );
+
+        // primitive values must use a definite length even in BER
+        let mut parser = Parser::with_options(&[0x04, 0x80, 0x00, 0x00], ParseOptions::lenient());
+        assert_eq!(
+            parser.expect::<OctetString>().unwrap_err().variant,
+            ASNErrorVariant::UnsupportedIndefiniteLength
+        );
+    }
+
+    #[test]
+    fn limits_the_nesting_depth_of_contents() {
+        let options = ParseOptions {
+            max_depth: 2,
+            ..ParseOptions::default()
+        };
+
+        // SEQUENCE { SEQUENCE { SEQUENCE {} } }
+        let input = [0x30, 0x04, 0x30, 0x02, 0x30, 0x00];
+        let mut parser = Parser::with_options(&input, options)
+            .expect_sequence_contents()
+            .unwrap()
+            .expect_sequence_contents()
+            .unwrap();
+        let err = parser
+            .expect_sequence_contents()
+            .err()
+            .map(|err| err.relative_to(&input));
+        assert_eq!(
+            err.as_ref().map(|err| &err.variant),
+            Some(&ASNErrorVariant::MaxDepthExceeded(2))
+        );
+        assert_eq!(err.and_then(|err| err.offset()), Some(4));
+
+        // the same nesting with indefinite lengths
+        let input = [
+            0x30, 0x80, 0x30, 0x80, 0x30, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let lenient = ParseOptions {
+            max_depth: 2,
+            ..ParseOptions::lenient()
+        };
+        assert_eq!(
+            Parser::with_options(&input, lenient)
+                .expect::<Sequence>()
+                .unwrap_err()
+                .variant,
+            ASNErrorVariant::MaxDepthExceeded(2)
+        );
+        let lenient = ParseOptions {
+            max_depth: 3,
+            ..ParseOptions::lenient()
+        };
+        assert!(Parser::with_options(&input, lenient)
+            .expect::<Sequence>()
+            .is_ok());
+    }
+
+    #[test]
+    fn string_character_sets_follow_the_options() {
+        // IA5String "é", PrintableString "a_b"
+        let input = [0x16, 0x02, 0xC3, 0xA9, 0x13, 0x03, 0x61, 0x5F, 0x62];
+
+        let mut parser = Parser::new(&input);
+        assert_eq!(
+            parser.expect::<IA5String>().unwrap_err().variant,
+            ASNErrorVariant::NonAsciiIA5String(0xC3)
+        );
+        let mut parser = Parser::new(&input[4..]);
+        assert_eq!(parser.expect::<PrintableString>().unwrap(), "a_b");
+
+        let mut parser = Parser::with_options(&input, ParseOptions::lenient());
+        assert_eq!(parser.expect::<IA5String>().unwrap(), "é");
+        assert_eq!(parser.expect::<PrintableString>().unwrap(), "a_b");
+
+        let mut parser = Parser::with_options(&input[4..], ParseOptions::strict());
+        assert_eq!(
+            parser.expect::<PrintableString>().unwrap_err().variant,
+            ASNErrorVariant::BadPrintableChar(b'_')
+        );
+    }
+
+    #[test]
+    fn iterates_encoded_object_identifier_arcs() {
+        // sha1WithRSAEncryption
+        let arcs: Vec<_> = ASNObjectIdentifier::iter_encoded(&[
+            0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x05,
+        ])
+        .collect();
+        assert_eq!(arcs, [1, 2, 840, 113549, 1, 1, 5].map(Ok::<u128, ASNError>));
+    }
+
+    #[test]
+    fn iteration_stops_after_truncated_final_arc() {
+        let mut arcs = ASNObjectIdentifier::iter_encoded(&[0x2A, 0x86, 0x48, 0x86, 0xF7]);
+        assert_eq!(arcs.next(), Some(Ok(1)));
+        assert_eq!(arcs.next(), Some(Ok(2)));
+        assert_eq!(arcs.next(), Some(Ok(840)));
+        assert_eq!(arcs.next(), Some(Err(ASNErrorVariant::EndOfStream.into())));
+        assert_eq!(arcs.next(), None);
+
+        assert_eq!(
+            ASNObjectIdentifier::iter_encoded(&[]).collect::<Vec<_>>(),
+            [Err(ASNErrorVariant::EndOfStream.into())]
+        );
+    }
+
+    #[test]
+    fn rejects_arcs_with_leading_zero_groups() {
+        assert_eq!(
+            parse_object_identifier(&[0x2A, 0x80, 0x01], ParseOptions::default(), None),
+            Err(ASNErrorVariant::NonMinimalOid)
+        );
+        assert_eq!(
+            parse_object_identifier(&[0x2A, 0x81, 0x00], ParseOptions::default(), None),
+            Ok(ObjectIdentifier::asn(ASNObjectIdentifier::new(vec![
+                1, 2, 128
+            ])))
+        );
+    }
+
+    #[test]
+    fn parses_arcs_up_to_128_bits() {
+        // 2.25.18446744073709551615
+        assert_eq!(
+            parse_object_identifier(
+                &[0x69, 0x81, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F],
+                ParseOptions::default(),
+                None
+            ),
+            Ok(ObjectIdentifier::asn(ASNObjectIdentifier::new(vec![
+                2,
+                25,
+                u128::from(u64::MAX)
+            ])))
+        );
+
+        // 2.25.329800735698586629295641978511506172918 (f81d4fae-7dec-11d0-a765-00a0c91e6bf6)
+        let uuid = [
+            0x69, 0x83, 0xF0, 0x9D, 0xA7, 0xEB, 0xCF, 0xDE, 0xE0, 0xC7, 0xA1, 0xA7, 0xB2, 0xC0,
+            0x94, 0x8C, 0xC8, 0xF9, 0xD7, 0x76,
+        ];
+        assert_eq!(
+            parse_object_identifier(&uuid, ParseOptions::default(), None),
+            Ok(ObjectIdentifier::asn(ASNObjectIdentifier::new(vec![
+                2,
+                25,
+                0xf81d4fae_7dec_11d0_a765_00a0c91e6bf6
+            ])))
+        );
+
+        // 2.25.(2^128), one bit too many
+        let mut too_wide = vec![0x69, 0x84];
+        too_wide.extend_from_slice(&[0x80; 17]);
+        too_wide.push(0x00);
+        assert_eq!(
+            parse_object_identifier(&too_wide, ParseOptions::default(), None),
+            Err(ASNErrorVariant::BadOidLength)
+        );
+
+        // non-terminated final group
+        assert_eq!(
+            parse_object_identifier(&[0x69, 0x81, 0xFF], ParseOptions::default(), None),
+            Err(ASNErrorVariant::EndOfStream)
         );
     }
 
@@ -695,7 +2112,11 @@ mod tests {
     fn parses_known_object_identifiers() {
         // Microsoft: szOID_REQUEST_CLIENT_INFO
         assert_eq!(
-            parse_object_identifier(&[0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x15, 0x14]),
+            parse_object_identifier(
+                &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x15, 0x14],
+                ParseOptions::default(),
+                None
+            ),
             Ok(ObjectIdentifier::asn(ASNObjectIdentifier::new(
                 [1, 3, 6, 1, 4, 1, 311, 21, 20].to_vec()
             )))
@@ -703,10 +2124,48 @@ mod tests {
 
         // sha1WithRSAEncryption
         assert_eq!(
-            parse_object_identifier(&[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x05]),
+            parse_object_identifier(
+                &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x05],
+                ParseOptions::default(),
+                None
+            ),
             Ok(ObjectIdentifier::asn(ASNObjectIdentifier::new(
                 [1, 2, 840, 113549, 1, 1, 5].to_vec()
             )))
         );
     }
+
+    #[test]
+    fn parses_implicitly_tagged_integer() {
+        // [0] IMPLICIT INTEGER 256
+        let mut parser = Parser::new(&[0x80, 0x02, 0x01, 0x00]);
+        let contents = parser.get_optional_context_field(0).unwrap().unwrap();
+        let value = Parser::new(contents).parse_implicit::<Integer>().unwrap();
+        assert_eq!(value.as_i32(), Some(256));
+        assert!(parser.is_empty());
+
+        assert_eq!(
+            Parser::new(&[])
+                .parse_implicit::<Integer>()
+                .unwrap_err()
+                .variant,
+            ASNErrorVariant::ZeroLengthInteger
+        );
+    }
+
+    #[test]
+    fn parses_implicitly_tagged_boolean() {
+        // [1] IMPLICIT BOOLEAN TRUE
+        let mut parser = Parser::new(&[0x81, 0x01, 0xFF]);
+        let contents = parser.get_optional_context_field(1).unwrap().unwrap();
+        assert_eq!(Parser::new(contents).parse_implicit::<Boolean>(), Ok(true));
+
+        assert_eq!(
+            Parser::new(&[0x01])
+                .parse_implicit::<Boolean>()
+                .unwrap_err()
+                .variant,
+            ASNErrorVariant::BadBooleanValue(0x01)
+        );
+    }
 }
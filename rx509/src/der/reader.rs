@@ -34,13 +34,6 @@ impl<'a> Reader<'a> {
         self.bytes.len()
     }
 
-    pub fn peek_byte(&self) -> Result<u8, EndOfStream> {
-        match self.bytes.first() {
-            None => Err(EndOfStream),
-            Some(x) => Ok(*x),
-        }
-    }
-
     pub fn read_byte(&mut self) -> Result<u8, EndOfStream> {
         let (first, remainder) = self.bytes.split_first().ok_or(EndOfStream)?;
         self.bytes = remainder;
@@ -57,6 +50,11 @@ impl<'a> Reader<'a> {
     pub fn remainder(&self) -> &'a [u8] {
         self.bytes
     }
+
+    /// Consume and return everything that is left
+    pub fn take_remaining(&mut self) -> &'a [u8] {
+        core::mem::take(&mut self.bytes)
+    }
 }
 
 #[cfg(test)]
@@ -87,4 +85,14 @@ mod tests {
         assert_eq!(taken.len(), 3);
         assert_eq!(taken, &[0x01, 0x02, 0x03]);
     }
+
+    #[test]
+    fn take_remaining_empties_the_input() {
+        let mut input = Reader::new(&[0x01, 0x02, 0x03]);
+        input.read_byte().unwrap();
+
+        assert_eq!(input.take_remaining(), &[0x02, 0x03]);
+        assert!(input.is_empty());
+        assert_eq!(input.take_remaining(), &[]);
+    }
 }
@@ -0,0 +1,202 @@
+use crate::der::options::ParseOptions;
+use crate::der::parser::{element_len, Parser};
+use crate::der::types::{ASNError, ASNErrorVariant, ASNType};
+
+use std::io::{ErrorKind, Read};
+
+// bytes requested from the source by each read
+const CHUNK_SIZE: usize = 4096;
+
+/// Errors that can occur while reading elements from a [`StreamParser`]
+#[derive(Debug)]
+pub enum StreamError {
+    /// the source failed with an error other than `WouldBlock` or `Interrupted`
+    Io(std::io::Error),
+    /// the element is malformed or the source ended inside of it, offsets are relative to the
+    /// start of the element
+    Parse(ASNError),
+}
+
+impl core::fmt::Display for StreamError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            StreamError::Io(err) => write!(f, "read failed: {}", err),
+            StreamError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+/// The outcome of [`StreamParser::read_element`]
+#[derive(Debug, PartialEq, Eq)]
+pub enum StreamStatus<'a> {
+    /// a complete top-level element along with its encoding, identifier and length included
+    Element { value: ASNType<'a>, der: &'a [u8] },
+    /// more bytes are needed, either the source would block or it didn't complete the element
+    Incomplete,
+    /// the source ended between two elements
+    End,
+}
+
+/// Decodes top-level elements as their bytes arrive from a [`Read`] source
+///
+/// The bytes of the element being received are buffered internally. The length octets tell how
+/// many bytes each element needs, so nothing is decoded until the whole element is available.
+/// After an error the stream can't be resumed.
+pub struct StreamParser<R: Read> {
+    source: R,
+    options: ParseOptions,
+    buffer: Vec<u8>,
+    // length of the element returned by the previous call, removed from the buffer by the next
+    consumed: usize,
+}
+
+impl<R: Read> StreamParser<R> {
+    pub fn new(source: R) -> Self {
+        Self::with_options(source, ParseOptions::default())
+    }
+
+    /// Like [`StreamParser::new`], but applying the checks selected by `options`
+    ///
+    /// [`ParseOptions::reject_trailing_data`] is ignored since a stream may hold any number of
+    /// elements.
+    pub fn with_options(source: R, options: ParseOptions) -> Self {
+        Self {
+            source,
+            options,
+            buffer: Vec::new(),
+            consumed: 0,
+        }
+    }
+
+    /// Return the next element if it's already buffered, otherwise read from the source once
+    pub fn read_element(&mut self) -> Result<StreamStatus<'_>, StreamError> {
+        self.buffer.drain(..self.consumed);
+        self.consumed = 0;
+
+        let len = match self.buffered_len()? {
+            Some(len) => len,
+            None => {
+                let mut chunk = [0; CHUNK_SIZE];
+                match self.source.read(&mut chunk) {
+                    Ok(0) if self.buffer.is_empty() => return Ok(StreamStatus::End),
+                    Ok(0) => {
+                        return Err(StreamError::Parse(ASNErrorVariant::EndOfStream.into()));
+                    }
+                    Ok(count) => self.buffer.extend_from_slice(&chunk[..count]),
+                    Err(err)
+                        if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::Interrupted) =>
+                    {
+                        return Ok(StreamStatus::Incomplete);
+                    }
+                    Err(err) => return Err(StreamError::Io(err)),
+                }
+                match self.buffered_len()? {
+                    Some(len) => len,
+                    None => return Ok(StreamStatus::Incomplete),
+                }
+            }
+        };
+
+        self.consumed = len;
+        let der = &self.buffer[..len];
+        let value = Parser::with_options(der, self.options)
+            .expect_any()
+            .map_err(|err| StreamError::Parse(err.relative_to(der)))?;
+        Ok(StreamStatus::Element { value, der })
+    }
+
+    // length of the first element if the buffer holds all of it
+    fn buffered_len(&self) -> Result<Option<usize>, StreamError> {
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+        match element_len(&self.buffer, self.options) {
+            Ok(len) => Ok(Some(len)),
+            Err(ASNErrorVariant::EndOfStream) => Ok(None),
+            Err(err) => Err(StreamError::Parse(
+                ASNError::at(err, &self.buffer).relative_to(&self.buffer),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::der::types::ASNTypeId;
+
+    // hands out a single byte per read, reporting that it would block in between
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        ready: bool,
+    }
+
+    impl<'a> Read for Trickle<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.ready = !self.ready;
+            if !self.ready {
+                return Err(ErrorKind::WouldBlock.into());
+            }
+            match self.bytes.split_first() {
+                Some((first, rest)) if !buf.is_empty() => {
+                    buf[0] = *first;
+                    self.bytes = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    #[test]
+    fn yields_an_element_fed_one_byte_at_a_time() {
+        let cert = include_bytes!("../../../certs/512b-rsa-example-cert.der");
+        let mut parser = StreamParser::new(Trickle {
+            bytes: cert,
+            ready: false,
+        });
+
+        let mut calls = 0;
+        loop {
+            calls += 1;
+            match parser.read_element().unwrap() {
+                StreamStatus::Incomplete => {}
+                StreamStatus::Element { value, der } => {
+                    assert!(matches!(value, ASNType::Sequence(_)));
+                    assert_eq!(der, &cert[..]);
+                    break;
+                }
+                StreamStatus::End => panic!("the certificate wasn't yielded"),
+            }
+        }
+        // the reads of each byte alternate with reads that would block
+        assert_eq!(calls, 2 * cert.len() - 1);
+
+        assert_eq!(parser.read_element().unwrap(), StreamStatus::Incomplete);
+        assert_eq!(parser.read_element().unwrap(), StreamStatus::End);
+    }
+
+    #[test]
+    fn yields_consecutive_elements_and_reports_truncation() {
+        // INTEGER 5, NULL, then the header of an OCTET STRING missing its last byte
+        let input = [0x02, 0x01, 0x05, 0x05, 0x00, 0x04, 0x02, 0xAA];
+        let mut parser = StreamParser::new(&input[..]);
+
+        let mut values = Vec::new();
+        loop {
+            match parser.read_element() {
+                Ok(StreamStatus::Element { value, .. }) => values.push(value.get_id()),
+                Ok(StreamStatus::Incomplete) => {}
+                Ok(StreamStatus::End) => panic!("the last element is truncated"),
+                Err(StreamError::Parse(err)) => {
+                    assert_eq!(err.variant, ASNErrorVariant::EndOfStream);
+                    break;
+                }
+                Err(err) => panic!("unexpected error: {}", err),
+            }
+        }
+        assert_eq!(values, [ASNTypeId::Integer, ASNTypeId::Null]);
+    }
+}
@@ -1,4 +1,10 @@
+use std::borrow::Cow;
+
+use crate::der::calendar;
+use crate::der::hex;
+#[cfg(feature = "display")]
 use crate::der::oid::get_oid;
+use crate::der::parser;
 use crate::der::reader;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -24,15 +30,20 @@ pub enum PC {
 pub struct Identifier {
     pub class: TagClass,
     pub pc: PC,
-    pub tag: u8,
+    pub tag: u32,
 }
 
 impl Identifier {
-    pub fn new(class: TagClass, pc: PC, tag: u8) -> Identifier {
+    // low 5 bits of the first byte that announce the high-tag-number (long) form
+    const LONG_FORM: u8 = 0b0001_1111;
+
+    pub fn new(class: TagClass, pc: PC, tag: u32) -> Identifier {
         Identifier { class, pc, tag }
     }
 
-    pub fn from(byte: u8) -> Identifier {
+    /// Read the identifier octets, including the subsequent octets of the high-tag-number form
+    pub(crate) fn read(reader: &mut reader::Reader) -> Result<Identifier, ASNErrorVariant> {
+        let byte = reader.read_byte()?;
         let class = match byte & 0b1100_0000 {
             0b0000_0000 => TagClass::Universal,
             0b0100_0000 => TagClass::Application,
@@ -46,48 +57,126 @@ impl Identifier {
             PC::Primitive
         };
 
-        let tag = byte & 0b0001_1111;
+        let tag = match byte & Self::LONG_FORM {
+            Self::LONG_FORM => Self::read_long_form_tag(reader)?,
+            tag => u32::from(tag),
+        };
+
+        Ok(Identifier::new(class, pc, tag))
+    }
+
+    // base 128 digits, most significant first, with the high bit set on all but the last
+    fn read_long_form_tag(reader: &mut reader::Reader) -> Result<u32, ASNErrorVariant> {
+        let first = reader.read_byte()?;
+        // X.690 8.1.2.4.2 (c) forbids a leading digit of zero
+        if first == 0x80 {
+            return Err(ASNErrorVariant::BadTagEncoding);
+        }
+
+        let mut byte = first;
+        let mut tag: u32 = 0;
+        loop {
+            if tag.leading_zeros() < 7 {
+                return Err(ASNErrorVariant::TagNumberTooLarge);
+            }
+            tag = (tag << 7) | u32::from(byte & 0b0111_1111);
+            if byte & 0b1000_0000 == 0 {
+                break;
+            }
+            byte = reader.read_byte()?;
+        }
 
-        Identifier::new(class, pc, tag)
+        // tags up to 30 must use the single byte form
+        if tag < u32::from(Self::LONG_FORM) {
+            return Err(ASNErrorVariant::BadTagEncoding);
+        }
+
+        Ok(tag)
     }
 }
 
 impl<'a> ASNInteger<'a> {
-    const VALID_I32_LENGTHS: core::ops::Range<usize> = 1usize..4usize;
-
-    pub fn new(bytes: &'a [u8]) -> ASNInteger {
+    pub fn new(bytes: &'a [u8]) -> ASNInteger<'a> {
         ASNInteger { bytes }
     }
 
+    /// Content bytes exactly as encoded, including any leading sign byte
+    ///
+    /// Use this to copy a value such as a serialNumber verbatim into another structure (e.g. an OCSP CertID).
+    pub fn der_content(&self) -> &'a [u8] {
+        self.bytes
+    }
+
     pub fn as_i32(&self) -> Option<i32> {
-        // can only parse values with length in [1,4] bytes
-        if !ASNInteger::VALID_I32_LENGTHS.contains(&self.bytes.len()) {
+        self.sign_extend(4).map(|x| x as i32)
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        self.sign_extend(8).map(|x| x as i64)
+    }
+
+    /// Returns `None` for negative values
+    pub fn as_u64(&self) -> Option<u64> {
+        self.unsigned(8).map(|x| x as u64)
+    }
+
+    /// Returns `None` for negative values
+    pub fn as_u128(&self) -> Option<u128> {
+        self.unsigned(16)
+    }
+
+    /// Value of any size, e.g. a serial number or an RSA modulus
+    ///
+    /// The content bytes are interpreted as two's complement, so a leading 0x00 that only keeps
+    /// the value positive doesn't change the magnitude. An empty INTEGER, which the parser
+    /// rejects, is treated as zero.
+    #[cfg(feature = "bigint")]
+    pub fn as_bignum(&self) -> num_bigint::BigInt {
+        num_bigint::BigInt::from_signed_bytes_be(self.bytes)
+    }
+
+    // two's complement value of at most `max_len` content bytes
+    fn sign_extend(&self, max_len: usize) -> Option<i128> {
+        let first = *self.bytes.first()?;
+        if self.bytes.len() > max_len {
             return None;
         }
 
-        let mut acc: i32 = 0;
+        // sign extend from the most significant bit of the first byte
+        let mut acc: i128 = if first & 0x80 != 0 { -1 } else { 0 };
         for byte in self.bytes {
-            acc <<= 8;
-            acc |= *byte as i32;
+            acc = (acc << 8) | i128::from(*byte);
+        }
+        Some(acc)
+    }
+
+    // non-negative value of at most `max_len` bytes, not counting a leading sign byte
+    fn unsigned(&self, max_len: usize) -> Option<u128> {
+        let magnitude = match self.bytes {
+            [] => return None,
+            [first, ..] if first & 0x80 != 0 => return None,
+            [0x00, rest @ ..] if !rest.is_empty() => rest,
+            bytes => bytes,
+        };
+        if magnitude.len() > max_len {
+            return None;
+        }
+
+        let mut acc: u128 = 0;
+        for byte in magnitude {
+            acc = (acc << 8) | u128::from(*byte);
         }
         Some(acc)
     }
 }
 
+#[cfg(feature = "display")]
 impl<'a> std::fmt::Display for ASNInteger<'a> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self.as_i32() {
             Some(x) => write!(f, "{}", x),
-            None => {
-                if let Some((tail, head)) = self.bytes.split_last() {
-                    for byte in head {
-                        write!(f, "{:02X}:", byte)?;
-                    }
-                    write!(f, "{:02X}", tail)
-                } else {
-                    write!(f, "[]")
-                }
-            }
+            None if self.bytes.is_empty() => write!(f, "[]"),
+            None => f.write_str(&hex::to_hex(self.bytes)),
         }
     }
 }
@@ -95,9 +184,9 @@ impl<'a> std::fmt::Display for ASNInteger<'a> {
 #[derive(Debug, PartialEq, Eq)]
 pub struct ASNBitString<'a> {
     // the number of unused bits in last octet [0, 7]
-    unused_bits: u8,
+    pub(crate) unused_bits: u8,
     // the octets, the last one only has (8 - unused_bits) bits
-    bytes: &'a [u8],
+    pub(crate) bytes: &'a [u8],
 }
 
 impl<'a> ASNBitString<'a> {
@@ -105,8 +194,15 @@ impl<'a> ASNBitString<'a> {
         ASNBitString { unused_bits, bytes }
     }
 
+    /// The octets as colon-separated hex, see [`to_hex`](crate::der::to_hex)
+    ///
+    /// Unused bits are shown as encoded, i.e. as zeros in the last octet.
+    pub fn to_hex(&self) -> String {
+        hex::to_hex(self.bytes)
+    }
+
     // convertible to octets if it's all full bytes
-    pub fn octets(&self) -> Option<&[u8]> {
+    pub fn octets(&self) -> Option<&'a [u8]> {
         if self.unused_bits == 0 {
             Some(self.bytes)
         } else {
@@ -121,6 +217,33 @@ impl<'a> ASNBitString<'a> {
     pub fn iter(&'a self) -> ASNBitStringIterator<'a> {
         ASNBitStringIterator::new(self)
     }
+
+    // packs up to 64 bits into an integer, left-aligned so that named bit n is
+    // bit (63 - n) regardless of how many trailing zero bytes were trimmed
+    pub fn as_u64_be(&self) -> Option<u64> {
+        if self.bytes.len() > 8 {
+            return None;
+        }
+        let value = self
+            .bytes
+            .iter()
+            .enumerate()
+            .fold(0u64, |acc, (i, b)| acc | (*b as u64) << (56 - 8 * i));
+        // clear any unused bits that a non-DER encoder may have set
+        let mask = match self.size() {
+            0 => 0,
+            size => u64::MAX << (64 - size),
+        };
+        Some(value & mask)
+    }
+
+    // same as as_u64_be with named bit n as bit (31 - n), None past 32 significant bits
+    pub fn as_u32_be(&self) -> Option<u32> {
+        if self.size() > 32 {
+            return None;
+        }
+        self.as_u64_be().map(|x| (x >> 32) as u32)
+    }
 }
 
 pub struct ASNBitStringIterator<'a> {
@@ -157,45 +280,92 @@ impl<'a> Iterator for ASNBitStringIterator<'a> {
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct ASNExplicitTag<'a> {
-    pub value: u8,
+    pub value: u32,
     pub contents: &'a [u8],
 }
 
 impl<'a> ASNExplicitTag<'a> {
-    pub fn new(value: u8, contents: &'a [u8]) -> ASNExplicitTag<'a> {
+    pub fn new(value: u32, contents: &'a [u8]) -> ASNExplicitTag<'a> {
         ASNExplicitTag { value, contents }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ASNObjectIdentifier {
-    items: Vec<u32>,
+/// Object identifiers order lexicographically by arc, e.g. 1.2 < 1.2.0 < 1.3
+///
+/// Each arc holds up to 128 bits, enough for the UUID arcs under 2.25 (ITU-T X.667). The arcs
+/// are either owned or borrowed, e.g. from a constant or the arena of an arena parse.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ASNObjectIdentifier<'a> {
+    items: Cow<'a, [u128]>,
 }
 
-impl ASNObjectIdentifier {
-    pub fn new(items: Vec<u32>) -> ASNObjectIdentifier {
-        ASNObjectIdentifier { items }
+/// Build an [`ASNObjectIdentifier`] from its arcs, e.g. `oid!(1, 3, 6, 1, 5, 5, 7, 3, 1)`
+#[macro_export]
+macro_rules! oid {
+    ($($arc:expr),+ $(,)?) => {
+        $crate::der::ASNObjectIdentifier::new(vec![$($arc),+])
+    };
+}
+
+impl<'a> ASNObjectIdentifier<'a> {
+    pub fn new(items: Vec<u128>) -> ASNObjectIdentifier<'a> {
+        ASNObjectIdentifier {
+            items: Cow::Owned(items),
+        }
+    }
+
+    /// OID over arcs stored elsewhere, which aren't copied
+    pub fn from_slice(items: &'a [u128]) -> ASNObjectIdentifier<'a> {
+        ASNObjectIdentifier {
+            items: Cow::Borrowed(items),
+        }
+    }
+
+    /// Copy borrowed arcs, so the OID can outlive them
+    pub fn into_owned(self) -> ASNObjectIdentifier<'static> {
+        ASNObjectIdentifier::new(self.items.into_owned())
+    }
+
+    pub fn values(&self) -> &[u128] {
+        &self.items
+    }
+
+    /// Dotted-decimal form, e.g. "2.5.4.3", even when the OID has a friendly name
+    pub fn to_dotted_string(&self) -> String {
+        let arcs: Vec<String> = self.items.iter().map(|x| x.to_string()).collect();
+        arcs.join(".")
     }
 
-    pub fn values(&self) -> &[u32] {
-        self.items.as_slice()
+    /// Iterate the arcs of the contents of an encoded OBJECT IDENTIFIER without allocating
+    ///
+    /// The first two arcs are decoded from the leading byte. Iteration ends after the first error.
+    pub fn iter_encoded(contents: &[u8]) -> impl Iterator<Item = Result<u128, ASNError>> + '_ {
+        parser::EncodedArcs::new(contents).map(|arc| arc.map_err(ASNError::from))
+    }
+
+    /// Decode the contents of an OBJECT IDENTIFIER, the inverse of `encode`
+    pub fn from_encoded(contents: &[u8]) -> Result<ASNObjectIdentifier<'a>, ASNError> {
+        Ok(ASNObjectIdentifier::new(
+            ASNObjectIdentifier::iter_encoded(contents).collect::<Result<_, _>>()?,
+        ))
     }
 }
 
-impl std::fmt::Display for ASNObjectIdentifier {
+impl std::fmt::Display for ASNObjectIdentifier<'_> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
-        match get_oid(self.values()) {
-            Some(oid) => f.write_str(oid.to_str()),
-            None => {
-                if let Some((last, first)) = self.values().split_last() {
-                    for value in first {
-                        write!(f, "{}.", value)?;
-                    }
-                    write!(f, "{}", last)?;
-                }
-                Ok(())
+        // friendly names are only available with the display feature
+        #[cfg(feature = "display")]
+        if let Some(oid) = get_oid(self.values()) {
+            return f.write_str(oid.to_str());
+        }
+
+        if let Some((last, first)) = self.values().split_last() {
+            for value in first {
+                write!(f, "{}.", value)?;
             }
+            write!(f, "{}", last)?;
         }
+        Ok(())
     }
 }
 
@@ -278,6 +448,54 @@ impl<'a> ASNWrapperType<'a> for PrintableString<'a> {
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub struct NumericString<'a> {
+    pub value: &'a str,
+}
+impl<'a> NumericString<'a> {
+    pub fn asn(value: &'a str) -> ASNType<'a> {
+        ASNType::NumericString(NumericString { value })
+    }
+}
+impl<'a> ASNWrapperType<'a> for NumericString<'a> {
+    type Item = &'a str;
+
+    fn get_id() -> ASNTypeId {
+        ASNTypeId::NumericString
+    }
+
+    fn get_value(asn_type: ASNType<'a>) -> Option<Self::Item> {
+        match asn_type {
+            ASNType::NumericString(wrapper) => Some(wrapper.value),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct VisibleString<'a> {
+    pub value: &'a str,
+}
+impl<'a> VisibleString<'a> {
+    pub fn asn(value: &'a str) -> ASNType<'a> {
+        ASNType::VisibleString(VisibleString { value })
+    }
+}
+impl<'a> ASNWrapperType<'a> for VisibleString<'a> {
+    type Item = &'a str;
+
+    fn get_id() -> ASNTypeId {
+        ASNTypeId::VisibleString
+    }
+
+    fn get_value(asn_type: ASNType<'a>) -> Option<Self::Item> {
+        match asn_type {
+            ASNType::VisibleString(wrapper) => Some(wrapper.value),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct IA5String<'a> {
     pub value: &'a str,
@@ -326,6 +544,56 @@ impl<'a> ASNWrapperType<'a> for UTF8String<'a> {
     }
 }
 
+/// Decoded from big-endian UCS-2, so the value is owned rather than borrowed from the input
+#[derive(Debug, PartialEq, Eq)]
+pub struct BMPString {
+    pub value: String,
+}
+impl BMPString {
+    pub fn asn<'a>(value: String) -> ASNType<'a> {
+        ASNType::BMPString(BMPString { value })
+    }
+}
+impl<'a> ASNWrapperType<'a> for BMPString {
+    type Item = String;
+
+    fn get_id() -> ASNTypeId {
+        ASNTypeId::BMPString
+    }
+
+    fn get_value(asn_type: ASNType<'a>) -> Option<Self::Item> {
+        match asn_type {
+            ASNType::BMPString(wrapper) => Some(wrapper.value),
+            _ => None,
+        }
+    }
+}
+
+/// T61String contents decoded as Latin-1, which is how most implementations treat them in practice
+#[derive(Debug, PartialEq, Eq)]
+pub struct TeletexString {
+    pub value: String,
+}
+impl TeletexString {
+    pub fn asn<'a>(value: String) -> ASNType<'a> {
+        ASNType::TeletexString(TeletexString { value })
+    }
+}
+impl<'a> ASNWrapperType<'a> for TeletexString {
+    type Item = String;
+
+    fn get_id() -> ASNTypeId {
+        ASNTypeId::TeletexString
+    }
+
+    fn get_value(asn_type: ASNType<'a>) -> Option<Self::Item> {
+        match asn_type {
+            ASNType::TeletexString(wrapper) => Some(wrapper.value),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct Sequence<'a> {
     pub value: &'a [u8],
@@ -375,16 +643,16 @@ impl<'a> ASNWrapperType<'a> for Set<'a> {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-pub struct ObjectIdentifier {
-    pub value: ASNObjectIdentifier,
+pub struct ObjectIdentifier<'a> {
+    pub value: ASNObjectIdentifier<'a>,
 }
-impl ObjectIdentifier {
-    pub fn asn<'a>(value: ASNObjectIdentifier) -> ASNType<'a> {
+impl<'a> ObjectIdentifier<'a> {
+    pub fn asn(value: ASNObjectIdentifier<'a>) -> ASNType<'a> {
         ASNType::ObjectIdentifier(ObjectIdentifier { value })
     }
 }
-impl<'a> ASNWrapperType<'a> for ObjectIdentifier {
-    type Item = ASNObjectIdentifier;
+impl<'a> ASNWrapperType<'a> for ObjectIdentifier<'a> {
+    type Item = ASNObjectIdentifier<'a>;
 
     fn get_id() -> ASNTypeId {
         ASNTypeId::ObjectIdentifier
@@ -460,6 +728,11 @@ impl UtcTime {
         Self { value: secs }
     }
 
+    /// ISO 8601 form in UTC with second precision, e.g. "2012-08-22T05:26:54Z"
+    pub fn to_iso8601(&self) -> String {
+        self.to_string()
+    }
+
     pub fn now() -> Result<Self, ASNError> {
         std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -467,6 +740,18 @@ impl UtcTime {
             .map_err(|_| ASNErrorVariant::BadUTCTime.into())
     }
 }
+
+/// Formats as ISO 8601 in UTC, see [`UtcTime::to_iso8601`]
+impl core::fmt::Display for UtcTime {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let (year, month, day, hours, minutes, seconds) = calendar::ymdhms_utc_from_time(*self);
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year, month, day, hours, minutes, seconds
+        )
+    }
+}
 impl<'a> ASNWrapperType<'a> for UtcTime {
     type Item = UtcTime;
 
@@ -482,6 +767,29 @@ impl<'a> ASNWrapperType<'a> for UtcTime {
     }
 }
 
+/// GeneralizedTime, normalized into the same representation as UTCTime
+#[derive(Debug, PartialEq, Eq)]
+pub struct GeneralizedTime;
+impl GeneralizedTime {
+    pub fn asn<'a>(value: u64) -> ASNType<'a> {
+        ASNType::GeneralizedTime(UtcTime { value })
+    }
+}
+impl<'a> ASNWrapperType<'a> for GeneralizedTime {
+    type Item = UtcTime;
+
+    fn get_id() -> ASNTypeId {
+        ASNTypeId::GeneralizedTime
+    }
+
+    fn get_value(asn_type: ASNType<'a>) -> Option<Self::Item> {
+        match asn_type {
+            ASNType::GeneralizedTime(wrapper) => Some(wrapper),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct ExplicitTag<'a> {
     pub value: ASNExplicitTag<'a>,
@@ -514,19 +822,23 @@ pub enum ASNType<'a> {
     Integer(Integer<'a>),
     PrintableString(PrintableString<'a>),
     IA5String(IA5String<'a>),
+    NumericString(NumericString<'a>),
+    VisibleString(VisibleString<'a>),
     UTF8String(UTF8String<'a>),
+    BMPString(BMPString),
+    TeletexString(TeletexString),
     Null,
     UTCTime(UtcTime),
     GeneralizedTime(UtcTime),
     BitString(BitString<'a>),
     OctetString(OctetString<'a>),
-    ObjectIdentifier(ObjectIdentifier),
+    ObjectIdentifier(ObjectIdentifier<'a>),
     ExplicitTag(ExplicitTag<'a>),
 }
 
 // An identifier for the type that carries no data
 // used for error purposes
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ASNTypeId {
     Boolean,
     Sequence,
@@ -534,7 +846,11 @@ pub enum ASNTypeId {
     Integer,
     PrintableString,
     IA5String,
+    NumericString,
+    VisibleString,
     UTF8String,
+    BMPString,
+    TeletexString,
     Null,
     UTCTime,
     GeneralizedTime,
@@ -553,7 +869,11 @@ impl<'a> ASNType<'a> {
             ASNType::Integer(_) => ASNTypeId::Integer,
             ASNType::PrintableString(_) => ASNTypeId::PrintableString,
             ASNType::IA5String(_) => ASNTypeId::IA5String,
+            ASNType::NumericString(_) => ASNTypeId::NumericString,
+            ASNType::VisibleString(_) => ASNTypeId::VisibleString,
             ASNType::UTF8String(_) => ASNTypeId::UTF8String,
+            ASNType::BMPString(_) => ASNTypeId::BMPString,
+            ASNType::TeletexString(_) => ASNTypeId::TeletexString,
             ASNType::Null => ASNTypeId::Null,
             ASNType::UTCTime(_) => ASNTypeId::UTCTime,
             ASNType::GeneralizedTime(_) => ASNTypeId::GeneralizedTime,
@@ -565,6 +885,7 @@ impl<'a> ASNType<'a> {
     }
 }
 
+#[cfg(feature = "display")]
 impl<'a> core::fmt::Display for ASNType<'a> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
@@ -583,11 +904,27 @@ impl<'a> core::fmt::Display for ASNType<'a> {
                 f.write_str("IA5String: ")?;
                 f.write_str(wrapper.value)
             }
+            ASNType::NumericString(wrapper) => {
+                f.write_str("NumericString: ")?;
+                f.write_str(wrapper.value)
+            }
+            ASNType::VisibleString(wrapper) => {
+                f.write_str("VisibleString: ")?;
+                f.write_str(wrapper.value)
+            }
+            ASNType::BMPString(wrapper) => {
+                f.write_str("BMPString: ")?;
+                f.write_str(&wrapper.value)
+            }
+            ASNType::TeletexString(wrapper) => {
+                f.write_str("TeletexString: ")?;
+                f.write_str(&wrapper.value)
+            }
             ASNType::Integer(wrapper) => write!(f, "Integer: {}", wrapper.value),
             ASNType::Null => f.write_str("Null"),
             ASNType::ObjectIdentifier(wrapper) => write!(f, "ObjectIdentifier: {}", wrapper.value),
-            ASNType::UTCTime(wrapper) => write!(f, "UTCTime: {}", wrapper.value),
-            ASNType::GeneralizedTime(wrapper) => write!(f, "GeneratlizedTime: {}", wrapper.value),
+            ASNType::UTCTime(wrapper) => write!(f, "UTCTime: {}", wrapper),
+            ASNType::GeneralizedTime(wrapper) => write!(f, "GeneralizedTime: {}", wrapper),
             ASNType::BitString(_) => f.write_str("BitString"),
             ASNType::OctetString(_) => f.write_str("OctetString"),
             ASNType::ExplicitTag(wrapper) => write!(f, "[{}]", wrapper.value.value),
@@ -598,11 +935,57 @@ impl<'a> core::fmt::Display for ASNType<'a> {
 #[derive(Debug, PartialEq, Eq)]
 pub struct ASNError {
     pub(crate) variant: ASNErrorVariant,
+    location: Location,
+}
+
+// where in the input an error occurred
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Location {
+    Unknown,
+    // address of the failing element, nested parsers only see sub-slices of the input
+    Address(usize),
+    // resolved by the entry point that received the input
+    Offset(usize),
+}
+
+impl ASNError {
+    /// Byte offset into the parsed input of the element that caused the error, if known
+    ///
+    /// Only reported by the entry points that take the input buffer, e.g. `Certificate::parse`.
+    pub fn offset(&self) -> Option<usize> {
+        match self.location {
+            Location::Offset(offset) => Some(offset),
+            _ => None,
+        }
+    }
+
+    /// Error for the element starting at `element`, which must be a sub-slice of the input
+    pub(crate) fn at(variant: ASNErrorVariant, element: &[u8]) -> Self {
+        Self {
+            variant,
+            location: Location::Address(element.as_ptr() as usize),
+        }
+    }
+
+    /// Resolve the location of the error into an offset within `input`
+    pub(crate) fn relative_to(mut self, input: &[u8]) -> Self {
+        if let Location::Address(address) = self.location {
+            let start = input.as_ptr() as usize;
+            self.location = match address.checked_sub(start) {
+                Some(offset) if offset <= input.len() => Location::Offset(offset),
+                _ => Location::Unknown,
+            };
+        }
+        self
+    }
 }
 
 impl core::convert::From<ASNErrorVariant> for ASNError {
     fn from(variant: ASNErrorVariant) -> Self {
-        Self { variant }
+        Self {
+            variant,
+            location: Location::Unknown,
+        }
     }
 }
 
@@ -612,33 +995,64 @@ pub(crate) enum ASNErrorVariant {
     BadBooleanLength(usize),
     BadBooleanValue(u8),
     EndOfStream,
+    EmptyInput,
     ZeroLengthInteger,
+    BadTagEncoding,    // non-minimal high-tag-number form
+    TagNumberTooLarge, // high-tag-number form that doesn't fit in u32
     NullWithNonEmptyContents(usize),
     UnsupportedId(Identifier),
+    PrimitiveConstructedTypeMismatch(Identifier), // supported type with the wrong encoding form
     UnsupportedIndefiniteLength,
+    MaxDepthExceeded(usize), // the limit on the nesting of constructed values
     ReservedLengthValue,
     UnsupportedLengthByteCount(u8),
     BadLengthEncoding(u8, usize), // count of bytes followed by the value
-    BadOidLength,
+    NonMinimalLength,             // multi-byte length with a leading zero byte
+    LengthExceedsPlatform(u64),   // decoded length that doesn't fit in usize
+    BadOidLength,                 // sub-identifier that doesn't fit in 128 bits
+    NonMinimalOid,                // sub-identifier starting with a 0x80 byte
+    BadOidArcs(ASNObjectIdentifier<'static>), // OID whose first two arcs can't be encoded
     BadUTF8(core::str::Utf8Error),
+    BadBMPString,          // odd number of bytes or an unpaired surrogate
+    NonAsciiIA5String(u8), // first byte above 0x7F
+    BadPrintableChar(u8),  // first byte outside the PrintableString character set
+    BadNumericChar(u8),    // first byte other than a digit or space
+    BadVisibleChar(u8),    // first byte outside 0x20 to 0x7E
     BadUTCTime,
+    YearOutOfRange(ASNTypeId, u64), // time type and the year it can't represent
+    NonLatin1Char(char),            // character a TeletexString can't encode
     BitStringUnusedBitsTooLarge(u8),
+    EmptyBitStringWithUnusedBits(u8),
     // these errors relate to schemas
     UnexpectedType(ASNTypeId, ASNTypeId), // the expected type followed by the actual type
     ExpectedEnd(ASNTypeId),               // type present instead of end
     IntegerTooLarge(usize),               // count of bytes
     BadEnumValue(&'static str, i32),      // name of the enum and the bad integer value
-    UnexpectedOid(ASNObjectIdentifier),   // unexpected object identifier
-    UnexpectedTag(u8),                    // unexpected tag
+    NegativeValue(&'static str, i32),     // name of the field and the negative value
+    UnexpectedTag(u32),                   // unexpected tag
+    DuplicateExtension(ASNObjectIdentifier<'static>), // extension OID that appears more than once
+    KeyUsageTooManyBits(usize),           // number of bits in the KeyUsage bit string
+    UnalignedBitString(u8),               // unused bits in a BIT STRING that must hold whole octets
 }
 
 impl core::fmt::Display for ASNError {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.variant)
+        write!(f, "{}", self.variant)?;
+        if let Some(offset) = self.offset() {
+            write!(f, " at byte {}", offset)?;
+        }
+        Ok(())
     }
 }
 
-impl std::error::Error for ASNError {}
+impl std::error::Error for ASNError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.variant {
+            ASNErrorVariant::BadUTF8(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 impl From<reader::EndOfStream> for ASNErrorVariant {
     fn from(_: reader::EndOfStream) -> Self {
@@ -661,9 +1075,21 @@ impl core::fmt::Display for ASNErrorVariant {
             ASNErrorVariant::NullWithNonEmptyContents(length) => {
                 write!(f, "NULL type w/ non-empty contents (length == {})", length)
             }
+            ASNErrorVariant::BadTagEncoding => {
+                f.write_str("Tag number not encoded in the minimum number of bytes")
+            }
+            ASNErrorVariant::TagNumberTooLarge => f.write_str("Tag number exceeds u32"),
             ASNErrorVariant::UnsupportedId(id) => write!(f, "Unsupported id: {:?})", id),
+            ASNErrorVariant::PrimitiveConstructedTypeMismatch(id) => write!(
+                f,
+                "Universal tag {} uses the wrong primitive/constructed form: {:?}",
+                id.tag, id.pc
+            ),
             ASNErrorVariant::UnsupportedIndefiniteLength => {
-                f.write_str("Encountered indefinite length encoding. Not allowed in DER.")
+                f.write_str("Indefinite-length encoding is BER, not DER")
+            }
+            ASNErrorVariant::MaxDepthExceeded(limit) => {
+                write!(f, "Nesting exceeds the maximum depth of {}", limit)
             }
             ASNErrorVariant::ReservedLengthValue => {
                 f.write_str("Length byte count of 127 is reserved")
@@ -671,17 +1097,59 @@ impl core::fmt::Display for ASNErrorVariant {
             ASNErrorVariant::UnsupportedLengthByteCount(length) => {
                 write!(f, "Length byte count of {} not supported", length)
             }
+            ASNErrorVariant::LengthExceedsPlatform(length) => {
+                write!(f, "Length {} exceeds the platform's maximum size", length)
+            }
             ASNErrorVariant::BadLengthEncoding(count, value) => {
                 write!(f, "Value {} encoded using {} bytes", value, count)
             }
+            ASNErrorVariant::NonMinimalLength => {
+                f.write_str("Length encoded with a leading zero byte")
+            }
             ASNErrorVariant::BadOidLength => f.write_str("Bad OID length"),
+            ASNErrorVariant::NonMinimalOid => {
+                f.write_str("OID sub-identifier encoded with a leading 0x80 byte")
+            }
+            ASNErrorVariant::BadOidArcs(oid) => write!(
+                f,
+                "OID {} must start with arc 0, 1, or 2 followed by an arc below 40 under 0 or 1, \
+                 or below 2^128 - 80 under 2",
+                oid.to_dotted_string()
+            ),
             ASNErrorVariant::BadUTF8(err) => write!(f, "Bad UTF8 encoding: {}", err),
+            ASNErrorVariant::BadBMPString => f.write_str("Bad BMPString encoding"),
+            ASNErrorVariant::NonAsciiIA5String(byte) => {
+                write!(f, "IA5String contains non-ASCII byte: 0x{:02X}", byte)
+            }
+            ASNErrorVariant::BadPrintableChar(byte) => {
+                write!(
+                    f,
+                    "PrintableString contains disallowed byte: 0x{:02X}",
+                    byte
+                )
+            }
+            ASNErrorVariant::BadNumericChar(byte) => {
+                write!(f, "NumericString contains disallowed byte: 0x{:02X}", byte)
+            }
+            ASNErrorVariant::BadVisibleChar(byte) => {
+                write!(f, "VisibleString contains disallowed byte: 0x{:02X}", byte)
+            }
             ASNErrorVariant::BadUTCTime => write!(f, "Bad UTC time string"),
+            ASNErrorVariant::YearOutOfRange(kind, year) => {
+                write!(f, "Year {} can't be encoded as {:?}", year, kind)
+            }
+            ASNErrorVariant::NonLatin1Char(c) => {
+                write!(f, "TeletexString can't encode U+{:04X}", u32::from(*c))
+            }
             ASNErrorVariant::BitStringUnusedBitsTooLarge(unused) => write!(
                 f,
                 "Bit string w/ unused bits outside range [0..7]: {}",
                 unused
             ),
+            ASNErrorVariant::EmptyBitStringWithUnusedBits(unused) => {
+                write!(f, "Empty bit string w/ non-zero unused bits: {}", unused)
+            }
+            ASNErrorVariant::EmptyInput => f.write_str("Input is empty"),
             ASNErrorVariant::EndOfStream => {
                 f.write_str("Consumed all input before parsing required fields")
             }
@@ -699,8 +1167,21 @@ impl core::fmt::Display for ASNErrorVariant {
             ASNErrorVariant::BadEnumValue(name, value) => {
                 write!(f, "The enum '{}' has not mapping for value {}", name, value)
             }
-            ASNErrorVariant::UnexpectedOid(oid) => {
-                write!(f, "The Object Identifier '{}' was unexpected.", oid)
+            ASNErrorVariant::NegativeValue(name, value) => {
+                write!(f, "The field '{}' may not be negative: {}", name, value)
+            }
+            ASNErrorVariant::DuplicateExtension(oid) => {
+                write!(f, "The extension '{}' appears more than once.", oid)
+            }
+            ASNErrorVariant::KeyUsageTooManyBits(count) => {
+                write!(f, "KeyUsage has {} bits but only 9 are defined", count)
+            }
+            ASNErrorVariant::UnalignedBitString(unused) => {
+                write!(
+                    f,
+                    "BIT STRING must hold whole octets but has {} unused bits",
+                    unused
+                )
             }
             ASNErrorVariant::UnexpectedTag(tag) => {
                 write!(f, "The explicit tag '{}' was unexpected.", tag)
@@ -708,3 +1189,186 @@ impl core::fmt::Display for ASNErrorVariant {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn exposes_the_utf8_error_as_the_source() {
+        // UTF8String "a" followed by an invalid byte
+        let input = [0x0C, 0x02, 0x61, 0xFF];
+        let err = parser::Parser::new(&input)
+            .expect::<UTF8String>()
+            .unwrap_err()
+            .relative_to(&input);
+
+        let source = err.source().unwrap();
+        let utf8 = source.downcast_ref::<core::str::Utf8Error>().unwrap();
+        assert_eq!(utf8.valid_up_to(), 1);
+        assert_eq!(
+            err.to_string(),
+            format!("Bad UTF8 encoding: {} at byte 0", utf8)
+        );
+        assert!(ASNError::from(ASNErrorVariant::EndOfStream)
+            .source()
+            .is_none());
+    }
+
+    #[test]
+    fn as_i32_sign_extends_negative_values() {
+        assert_eq!(ASNInteger::new(&[0x7F]).as_i32(), Some(127));
+        assert_eq!(ASNInteger::new(&[0x00, 0x80]).as_i32(), Some(128));
+        assert_eq!(ASNInteger::new(&[0xFF]).as_i32(), Some(-1));
+        assert_eq!(ASNInteger::new(&[0xFF, 0x7F]).as_i32(), Some(-129));
+    }
+
+    #[test]
+    fn as_i32_accepts_exactly_four_bytes() {
+        assert_eq!(
+            ASNInteger::new(&[0x7F, 0xFF, 0xFF, 0xFF]).as_i32(),
+            Some(i32::MAX)
+        );
+        assert_eq!(
+            ASNInteger::new(&[0xFE, 0xDC, 0xBA, 0x98]).as_i32(),
+            Some(-0x01234568)
+        );
+        assert_eq!(
+            ASNInteger::new(&[0x00, 0x80, 0x00, 0x00, 0x00]).as_i32(),
+            None
+        );
+        assert_eq!(ASNInteger::new(&[]).as_i32(), None);
+    }
+
+    #[test]
+    fn as_i64_decodes_values_wider_than_i32() {
+        assert_eq!(
+            ASNInteger::new(&[0x01, 0x00, 0x00, 0x00, 0x00]).as_i64(),
+            Some(0x01_0000_0000)
+        );
+        assert_eq!(
+            ASNInteger::new(&[0x80, 0x00, 0x00, 0x00, 0x00]).as_i64(),
+            Some(-0x80_0000_0000)
+        );
+        assert_eq!(ASNInteger::new(&[0x01; 9]).as_i64(), None);
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn as_bignum_follows_the_sign_bit() {
+        use num_bigint::BigInt;
+
+        assert_eq!(ASNInteger::new(&[0x80]).as_bignum(), BigInt::from(-128));
+        assert_eq!(
+            ASNInteger::new(&[0x00, 0x80]).as_bignum(),
+            BigInt::from(128)
+        );
+        assert_eq!(
+            ASNInteger::new(&[0xFF, 0x7F]).as_bignum(),
+            BigInt::from(-129)
+        );
+
+        // -2^135 doesn't fit in any primitive integer
+        let mut negative = [0x00; 17];
+        negative[0] = 0x80;
+        assert_eq!(
+            ASNInteger::new(&negative).as_bignum(),
+            -(BigInt::from(1) << 135usize)
+        );
+    }
+
+    #[test]
+    fn unsigned_accessors_allow_a_leading_sign_byte() {
+        let max = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        assert_eq!(ASNInteger::new(&max).as_u64(), Some(u64::MAX));
+        assert_eq!(ASNInteger::new(&max).as_i64(), None);
+        assert_eq!(ASNInteger::new(&[0x00, 0x80]).as_u64(), Some(0x80));
+        assert_eq!(ASNInteger::new(&[0x00]).as_u64(), Some(0));
+        assert_eq!(ASNInteger::new(&[0x80]).as_u64(), None);
+        assert_eq!(ASNInteger::new(&[0x01; 9]).as_u64(), None);
+
+        // a typical 16 byte serial number
+        let serial = [
+            0x00, 0xFD, 0x1C, 0xEB, 0x8E, 0x4A, 0x3B, 0x4F, 0xA1, 0x01, 0x02, 0x03, 0x04, 0x05,
+            0x06, 0x07, 0x08,
+        ];
+        assert_eq!(
+            ASNInteger::new(&serial).as_u128(),
+            Some(0xFD1CEB8E4A3B4FA1_0102030405060708)
+        );
+    }
+
+    #[test]
+    fn packs_small_bit_strings_into_u64() {
+        // keyCertSign (5) and cRLSign (6) from a CA KeyUsage of 03 02 01 06
+        let key_usage = ASNBitString::new(1, &[0x06]);
+        assert_eq!(
+            key_usage.as_u64_be(),
+            Some((1 << (63 - 5)) | (1 << (63 - 6)))
+        );
+        assert_eq!(ASNBitString::new(0, &[]).as_u64_be(), Some(0));
+        assert_eq!(ASNBitString::new(1, &[0xFF]).as_u64_be(), Some(0xFE << 56));
+        assert_eq!(ASNBitString::new(0, &[0; 8]).as_u64_be(), Some(0));
+        assert_eq!(ASNBitString::new(0, &[0; 9]).as_u64_be(), None);
+    }
+
+    #[test]
+    fn packs_small_bit_strings_into_u32() {
+        // digitalSignature (0) and decipherOnly (8) with 7 unused bits set by a sloppy encoder
+        let key_usage = ASNBitString::new(7, &[0x80, 0xFF]);
+        assert_eq!(key_usage.size(), 9);
+        assert_eq!(key_usage.as_u32_be(), Some((1 << 31) | (1 << (31 - 8))));
+
+        // two bits, only the second one set
+        assert_eq!(ASNBitString::new(6, &[0x40]).as_u32_be(), Some(1 << 30));
+
+        assert_eq!(ASNBitString::new(0, &[0xFF; 4]).as_u32_be(), Some(u32::MAX));
+        assert_eq!(ASNBitString::new(7, &[0; 5]).as_u32_be(), None);
+    }
+
+    #[test]
+    fn displays_utc_time_as_iso_8601() {
+        for (seconds, expected) in [
+            (0, "1970-01-01T00:00:00Z"),
+            (951782400, "2000-02-29T00:00:00Z"),
+            // one second past the largest signed 32-bit time
+            (2147483648, "2038-01-19T03:14:08Z"),
+            (4607280000, "2116-01-01T00:00:00Z"),
+            (253402300799, "9999-12-31T23:59:59Z"),
+            (u64::MAX, "584554051223-11-09T07:00:15Z"),
+        ] {
+            assert_eq!(
+                UtcTime::from_seconds_since_epoch(seconds).to_string(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn orders_object_identifiers_by_arc() {
+        let server_auth = oid!(1, 3, 6, 1, 5, 5, 7, 3, 1);
+        assert_eq!(
+            server_auth,
+            ASNObjectIdentifier::new(vec![1, 3, 6, 1, 5, 5, 7, 3, 1])
+        );
+        assert!(server_auth < oid!(1, 3, 6, 1, 5, 5, 7, 3, 2));
+        // arcs compare numerically, not by their encoding or dotted string
+        assert!(oid!(2, 5, 4, 9) < oid!(2, 5, 4, 10));
+        assert!(oid!(1, 2) < oid!(1, 2, 0));
+    }
+
+    #[test]
+    fn object_identifiers_can_key_a_btree_map() {
+        let mut names = std::collections::BTreeMap::new();
+        names.insert(oid!(2, 5, 4, 10), "O");
+        names.insert(oid!(2, 5, 4, 3), "CN");
+        names.insert(oid!(2, 5, 4, 6), "C");
+
+        assert_eq!(names.get(&oid!(2, 5, 4, 3)), Some(&"CN"));
+        assert_eq!(
+            names.values().copied().collect::<Vec<_>>(),
+            ["CN", "C", "O"]
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use crate::der::encode::{encode, write_length};
+use crate::der::types::*;
+
+/// Appends DER encoded values to a buffer
+///
+/// A thin layer over [`encode`] for building structures in place.
+/// Constructed values are written with [`Writer::write_sequence`], which fills in the length
+/// once the contents are known, so nothing has to be assembled up front.
+pub struct Writer<'a> {
+    out: &'a mut Vec<u8>,
+}
+
+impl<'a> Writer<'a> {
+    pub fn new(out: &'a mut Vec<u8>) -> Self {
+        Self { out }
+    }
+
+    /// Append any value, fails without writing anything if it can't be encoded
+    pub fn write(&mut self, value: &ASNType) -> Result<(), ASNError> {
+        encode(value, self.out)
+    }
+
+    /// Append an INTEGER from the big-endian two's complement bytes of its contents
+    pub fn write_integer(&mut self, bytes: &[u8]) -> Result<(), ASNError> {
+        if bytes.is_empty() {
+            return Err(ASNErrorVariant::ZeroLengthInteger.into());
+        }
+        self.write(&Integer::asn(ASNInteger::new(bytes)))
+    }
+
+    /// Append an OBJECT IDENTIFIER, fails if its arcs can't be encoded
+    pub fn write_oid(&mut self, oid: &ASNObjectIdentifier) -> Result<(), ASNError> {
+        self.write(&ObjectIdentifier::asn(oid.clone()))
+    }
+
+    pub fn write_octet_string(&mut self, bytes: &[u8]) {
+        self.write_infallible(&OctetString::asn(bytes));
+    }
+
+    pub fn write_bit_string(&mut self, unused_bits: u8, bytes: &[u8]) {
+        self.write_infallible(&BitString::asn(ASNBitString::new(unused_bits, bytes)));
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_infallible(&Boolean::asn(value));
+    }
+
+    pub fn write_null(&mut self) {
+        self.write_infallible(&ASNType::Null);
+    }
+
+    /// Append a SEQUENCE whose contents are written by `f`
+    ///
+    /// The length is written in its minimal form after `f` returns. If `f` fails, everything
+    /// it wrote is removed.
+    pub fn write_sequence<F>(&mut self, f: F) -> Result<(), ASNError>
+    where
+        F: FnOnce(&mut Writer) -> Result<(), ASNError>,
+    {
+        let start = self.out.len();
+        self.out.push(0x30);
+        let contents = self.out.len();
+        if let Err(err) = f(&mut Writer::new(self.out)) {
+            self.out.truncate(start);
+            return Err(err);
+        }
+
+        let mut length = Vec::new();
+        write_length(self.out.len() - contents, &mut length);
+        self.out.splice(contents..contents, length);
+        Ok(())
+    }
+
+    // only for the types whose every value has an encoding
+    fn write_infallible(&mut self, value: &ASNType) {
+        let _ = self.write(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::der::parser::Parser;
+    use crate::x509::{Certificate, SubjectPublicKeyInfo};
+
+    fn write_spki(spki: &SubjectPublicKeyInfo, out: &mut Vec<u8>) -> Result<(), ASNError> {
+        Writer::new(out).write_sequence(|w| {
+            w.write_sequence(|w| {
+                w.write_oid(&spki.algorithm.algorithm)?;
+                match &spki.algorithm.parameters {
+                    Some(parameters) => w.write(parameters),
+                    None => Ok(()),
+                }
+            })?;
+            let key = &spki.subject_public_key;
+            w.write_bit_string(key.unused_bits, key.bytes);
+            Ok(())
+        })
+    }
+
+    // the original encoding of the SubjectPublicKeyInfo, which follows the optional version and
+    // five other fields of the TBSCertificate
+    fn encoded_spki(cert: &[u8]) -> &[u8] {
+        let mut parser = Parser::new(cert);
+        let mut cert = parser.expect_sequence_contents().unwrap();
+        let mut tbs = cert.expect_sequence_contents().unwrap();
+        tbs.get_optional_context_field(0).unwrap();
+        for _ in 0..5 {
+            tbs.skip().unwrap();
+        }
+        tbs.expect_with_der::<Sequence>().unwrap().1
+    }
+
+    #[test]
+    fn reencodes_subject_public_key_infos_byte_for_byte() {
+        for der in [
+            &include_bytes!("../../../certs/512b-rsa-example-cert.der")[..],
+            include_bytes!("../../../certs/ed25519-example-cert.der"),
+            include_bytes!("../../../certs/ec_p256_cert.der"),
+        ] {
+            let cert = Certificate::parse(der).unwrap();
+            let mut out = Vec::new();
+            write_spki(
+                &cert.tbs_certificate.value.subject_public_key_info,
+                &mut out,
+            )
+            .unwrap();
+            assert_eq!(out, encoded_spki(der));
+        }
+    }
+
+    #[test]
+    fn back_patches_long_sequence_lengths() {
+        let mut out = Vec::new();
+        let mut writer = Writer::new(&mut out);
+        writer
+            .write_sequence(|w| {
+                w.write_integer(&[0x05])?;
+                w.write_octet_string(&[0xAA; 200]);
+                w.write_bool(true);
+                Ok(())
+            })
+            .unwrap();
+        writer.write_null();
+
+        assert_eq!(&out[..3], [0x30, 0x81, 3 + 203 + 3]);
+        assert_eq!(&out[3..6], [0x02, 0x01, 0x05]);
+        assert_eq!(&out[6..9], [0x04, 0x81, 200]);
+        assert_eq!(&out[out.len() - 5..], [0x01, 0x01, 0xFF, 0x05, 0x00]);
+    }
+
+    #[test]
+    fn removes_a_sequence_whose_contents_fail() {
+        let mut out = vec![0x05, 0x00];
+        let err = Writer::new(&mut out)
+            .write_sequence(|w| {
+                w.write_bool(false);
+                w.write_oid(&ASNObjectIdentifier::new(vec![7, 1]))
+            })
+            .unwrap_err();
+        assert_eq!(
+            err.variant,
+            ASNErrorVariant::BadOidArcs(ASNObjectIdentifier::new(vec![7, 1]))
+        );
+        assert_eq!(out, [0x05, 0x00]);
+
+        assert_eq!(
+            Writer::new(&mut out)
+                .write_integer(&[])
+                .unwrap_err()
+                .variant,
+            ASNErrorVariant::ZeroLengthInteger
+        );
+    }
+}
@@ -1,4 +1,6 @@
 /// ASN.1 DER types and routines
 pub mod der;
+/// PEM (RFC 7468) decoding
+pub mod pem;
 /// x.509 model and parser
 pub mod x509;
@@ -0,0 +1,215 @@
+use crate::der::ASNError;
+
+/// Errors that can occur while decoding PEM
+#[derive(Debug, PartialEq, Eq)]
+pub enum PemError {
+    /// a BEGIN line without a matching END line
+    UnterminatedBlock(String),
+    /// the label of the END line differs from the label of the BEGIN line
+    LabelMismatch(String, String), // BEGIN label followed by the END label
+    /// the body of a block is not valid base64
+    BadBase64,
+    /// the input has no block with the expected label
+    MissingBlock(&'static str),
+    /// the decoded contents failed to parse
+    Der(ASNError),
+}
+
+impl core::fmt::Display for PemError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            PemError::UnterminatedBlock(label) => write!(f, "no END line for '{}'", label),
+            PemError::LabelMismatch(begin, end) => {
+                write!(
+                    f,
+                    "BEGIN label '{}' doesn't match END label '{}'",
+                    begin, end
+                )
+            }
+            PemError::BadBase64 => f.write_str("bad base64 encoding"),
+            PemError::MissingBlock(label) => write!(f, "no '{}' block", label),
+            PemError::Der(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for PemError {}
+
+impl From<ASNError> for PemError {
+    fn from(err: ASNError) -> Self {
+        PemError::Der(err)
+    }
+}
+
+/// Decode every `-----BEGIN X-----` / `-----END X-----` block of the input, in order
+///
+/// Returns the label (e.g. "CERTIFICATE") and decoded contents of each block. Text outside of
+/// the blocks is ignored, as are line endings and other whitespace within the base64 body.
+pub fn decode(input: &str) -> Result<Vec<(String, Vec<u8>)>, PemError> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(&str, String)> = None;
+
+    for line in input.lines().map(str::trim) {
+        match current.take() {
+            None => current = boundary(line, "BEGIN").map(|label| (label, String::new())),
+            Some((label, mut body)) => match boundary(line, "END") {
+                Some(end) if end == label => blocks.push((label.to_string(), base64(&body)?)),
+                Some(end) => {
+                    return Err(PemError::LabelMismatch(label.to_string(), end.to_string()))
+                }
+                None => {
+                    body.push_str(line);
+                    current = Some((label, body));
+                }
+            },
+        }
+    }
+
+    match current {
+        Some((label, _)) => Err(PemError::UnterminatedBlock(label.to_string())),
+        None => Ok(blocks),
+    }
+}
+
+// the label of a "-----BEGIN label-----" or "-----END label-----" line
+fn boundary<'a>(line: &'a str, kind: &str) -> Option<&'a str> {
+    line.strip_prefix("-----")?
+        .strip_prefix(kind)?
+        .strip_prefix(' ')?
+        .strip_suffix("-----")
+}
+
+/// Standard base64 with padding and without line breaks, the inverse of the PEM body decoding
+pub fn to_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |acc, (i, byte)| {
+            acc | u32::from(*byte) << (16 - 8 * i)
+        });
+        // n bytes fill n + 1 symbols, the rest of the quantum is padding
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(group >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn base64(body: &str) -> Result<Vec<u8>, PemError> {
+    let mut out = Vec::with_capacity(body.len() / 4 * 3);
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    let mut symbols = 0;
+    let mut padding = 0;
+
+    for c in body.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        if c == b'=' {
+            padding += 1;
+            continue;
+        }
+        // padding may only appear at the end
+        if padding != 0 {
+            return Err(PemError::BadBase64);
+        }
+
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err(PemError::BadBase64),
+        };
+        symbols += 1;
+        acc = (acc << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+
+    // the final quantum is padded to 4 symbols and any unused bits must be zero
+    let expected_padding = (4 - symbols % 4) % 4;
+    if symbols % 4 == 1 || padding != expected_padding || acc != 0 {
+        return Err(PemError::BadBase64);
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSA_PEM: &str = include_str!("../../certs/512b-rsa-example-cert.pem");
+    const RSA_DER: &[u8] = include_bytes!("../../certs/512b-rsa-example-cert.der");
+
+    #[test]
+    fn decodes_certificate() {
+        assert_eq!(
+            decode(RSA_PEM),
+            Ok(vec![("CERTIFICATE".to_string(), RSA_DER.to_vec())])
+        );
+    }
+
+    #[test]
+    fn decodes_concatenated_blocks_with_any_line_ending() {
+        let crlf = RSA_PEM.replace('\n', "\r\n");
+        // the last block has no trailing newline
+        let input = format!(
+            "first\n{}{}",
+            crlf,
+            RSA_PEM.replace("CERTIFICATE", "X509 CRL").trim_end()
+        );
+
+        let blocks = decode(&input).unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0], ("CERTIFICATE".to_string(), RSA_DER.to_vec()));
+        assert_eq!(blocks[1], ("X509 CRL".to_string(), RSA_DER.to_vec()));
+    }
+
+    #[test]
+    fn rejects_mismatched_and_unterminated_blocks() {
+        assert_eq!(
+            decode("-----BEGIN CERTIFICATE-----\nAAAA\n-----END X509 CRL-----\n"),
+            Err(PemError::LabelMismatch(
+                "CERTIFICATE".to_string(),
+                "X509 CRL".to_string()
+            ))
+        );
+        assert_eq!(
+            decode("-----BEGIN CERTIFICATE-----\nAAAA\n"),
+            Err(PemError::UnterminatedBlock("CERTIFICATE".to_string()))
+        );
+    }
+
+    #[test]
+    fn decodes_base64_padding() {
+        assert_eq!(base64("TWFu"), Ok(b"Man".to_vec()));
+        assert_eq!(base64("TWE="), Ok(b"Ma".to_vec()));
+        assert_eq!(base64("TQ=\n="), Ok(b"M".to_vec()));
+        assert_eq!(base64(""), Ok(Vec::new()));
+
+        assert_eq!(base64("TWE"), Err(PemError::BadBase64));
+        assert_eq!(base64("TQ==TQ=="), Err(PemError::BadBase64));
+        assert_eq!(base64("TR=="), Err(PemError::BadBase64));
+        assert_eq!(base64("T==="), Err(PemError::BadBase64));
+        assert_eq!(base64("TW!u"), Err(PemError::BadBase64));
+    }
+
+    #[test]
+    fn encodes_base64_with_padding() {
+        assert_eq!(to_base64(b"Man"), "TWFu");
+        assert_eq!(to_base64(b"Ma"), "TWE=");
+        assert_eq!(to_base64(b"M"), "TQ==");
+        assert_eq!(to_base64(b""), "");
+        assert_eq!(base64(&to_base64(RSA_DER)), Ok(RSA_DER.to_vec()));
+    }
+}
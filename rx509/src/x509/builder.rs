@@ -0,0 +1,230 @@
+use crate::der::encode::{encode, SequenceBuilder};
+use crate::der::*;
+use crate::x509::verify::ED25519;
+use crate::x509::{Ava, AvaValue};
+
+use ed25519_dalek::{Signer, SigningKey};
+
+// RFC 5280 requires UTCTime for dates through 2049 and GeneralizedTime from 2050 on
+const YEAR_2050: u64 = 2524608000;
+
+// serial number used when none is provided
+const DEFAULT_SERIAL_NUMBER: &[u8] = &[0x01];
+
+/// Builds a self-signed X.509 v3 certificate signed with Ed25519
+#[derive(Debug)]
+pub struct CertificateBuilder<'a> {
+    serial_number: &'a [u8],
+    subject: Vec<Ava<'a>>,
+    not_before: UtcTime,
+    not_after: UtcTime,
+    extensions: Vec<(ASNObjectIdentifier<'a>, bool, &'a [u8])>,
+}
+
+impl<'a> CertificateBuilder<'a> {
+    /// Each AVA of the subject is placed in its own RDN, in order
+    pub fn new(subject: Vec<Ava<'a>>, not_before: UtcTime, not_after: UtcTime) -> Self {
+        Self {
+            serial_number: DEFAULT_SERIAL_NUMBER,
+            subject,
+            not_before,
+            not_after,
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Set the content bytes of the serialNumber INTEGER, defaults to 1
+    pub fn serial_number(&mut self, serial_number: &'a [u8]) -> &mut Self {
+        self.serial_number = serial_number;
+        self
+    }
+
+    /// Add an extension with the DER encoded extnValue
+    pub fn extension(
+        &mut self,
+        extn_id: ASNObjectIdentifier<'a>,
+        critical: bool,
+        value: &'a [u8],
+    ) -> &mut Self {
+        self.extensions.push((extn_id, critical, value));
+        self
+    }
+
+    /// Produce the DER encoding of the certificate, self-signed by `key`
+    ///
+    /// Fails if a value can't be encoded, e.g. a validity time after the year 9999.
+    pub fn build_self_signed(&self, key: &SigningKey) -> Result<Vec<u8>, ASNError> {
+        let tbs = self.tbs_certificate(key)?;
+        let signature = key.sign(&tbs.build());
+
+        let mut certificate = SequenceBuilder::new();
+        certificate
+            .add_sequence(&tbs)
+            .add_sequence(&ed25519_algorithm()?)
+            .add(&BitString::asn(ASNBitString::new(0, &signature.to_bytes())))?;
+        Ok(certificate.build())
+    }
+
+    fn tbs_certificate(&self, key: &SigningKey) -> Result<SequenceBuilder, ASNError> {
+        // v3
+        let mut version = Vec::new();
+        encode(&Integer::asn(ASNInteger::new(&[0x02])), &mut version)?;
+
+        let name = self.name()?;
+
+        let mut validity = SequenceBuilder::new();
+        validity
+            .add(&time(self.not_before))?
+            .add(&time(self.not_after))?;
+
+        let public_key = key.verifying_key().to_bytes();
+        let mut spki = SequenceBuilder::new();
+        spki.add_sequence(&ed25519_algorithm()?)
+            .add(&BitString::asn(ASNBitString::new(0, &public_key)))?;
+
+        let mut tbs = SequenceBuilder::new();
+        tbs.add(&ExplicitTag::asn(ASNExplicitTag::new(0, &version)))?
+            .add(&Integer::asn(ASNInteger::new(self.serial_number)))?
+            .add_sequence(&ed25519_algorithm()?)
+            .add_sequence(&name)
+            .add_sequence(&validity)
+            .add_sequence(&name)
+            .add_sequence(&spki);
+
+        if !self.extensions.is_empty() {
+            let mut extensions = SequenceBuilder::new();
+            for (extn_id, critical, value) in &self.extensions {
+                let mut extension = SequenceBuilder::new();
+                extension.add(&ObjectIdentifier::asn(extn_id.clone()))?;
+                // DER omits a BOOLEAN equal to its DEFAULT of FALSE
+                if *critical {
+                    extension.add(&Boolean::asn(true))?;
+                }
+                extension.add(&OctetString::asn(value))?;
+                extensions.add_sequence(&extension);
+            }
+            let extensions = extensions.build();
+            tbs.add(&ExplicitTag::asn(ASNExplicitTag::new(3, &extensions)))?;
+        }
+
+        Ok(tbs)
+    }
+
+    fn name(&self) -> Result<SequenceBuilder, ASNError> {
+        let mut name = SequenceBuilder::new();
+        for ava in &self.subject {
+            let mut attribute = SequenceBuilder::new();
+            attribute.add(&ObjectIdentifier::asn(ava.attr_type.clone()))?;
+            match &ava.value {
+                // countryName is restricted to PrintableString
+                AvaValue::String(value) if ava.attr_type.values() == [2, 5, 4, 6] => {
+                    attribute.add(&PrintableString::asn(value))?;
+                }
+                AvaValue::String(value) => {
+                    attribute.add(&UTF8String::asn(value))?;
+                }
+                AvaValue::Der(der) => {
+                    attribute.add_der(der);
+                }
+            }
+            let attribute = attribute.build();
+            name.add(&Set::asn(&attribute))?;
+        }
+        Ok(name)
+    }
+}
+
+fn ed25519_algorithm() -> Result<SequenceBuilder, ASNError> {
+    let mut algorithm = SequenceBuilder::new();
+    algorithm.add(&ObjectIdentifier::asn(ASNObjectIdentifier::new(
+        ED25519.to_vec(),
+    )))?;
+    Ok(algorithm)
+}
+
+fn time(value: UtcTime) -> ASNType<'static> {
+    if value.value < YEAR_2050 {
+        ASNType::UTCTime(value)
+    } else {
+        GeneralizedTime::asn(value.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::x509::ext::SpecificExtension;
+    use crate::x509::Certificate;
+
+    fn common_name(value: &str) -> Ava<'_> {
+        Ava::new(ASNObjectIdentifier::new(vec![2, 5, 4, 3]), value)
+    }
+
+    #[test]
+    fn builds_self_signed_certificate_that_verifies() {
+        let key = SigningKey::from_bytes(&[0x2A; 32]);
+        // SEQUENCE { BOOLEAN TRUE }
+        let basic_constraints = [0x30, 0x03, 0x01, 0x01, 0xFF];
+
+        let der = CertificateBuilder::new(
+            vec![
+                Ava::new(ASNObjectIdentifier::new(vec![2, 5, 4, 6]), "US"),
+                common_name("device-01"),
+            ],
+            UtcTime::from_seconds_since_epoch(1767225600),
+            UtcTime::from_seconds_since_epoch(4102444800),
+        )
+        .serial_number(&[0x00, 0x80])
+        .extension(
+            ASNObjectIdentifier::new(vec![2, 5, 29, 19]),
+            true,
+            &basic_constraints,
+        )
+        .build_self_signed(&key)
+        .unwrap();
+
+        let cert = Certificate::parse(&der).unwrap();
+        let tbs = &cert.tbs_certificate.value;
+        assert_eq!(tbs.serial_number.bytes, [0x00, 0x80]);
+        assert_eq!(
+            tbs.subject.parse().unwrap().common_name.as_deref(),
+            Some("device-01")
+        );
+        assert_eq!(tbs.issuer.der(), tbs.subject.der());
+        assert_eq!(tbs.validity.not_before_kind, ASNTypeId::UTCTime);
+        assert_eq!(tbs.validity.not_after_kind, ASNTypeId::GeneralizedTime);
+        assert_eq!(
+            tbs.validity.not_after,
+            UtcTime::from_seconds_since_epoch(4102444800)
+        );
+
+        let extensions = tbs.extensions.as_ref().unwrap().parse().unwrap();
+        assert_eq!(extensions.len(), 1);
+        assert!(extensions[0].critical);
+        assert!(matches!(
+            &extensions[0].content,
+            SpecificExtension::BasicConstraints(x) if x.ca
+        ));
+
+        assert_eq!(cert.verify_signature(&tbs.subject_public_key_info), Ok(()));
+    }
+
+    #[test]
+    fn omits_extensions_when_there_are_none() {
+        let key = SigningKey::from_bytes(&[0x01; 32]);
+        let der = CertificateBuilder::new(
+            vec![common_name("test")],
+            UtcTime::from_seconds_since_epoch(0),
+            UtcTime::from_seconds_since_epoch(1),
+        )
+        .build_self_signed(&key)
+        .unwrap();
+
+        let cert = Certificate::parse(&der).unwrap();
+        assert!(cert.tbs_certificate.value.extensions.is_none());
+        assert_eq!(
+            cert.verify_signature(&cert.tbs_certificate.value.subject_public_key_info),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use crate::der::parser::Parser;
+use crate::der::*;
+use crate::x509::ext::{Extensions, GeneralName, SpecificExtension};
+use crate::x509::{
+    parse_optional_time, parse_time, AlgorithmIdentifier, Constructed, Name, Version,
+};
+
+#[derive(Debug)]
+pub struct CertificateList<'a> {
+    // preserve raw bytes for signature validation using Constructed<T>
+    pub tbs_cert_list: Constructed<'a, TBSCertList<'a>>,
+    pub signature_algorithm: AlgorithmIdentifier<'a>,
+    pub signature_value: ASNBitString<'a>,
+}
+
+impl<'a> CertificateList<'a> {
+    pub fn parse(input: &[u8]) -> Result<CertificateList<'_>, ASNError> {
+        CertificateList::parse_with_options(input, ParseOptions::default_single_value())
+    }
+
+    /// Parse a CRL, applying the checks selected by `options` to every element read while
+    /// parsing it
+    ///
+    /// The revoked certificates and the extensions are parsed on demand with the default checks.
+    pub fn parse_with_options(
+        input: &[u8],
+        options: ParseOptions,
+    ) -> Result<CertificateList<'_>, ASNError> {
+        CertificateList::parse_der(input, options).map_err(|err| err.relative_to(input))
+    }
+
+    fn parse_der(input: &[u8], options: ParseOptions) -> Result<CertificateList<'_>, ASNError> {
+        let mut parser = Parser::non_empty_with_options(input, options)?;
+        let contents = parser.expect::<Sequence>()?;
+        let ret = parser.parse_nested(contents, |p2| {
+            let (tbs, tbs_der) = p2.expect_with_der::<Sequence>()?;
+            Ok(CertificateList {
+                tbs_cert_list: TBSCertList::parse(p2, tbs, tbs_der)?,
+                signature_algorithm: AlgorithmIdentifier::parse_from(p2)?,
+                signature_value: p2.expect::<BitString>()?,
+            })
+        })?;
+        if options.reject_trailing_data {
+            parser.expect_end()?;
+        }
+        Ok(ret)
+    }
+}
+
+#[derive(Debug)]
+pub struct TBSCertList<'a> {
+    pub version: Version,
+    pub signature: AlgorithmIdentifier<'a>,
+    pub issuer: Name<'a>,
+    pub this_update: UtcTime,
+    pub next_update: Option<UtcTime>,
+    pub revoked_certificates: Option<RevokedCertificates<'a>>,
+    pub crl_extensions: Option<Extensions<'a>>,
+}
+
+impl<'a> TBSCertList<'a> {
+    fn parse(
+        outer: &Parser<'a>,
+        input: &'a [u8],
+        der: &'a [u8],
+    ) -> Result<Constructed<'a, Self>, ASNError> {
+        fn parse_version(parser: &mut Parser) -> Result<Version, ASNError> {
+            match parser.peek_id().transpose()? {
+                Some(ASNTypeId::Integer) => parser.expect_enum("version", |x| match x {
+                    1 => Some(Version::V2),
+                    _ => None,
+                }),
+                _ => Ok(Version::V1),
+            }
+        }
+
+        fn parse_tbs_cert_list<'a>(parser: &mut Parser<'a>) -> Result<TBSCertList<'a>, ASNError> {
+            Ok(TBSCertList {
+                version: parse_version(parser)?,
+                signature: AlgorithmIdentifier::parse_from(parser)?,
+                issuer: Name::parse_from(parser)?,
+                this_update: parse_time(parser)?,
+                next_update: parse_optional_time(parser)?,
+                revoked_certificates: parser
+                    .get_optional::<Sequence>()?
+                    .map(RevokedCertificates::new),
+                crl_extensions: parser.get_optional_context_field(0)?.map(Extensions::new),
+            })
+        }
+
+        Ok(Constructed::new(
+            der,
+            outer.parse_nested(input, parse_tbs_cert_list)?,
+        ))
+    }
+}
+
+/// The revokedCertificates list of a CRL, parsed on demand
+#[derive(Debug)]
+pub struct RevokedCertificates<'a> {
+    raw_content: &'a [u8],
+}
+
+impl<'a> RevokedCertificates<'a> {
+    fn new(raw_content: &'a [u8]) -> Self {
+        Self { raw_content }
+    }
+
+    /// Parse every entry of the list, resolving the issuer of each revoked certificate.
+    ///
+    /// Per RFC 5280 section 5.3.3, the certificateIssuer entry extension of an indirect CRL
+    /// applies to its own entry and all of the entries that follow it, up to the next entry
+    /// carrying the extension.
+    pub fn parse(&self) -> Result<Vec<RevokedCertificate<'a>>, ASNError> {
+        let mut entries: Vec<RevokedCertificate> = Vec::new();
+        let mut certificate_issuer: Option<Vec<GeneralName<'a>>> = None;
+        let mut parser = Parser::new(self.raw_content);
+
+        while let Some(seq) = parser.expect_or_end::<Sequence>()? {
+            let entry = RevokedCertificate::parse(seq, certificate_issuer.take())?;
+            certificate_issuer.clone_from(&entry.certificate_issuer);
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+}
+
+#[derive(Debug)]
+pub struct RevokedCertificate<'a> {
+    pub user_certificate: ASNInteger<'a>,
+    pub revocation_date: UtcTime,
+    pub crl_entry_extensions: Option<Extensions<'a>>,
+    /// Issuer of the revoked certificate, carried forward from the last certificateIssuer entry
+    /// extension. `None` means the certificate was issued by the CRL issuer.
+    pub certificate_issuer: Option<Vec<GeneralName<'a>>>,
+}
+
+impl<'a> RevokedCertificate<'a> {
+    fn parse(
+        input: &'a [u8],
+        previous_issuer: Option<Vec<GeneralName<'a>>>,
+    ) -> Result<RevokedCertificate<'a>, ASNError> {
+        let mut entry = Parser::parse_all(input, |parser| {
+            let user_certificate = parser.expect::<Integer>()?;
+            let revocation_date = parse_time(parser)?;
+            // unlike certificates, the entry extensions are not wrapped in an explicit tag
+            let crl_entry_extensions = parser
+                .get_optional_with_der::<Sequence>()?
+                .map(|(_, der)| Extensions::new(der));
+            Ok(RevokedCertificate {
+                user_certificate,
+                revocation_date,
+                crl_entry_extensions,
+                certificate_issuer: previous_issuer,
+            })
+        })?;
+
+        if let Some(extensions) = &entry.crl_entry_extensions {
+            for extension in extensions.parse()? {
+                if let SpecificExtension::CertificateIssuer(issuer) = extension.content {
+                    entry.certificate_issuer = Some(issuer.names);
+                }
+            }
+        }
+
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dns_names(names: &Option<Vec<GeneralName>>) -> Option<Vec<String>> {
+        names.as_ref().map(|names| {
+            names
+                .iter()
+                .map(|name| match name {
+                    GeneralName::DnsName(x) => x.to_string(),
+                    other => panic!("unexpected name: {:?}", other),
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn carries_certificate_issuer_forward_in_indirect_crl() {
+        let crl =
+            CertificateList::parse(include_bytes!("../../../certs/indirect_crl.der")).unwrap();
+        let tbs = &crl.tbs_cert_list.value;
+        assert_eq!(tbs.version, Version::V2);
+        assert!(tbs.next_update.is_some());
+        assert!(tbs.crl_extensions.is_some());
+
+        let entries = tbs.revoked_certificates.as_ref().unwrap().parse().unwrap();
+        let serials: Vec<i32> = entries
+            .iter()
+            .map(|entry| entry.user_certificate.as_i32().unwrap())
+            .collect();
+        assert_eq!(serials, [1, 2, 3]);
+
+        // the first entry precedes any certificateIssuer extension, so it's issued by the CRL issuer
+        assert_eq!(dns_names(&entries[0].certificate_issuer), None);
+        // the second entry sets the certificate issuer
+        let other_ca = Some(vec!["other-ca.example.com".to_string()]);
+        assert_eq!(dns_names(&entries[1].certificate_issuer), other_ca);
+        // the third entry has extensions, but no certificateIssuer, so it inherits the previous one
+        assert_eq!(dns_names(&entries[2].certificate_issuer), other_ca);
+    }
+
+    #[test]
+    fn rejects_trailing_data_if_the_options_select_it() {
+        let mut der = include_bytes!("../../../certs/indirect_crl.der").to_vec();
+        der.push(0x00);
+
+        assert!(CertificateList::parse(&der).is_err());
+        assert!(CertificateList::parse_with_options(&der, ParseOptions::strict()).is_err());
+        assert!(CertificateList::parse_with_options(&der, ParseOptions::lenient()).is_ok());
+    }
+}
@@ -0,0 +1,163 @@
+use crate::der::parser::Parser;
+use crate::der::*;
+#[cfg(feature = "display")]
+use crate::x509::printer::{print_type, LinePrinter, Printable};
+use crate::x509::{AlgorithmIdentifier, Constructed, Name, SubjectPublicKeyInfo};
+
+/// PKCS#10 certification request (RFC 2986)
+#[derive(Debug)]
+pub struct CertificationRequest<'a> {
+    // preserve raw bytes for signature validation using Constructed<T>
+    pub certification_request_info: Constructed<'a, CertificationRequestInfo<'a>>,
+    pub signature_algorithm: AlgorithmIdentifier<'a>,
+    pub signature: ASNBitString<'a>,
+}
+
+impl<'a> CertificationRequest<'a> {
+    pub fn parse(input: &[u8]) -> Result<CertificationRequest<'_>, ASNError> {
+        CertificationRequest::parse_with_options(input, ParseOptions::default_single_value())
+    }
+
+    /// Parse a request, applying the checks selected by `options` to every element read while
+    /// parsing it
+    ///
+    /// The RDNs of the subject are parsed on demand, see [`Name::parse_with_options`].
+    pub fn parse_with_options(
+        input: &[u8],
+        options: ParseOptions,
+    ) -> Result<CertificationRequest<'_>, ASNError> {
+        CertificationRequest::parse_der(input, options).map_err(|err| err.relative_to(input))
+    }
+
+    fn parse_der(
+        input: &[u8],
+        options: ParseOptions,
+    ) -> Result<CertificationRequest<'_>, ASNError> {
+        let mut parser = Parser::non_empty_with_options(input, options)?;
+        let contents = parser.expect::<Sequence>()?;
+        let ret = parser.parse_nested(contents, |p2| {
+            let (info, info_der) = p2.expect_with_der::<Sequence>()?;
+            Ok(CertificationRequest {
+                certification_request_info: CertificationRequestInfo::parse(p2, info, info_der)?,
+                signature_algorithm: AlgorithmIdentifier::parse_from(p2)?,
+                signature: p2.expect::<BitString>()?,
+            })
+        })?;
+        if options.reject_trailing_data {
+            parser.expect_end()?;
+        }
+        Ok(ret)
+    }
+}
+
+#[derive(Debug)]
+pub struct CertificationRequestInfo<'a> {
+    pub subject: Name<'a>,
+    pub subject_public_key_info: SubjectPublicKeyInfo<'a>,
+    /// contents of the `[0] IMPLICIT SET OF Attribute`, which may be empty
+    pub attributes: &'a [u8],
+}
+
+impl<'a> CertificationRequestInfo<'a> {
+    fn parse(
+        outer: &Parser<'a>,
+        input: &'a [u8],
+        der: &'a [u8],
+    ) -> Result<Constructed<'a, Self>, ASNError> {
+        fn parse_version(parser: &mut Parser) -> Result<(), ASNError> {
+            // v1 is the only version
+            parser.expect_enum("version", |x| (x == 0).then_some(()))
+        }
+
+        fn parse_attributes<'a>(parser: &mut Parser<'a>) -> Result<&'a [u8], ASNError> {
+            let tag = parser.expect::<ExplicitTag>()?;
+            if tag.value != 0 {
+                return Err(ASNErrorVariant::UnexpectedTag(tag.value).into());
+            }
+            Ok(tag.contents)
+        }
+
+        fn parse_info<'a>(
+            parser: &mut Parser<'a>,
+        ) -> Result<CertificationRequestInfo<'a>, ASNError> {
+            parse_version(parser)?;
+            Ok(CertificationRequestInfo {
+                subject: Name::parse_from(parser)?,
+                subject_public_key_info: SubjectPublicKeyInfo::parse_from(parser)?,
+                attributes: parse_attributes(parser)?,
+            })
+        }
+
+        Ok(Constructed::new(
+            der,
+            outer.parse_nested(input, parse_info)?,
+        ))
+    }
+}
+
+#[cfg(feature = "display")]
+impl<'a> Printable for CertificationRequest<'a> {
+    fn print(&self, printer: &mut dyn LinePrinter) {
+        let info = &self.certification_request_info.value;
+
+        if let Ok(result) = info.subject.parse() {
+            print_type("subject", &result, printer);
+        } else {
+            print_type("subject (raw)", &info.subject, printer);
+        }
+
+        print_type(
+            "subject public key algorithm",
+            &info.subject_public_key_info.algorithm,
+            printer,
+        );
+        print_type("signature algorithm", &self.signature_algorithm, printer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::x509::KeyAlgorithm;
+
+    #[test]
+    fn parses_rsa_certification_request() {
+        let der = include_bytes!("../../../certs/rsa_csr.der");
+        let csr = CertificationRequest::parse(der).unwrap();
+        let info = &csr.certification_request_info.value;
+
+        let subject = info.subject.parse().unwrap();
+        assert_eq!(subject.country_name.as_deref(), Some("US"));
+        assert_eq!(subject.organization.as_deref(), Some("Example Corp"));
+        assert_eq!(subject.common_name.as_deref(), Some("csr.example.com"));
+
+        assert_eq!(
+            info.subject_public_key_info.key_algorithm(),
+            Some(KeyAlgorithm::Rsa)
+        );
+        // a single extensionRequest attribute
+        assert_eq!(info.attributes.len(), 45);
+        assert_eq!(
+            csr.signature_algorithm.algorithm.values(),
+            [1, 2, 840, 113549, 1, 1, 11]
+        );
+        assert_eq!(csr.signature.octets().map(|x| x.len()), Some(256));
+        assert_eq!(csr.certification_request_info.bytes, &der[4..416]);
+    }
+
+    #[test]
+    fn rejects_certificates() {
+        let der = include_bytes!("../../../certs/ed25519-example-cert.der");
+        assert!(CertificationRequest::parse(der).is_err());
+    }
+
+    #[test]
+    fn rejects_trailing_data_if_the_options_select_it() {
+        let mut der = include_bytes!("../../../certs/rsa_csr.der").to_vec();
+        der.push(0x00);
+
+        assert!(CertificationRequest::parse(&der).is_err());
+        assert!(CertificationRequest::parse_with_options(&der, ParseOptions::strict()).is_err());
+        assert!(CertificationRequest::parse_with_options(&der, ParseOptions::lenient()).is_ok());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::der::encode::SequenceBuilder;
+use crate::der::*;
+use crate::x509::{
+    AlgorithmIdentifier, Certificate, SubjectPublicKeyInfo, TBSCertificate, Validity, Version,
+};
+
+impl<'a> Certificate<'a> {
+    /// Reconstruct the DER encoding of the certificate from its parsed fields
+    ///
+    /// The output is byte-identical to the input only if the input was canonical DER,
+    /// e.g. a v1 certificate that explicitly encodes its default version is not reproduced.
+    /// Fails if a field holds a value without a DER encoding.
+    pub fn reencode(&self) -> Result<Vec<u8>, ASNError> {
+        let mut certificate = SequenceBuilder::new();
+        certificate
+            .add_sequence(&self.tbs_certificate.value.encode()?)
+            .add_sequence(&self.signature_algorithm.encode()?)
+            .add(&bit_string(&self.signature_value))?;
+        Ok(certificate.build())
+    }
+}
+
+impl<'a> TBSCertificate<'a> {
+    fn encode(&self) -> Result<SequenceBuilder, ASNError> {
+        let mut tbs = SequenceBuilder::new();
+
+        // v1 is the DEFAULT and is omitted
+        let version = match self.version {
+            Version::V1 => None,
+            Version::V2 => Some(1),
+            Version::V3 => Some(2),
+        };
+        if let Some(version) = version {
+            let mut contents = Vec::new();
+            encode::encode(&Integer::asn(ASNInteger::new(&[version])), &mut contents)?;
+            tbs.add(&ExplicitTag::asn(ASNExplicitTag::new(0, &contents)))?;
+        }
+
+        tbs.add(&Integer::asn(ASNInteger::new(
+            self.serial_number.der_content(),
+        )))?
+        .add_sequence(&self.signature.encode()?)
+        .add(&Sequence::asn(self.issuer.inner))?
+        .add_sequence(&self.validity.encode()?)
+        .add(&Sequence::asn(self.subject.inner))?
+        .add_sequence(&self.subject_public_key_info.encode()?);
+
+        for (tag, unique_id) in [(1, &self.issuer_unique_id), (2, &self.subject_unique_id)] {
+            if let Some(unique_id) = unique_id {
+                tbs.add_implicit(tag, &bit_string(unique_id))?;
+            }
+        }
+
+        if let Some(extensions) = &self.extensions {
+            tbs.add(&ExplicitTag::asn(ASNExplicitTag::new(
+                3,
+                extensions.raw_content(),
+            )))?;
+        }
+
+        Ok(tbs)
+    }
+}
+
+impl<'a> AlgorithmIdentifier<'a> {
+    /// DER encoding of the SEQUENCE, omitting absent parameters
+    pub fn to_der(&self) -> Result<Vec<u8>, ASNError> {
+        Ok(self.encode()?.build())
+    }
+
+    fn encode(&self) -> Result<SequenceBuilder, ASNError> {
+        let mut algorithm = SequenceBuilder::new();
+        algorithm.add(&ObjectIdentifier::asn(self.algorithm.clone()))?;
+        if let Some(parameters) = &self.parameters {
+            algorithm.add(parameters)?;
+        }
+        Ok(algorithm)
+    }
+}
+
+impl Validity {
+    fn encode(&self) -> Result<SequenceBuilder, ASNError> {
+        fn time(value: UtcTime, kind: ASNTypeId) -> ASNType<'static> {
+            match kind {
+                ASNTypeId::GeneralizedTime => GeneralizedTime::asn(value.value),
+                _ => ASNType::UTCTime(value),
+            }
+        }
+
+        let mut validity = SequenceBuilder::new();
+        validity
+            .add(&time(self.not_before, self.not_before_kind))?
+            .add(&time(self.not_after, self.not_after_kind))?;
+        Ok(validity)
+    }
+}
+
+impl<'a> SubjectPublicKeyInfo<'a> {
+    /// DER encoding of the SEQUENCE, the key BIT STRING keeping its count of unused bits
+    pub fn to_der(&self) -> Result<Vec<u8>, ASNError> {
+        Ok(self.encode()?.build())
+    }
+
+    fn encode(&self) -> Result<SequenceBuilder, ASNError> {
+        let mut spki = SequenceBuilder::new();
+        spki.add_sequence(&self.algorithm.encode()?)
+            .add(&bit_string(&self.subject_public_key))?;
+        Ok(spki)
+    }
+}
+
+fn bit_string<'a>(value: &ASNBitString<'a>) -> ASNType<'a> {
+    BitString::asn(ASNBitString::new(value.unused_bits, value.bytes))
+}
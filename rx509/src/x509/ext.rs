@@ -1,6 +1,8 @@
 use crate::der::parser::Parser;
 use crate::der::*;
+#[cfg(feature = "display")]
 use crate::x509::printer::{print_type, LinePrinter, Printable};
+use crate::x509::Name;
 
 #[derive(Debug)]
 pub struct Extensions<'a> {
@@ -12,11 +14,69 @@ impl<'a> Extensions<'a> {
         Self { raw_content }
     }
 
-    pub fn parse(&'a self) -> Result<Vec<Extension<'a>>, ASNError> {
-        let mut extensions: Vec<Extension> = Vec::new();
-        let mut parser = Parser::unwrap_outer_sequence(self.raw_content)?;
+    pub(crate) fn raw_content(&self) -> &'a [u8] {
+        self.raw_content
+    }
+
+    pub(crate) fn contains<F>(&self, predicate: F) -> Result<bool, ASNError>
+    where
+        F: Fn(&SpecificExtension<'a>) -> bool,
+    {
+        Ok(self.parse()?.iter().any(|ext| predicate(&ext.content)))
+    }
+
+    /// Parse the extensions, failing if the same extension OID appears more than once as
+    /// required by RFC 5280
+    pub fn parse(&self) -> Result<Vec<Extension<'a>>, ASNError> {
+        self.parse_impl(false)
+    }
+
+    /// Parse the extensions, keeping every instance of an extension OID that appears more than once
+    pub fn parse_lenient(&self) -> Result<Vec<Extension<'a>>, ASNError> {
+        self.parse_impl(true)
+    }
+
+    /// Like [`Extensions::parse`], but the list and the arcs of the extension OIDs are allocated
+    /// in `arena`
+    ///
+    /// The contents of each extension are parsed as usual.
+    #[cfg(feature = "arena")]
+    pub fn parse_in(
+        &self,
+        arena: &'a bumpalo::Bump,
+    ) -> Result<bumpalo::collections::Vec<'a, Extension<'a>>, ASNError> {
+        Extensions::parse_into(
+            Parser::new_in(self.raw_content, arena).only_sequence_contents()?,
+            false,
+            bumpalo::collections::Vec::new_in(arena),
+        )
+    }
+
+    fn parse_impl(&self, allow_duplicates: bool) -> Result<Vec<Extension<'a>>, ASNError> {
+        Extensions::parse_into(
+            Parser::unwrap_outer_sequence(self.raw_content)?,
+            allow_duplicates,
+            Vec::new(),
+        )
+    }
+
+    // shared by the heap and arena parses, which differ in the collection the extensions go to
+    fn parse_into<C>(
+        mut parser: Parser<'a>,
+        allow_duplicates: bool,
+        mut extensions: C,
+    ) -> Result<C, ASNError>
+    where
+        C: Extend<Extension<'a>> + core::ops::Deref<Target = [Extension<'a>]>,
+    {
         while let Some(seq) = parser.expect_or_end::<Sequence>()? {
-            extensions.push(Extension::parse(seq)?);
+            let extension = parser.parse_nested(seq, Extension::parse_contents)?;
+            if !allow_duplicates && extensions.iter().any(|x| x.extn_id == extension.extn_id) {
+                return Err(
+                    ASNErrorVariant::DuplicateExtension(extension.extn_id.into_owned()).into(),
+                );
+            }
+            extensions.extend([extension]);
         }
         Ok(extensions)
     }
@@ -24,46 +84,71 @@ impl<'a> Extensions<'a> {
 
 #[derive(Debug)]
 pub struct Extension<'a> {
-    pub extn_id: ASNObjectIdentifier,
+    pub extn_id: ASNObjectIdentifier<'a>,
     pub critical: bool,
+    /// the critical BOOLEAN was encoded, which DER only allows when it's TRUE since FALSE is
+    /// the DEFAULT
+    pub critical_was_explicit: bool,
     pub content: SpecificExtension<'a>,
 }
 
 impl<'a> Extension<'a> {
+    /// The critical flag is taken to be encoded only when it's TRUE, as DER requires
     pub fn new(
-        extn_id: ASNObjectIdentifier,
+        extn_id: ASNObjectIdentifier<'a>,
         critical: bool,
         content: SpecificExtension<'a>,
     ) -> Extension<'a> {
         Extension {
             extn_id,
             critical,
+            critical_was_explicit: critical,
             content,
         }
     }
 
-    pub fn parse(input: &'a [u8]) -> Result<Extension, ASNError> {
-        let ret = Parser::parse_all(input, |parser| {
-            let oid = parser.expect::<ObjectIdentifier>()?;
-            let is_critical = parser.get_optional_or_default::<Boolean>(false)?;
-            let raw_content = parser.expect::<OctetString>()?;
+    pub fn parse(input: &'a [u8]) -> Result<Extension<'a>, ASNError> {
+        Parser::parse_all(input, Extension::parse_contents)
+    }
 
-            let content = match oid.values() {
-                [2, 5, 29, 14] => SubjectKeyIdentifier::parse(raw_content)?.into(),
-                [2, 5, 29, 15] => KeyUsage::parse(raw_content)?.into(),
-                [2, 5, 29, 17] => SubjectAlternativeName::parse(raw_content)?.into(),
-                [2, 5, 29, 19] => BasicConstraints::parse(raw_content)?.into(),
-                [2, 5, 29, 37] => ExtendedKeyUsage::parse(raw_content)?.into(),
-                [1, 3, 6, 1, 4, 1, 50316, 802, 1] => ModbusRole::parse(raw_content)?.into(),
-                _ => SpecificExtension::Unknown(raw_content),
-            };
+    fn parse_contents(parser: &mut Parser<'a>) -> Result<Extension<'a>, ASNError> {
+        let oid = parser.expect::<ObjectIdentifier>()?;
+        let critical = parser.get_optional::<Boolean>()?;
+        let raw_content = parser.expect::<OctetString>()?;
 
-            Ok(Extension::new(oid, is_critical, content))
-        })?;
-        Ok(ret)
+        let content = match oid.values() {
+            [2, 5, 29, 14] => SubjectKeyIdentifier::parse(raw_content)?.into(),
+            [2, 5, 29, 15] => KeyUsage::decode(raw_content, false)?.into(),
+            [2, 5, 29, 16] => PrivateKeyUsagePeriod::parse(raw_content)?.into(),
+            [2, 5, 29, 17] => SubjectAlternativeName::parse(raw_content)?.into(),
+            [2, 5, 29, 18] => IssuerAlternativeName::parse(raw_content)?.into(),
+            [2, 5, 29, 19] => BasicConstraints::parse(raw_content)?.into(),
+            [2, 5, 29, 29] => CertificateIssuer::parse(raw_content)?.into(),
+            [2, 5, 29, 30] => NameConstraints::parse(raw_content)?.into(),
+            [2, 5, 29, 31] => CrlDistributionPoints::parse(raw_content)?.into(),
+            [2, 5, 29, 32] => CertificatePolicies::parse(raw_content)?.into(),
+            [2, 5, 29, 35] => AuthorityKeyIdentifier::parse(raw_content)?.into(),
+            [2, 5, 29, 37] => ExtendedKeyUsage::parse(raw_content)?.into(),
+            [1, 3, 6, 1, 5, 5, 7, 1, 1] => AuthorityInfoAccess::parse(raw_content)?.into(),
+            [1, 3, 6, 1, 5, 5, 7, 1, 24] => TlsFeature::parse(raw_content)?.into(),
+            [1, 3, 6, 1, 5, 5, 7, 48, 1, 5] => {
+                parse_ocsp_no_check(raw_content)?;
+                SpecificExtension::OcspNoCheck
+            }
+            [1, 3, 6, 1, 4, 1, 50316, 802, 1] => ModbusRole::parse(raw_content)?.into(),
+            _ => SpecificExtension::Unknown(raw_content),
+        };
+
+        Ok(Extension {
+            extn_id: oid,
+            critical: critical.unwrap_or(false),
+            critical_was_explicit: critical.is_some(),
+            content,
+        })
     }
 }
 
+#[cfg(feature = "display")]
 impl<'a> Printable for Extension<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
         printer.begin_line();
@@ -83,8 +168,18 @@ pub enum SpecificExtension<'a> {
     SubjectKeyIdentifier(SubjectKeyIdentifier<'a>),
     KeyUsage(KeyUsage),
     SubjectAlternativeName(SubjectAlternativeName<'a>),
+    IssuerAlternativeName(IssuerAlternativeName<'a>),
     BasicConstraints(BasicConstraints),
+    PrivateKeyUsagePeriod(PrivateKeyUsagePeriod),
+    CertificateIssuer(CertificateIssuer<'a>),
+    NameConstraints(NameConstraints<'a>),
+    CrlDistributionPoints(CrlDistributionPoints<'a>),
+    CertificatePolicies(CertificatePolicies<'a>),
+    AuthorityKeyIdentifier(AuthorityKeyIdentifier<'a>),
     ExtendedKeyUsage(ExtendedKeyUsage),
+    AuthorityInfoAccess(AuthorityInfoAccess<'a>),
+    TlsFeature(TlsFeature),
+    OcspNoCheck,
     ModbusRole(ModbusRole<'a>),
     Unknown(&'a [u8]),
 }
@@ -95,22 +190,43 @@ impl<'a> SpecificExtension<'a> {
             Self::SubjectKeyIdentifier(_) => "Subject Key Identifier",
             Self::KeyUsage(_) => "Key Usage",
             Self::SubjectAlternativeName(_) => "Subject Alternative Name",
+            Self::IssuerAlternativeName(_) => "Issuer Alternative Name",
             Self::BasicConstraints(_) => "Basic Constraints",
+            Self::PrivateKeyUsagePeriod(_) => "Private Key Usage Period",
+            Self::CertificateIssuer(_) => "Certificate Issuer",
+            Self::NameConstraints(_) => "Name Constraints",
+            Self::CrlDistributionPoints(_) => "CRL Distribution Points",
+            Self::CertificatePolicies(_) => "Certificate Policies",
+            Self::AuthorityKeyIdentifier(_) => "Authority Key Identifier",
             Self::ExtendedKeyUsage(_) => "Extended Key Usage",
+            Self::AuthorityInfoAccess(_) => "Authority Information Access",
+            Self::TlsFeature(_) => "TLS Feature",
+            Self::OcspNoCheck => "OCSP No Check",
             Self::ModbusRole(_) => "Modbus Role",
             Self::Unknown(_) => "Unknown",
         }
     }
 }
 
+#[cfg(feature = "display")]
 impl<'a> Printable for SpecificExtension<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
         match self {
             Self::SubjectKeyIdentifier(x) => x.print(printer),
             Self::KeyUsage(x) => x.print(printer),
             Self::SubjectAlternativeName(x) => x.print(printer),
+            Self::IssuerAlternativeName(x) => x.print(printer),
             Self::BasicConstraints(x) => x.print(printer),
+            Self::PrivateKeyUsagePeriod(x) => x.print(printer),
+            Self::CertificateIssuer(x) => x.print(printer),
+            Self::NameConstraints(x) => x.print(printer),
+            Self::CrlDistributionPoints(x) => x.print(printer),
+            Self::CertificatePolicies(x) => x.print(printer),
+            Self::AuthorityKeyIdentifier(x) => x.print(printer),
             Self::ExtendedKeyUsage(x) => x.print(printer),
+            Self::AuthorityInfoAccess(x) => x.print(printer),
+            Self::TlsFeature(x) => x.print(printer),
+            Self::OcspNoCheck => {}
             Self::ModbusRole(x) => x.print(printer),
             Self::Unknown(x) => print_type("raw content", x, printer),
         }
@@ -123,13 +239,14 @@ pub struct SubjectKeyIdentifier<'a> {
 }
 
 impl<'a> SubjectKeyIdentifier<'a> {
-    fn parse(input: &[u8]) -> Result<SubjectKeyIdentifier, ASNErrorVariant> {
+    fn parse(input: &[u8]) -> Result<SubjectKeyIdentifier<'_>, ASNError> {
         let mut parser = Parser::new(input);
         let key_identifier = parser.expect::<OctetString>()?;
         Ok(SubjectKeyIdentifier { key_identifier })
     }
 }
 
+#[cfg(feature = "display")]
 impl<'a> Printable for SubjectKeyIdentifier<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
         print_type("key identifier", &self.key_identifier, printer);
@@ -142,6 +259,78 @@ impl<'a> From<SubjectKeyIdentifier<'a>> for SpecificExtension<'a> {
     }
 }
 
+// AuthorityKeyIdentifier ::= SEQUENCE {
+//      keyIdentifier             [0] KeyIdentifier           OPTIONAL,
+//      authorityCertIssuer       [1] GeneralNames            OPTIONAL,
+//      authorityCertSerialNumber [2] CertificateSerialNumber OPTIONAL  }
+#[derive(Debug)]
+pub struct AuthorityKeyIdentifier<'a> {
+    pub key_identifier: Option<&'a [u8]>,
+    /// empty if authorityCertIssuer is absent
+    pub authority_cert_issuer: Vec<GeneralName<'a>>,
+    pub authority_cert_serial_number: Option<ASNInteger<'a>>,
+}
+
+impl<'a> AuthorityKeyIdentifier<'a> {
+    fn parse(input: &[u8]) -> Result<AuthorityKeyIdentifier<'_>, ASNError> {
+        let mut parser = Parser::unwrap_outer_sequence(input)?;
+        let key_identifier = parser.get_optional_context_field(0)?;
+        let authority_cert_issuer = match parser.get_optional_context_field(1)? {
+            Some(contents) => parse_general_names_from(Parser::new(contents))?,
+            None => Vec::new(),
+        };
+        let authority_cert_serial_number = match parser.get_optional_context_field(2)? {
+            Some(contents) => Some(Parser::new(contents).parse_implicit::<Integer>()?),
+            None => None,
+        };
+        parser.expect_end()?;
+        Ok(AuthorityKeyIdentifier {
+            key_identifier,
+            authority_cert_issuer,
+            authority_cert_serial_number,
+        })
+    }
+
+    /// Names of the issuer of the issuing CA certificate, empty if only a key identifier is used
+    pub fn issuer_names(&self) -> &[GeneralName<'a>] {
+        &self.authority_cert_issuer
+    }
+
+    /// Serial number of the issuing CA certificate
+    pub fn serial(&self) -> Option<&ASNInteger<'a>> {
+        self.authority_cert_serial_number.as_ref()
+    }
+}
+
+#[cfg(feature = "display")]
+impl<'a> Printable for AuthorityKeyIdentifier<'a> {
+    fn print(&self, printer: &mut dyn LinePrinter) {
+        if let Some(key_identifier) = &self.key_identifier {
+            print_type("key identifier", key_identifier, printer);
+        }
+        if !self.authority_cert_issuer.is_empty() {
+            printer.begin_line();
+            printer.println_str("authority cert issuer:");
+            printer.begin_type();
+            for name in &self.authority_cert_issuer {
+                printer.begin_line();
+                name.print(printer);
+            }
+            printer.end_type();
+        }
+        if let Some(serial) = &self.authority_cert_serial_number {
+            printer.begin_line();
+            printer.println_fmt(&format_args!("authority cert serial number: {}", serial));
+        }
+    }
+}
+
+impl<'a> From<AuthorityKeyIdentifier<'a>> for SpecificExtension<'a> {
+    fn from(from: AuthorityKeyIdentifier<'a>) -> Self {
+        SpecificExtension::AuthorityKeyIdentifier(from)
+    }
+}
+
 #[derive(Debug)]
 pub struct KeyUsage {
     pub digital_signature: bool,
@@ -156,9 +345,29 @@ pub struct KeyUsage {
 }
 
 impl KeyUsage {
-    fn parse(input: &[u8]) -> Result<KeyUsage, ASNErrorVariant> {
+    // the number of named bits, digitalSignature (0) through decipherOnly (8)
+    const NAMED_BITS: usize = 9;
+
+    /// Parse a KeyUsage extnValue, ignoring bits past decipherOnly and any trailing bytes
+    pub fn parse(input: &[u8]) -> Result<KeyUsage, ASNError> {
+        KeyUsage::decode(input, false).map_err(|err| err.relative_to(input))
+    }
+
+    /// Parse a KeyUsage extnValue, failing if the bit string is longer than the named bits
+    /// or is followed by trailing bytes
+    pub fn parse_strict(input: &[u8]) -> Result<KeyUsage, ASNError> {
+        KeyUsage::decode(input, true).map_err(|err| err.relative_to(input))
+    }
+
+    fn decode(input: &[u8], strict: bool) -> Result<KeyUsage, ASNError> {
         let mut parser = Parser::new(input);
         let bit_string = parser.expect::<BitString>()?;
+        if strict {
+            parser.expect_end()?;
+            if bit_string.size() > KeyUsage::NAMED_BITS {
+                return Err(ASNErrorVariant::KeyUsageTooManyBits(bit_string.size()).into());
+            }
+        }
 
         let mut key_usage = KeyUsage {
             digital_signature: false,
@@ -171,8 +380,7 @@ impl KeyUsage {
             encipher_only: false,
             decipher_only: false,
         };
-        let mut offset = 0;
-        for bit in bit_string.iter() {
+        for (offset, bit) in bit_string.iter().enumerate() {
             match offset {
                 0 => key_usage.digital_signature = bit,
                 1 => key_usage.content_commitment = bit,
@@ -185,12 +393,12 @@ impl KeyUsage {
                 8 => key_usage.decipher_only = bit,
                 _ => {}
             }
-            offset += offset;
         }
         Ok(key_usage)
     }
 }
 
+#[cfg(feature = "display")]
 impl Printable for KeyUsage {
     fn print(&self, printer: &mut dyn LinePrinter) {
         fn print_usage(description: &str, printer: &mut dyn LinePrinter) {
@@ -239,19 +447,43 @@ impl<'a> From<KeyUsage> for SpecificExtension<'a> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum GeneralName<'a> {
-    OtherName(&'a [u8]),
+    /// an otherName that is not specially handled, with the DER encoding of its value
+    OtherName {
+        type_id: ASNObjectIdentifier<'a>,
+        value: &'a [u8],
+    },
+    /// Microsoft UPN (1.3.6.1.4.1.311.20.2.3) carried in an otherName
+    UserPrincipalName(&'a str),
     Rfc822Name(&'a str),
     DnsName(&'a str),
     X400Address,
-    DirectoryName,
+    DirectoryName(Name<'a>),
     EdiPartyName,
     UniformResourceIdentifier(&'a str),
     IpAddress(&'a [u8]),
-    RegisteredId(ASNObjectIdentifier),
+    RegisteredId(ASNObjectIdentifier<'a>),
+}
+
+impl<'a> GeneralName<'a> {
+    /// The address of an iPAddress name holding 4 or 16 octets
+    ///
+    /// None for any other name, including the address and mask pairs of NameConstraints.
+    pub fn ip_addr(&self) -> Option<std::net::IpAddr> {
+        match self {
+            GeneralName::IpAddress(value) => match *value {
+                [a, b, c, d] => Some(std::net::Ipv4Addr::new(*a, *b, *c, *d).into()),
+                _ => <[u8; 16]>::try_from(*value)
+                    .ok()
+                    .map(|x| std::net::Ipv6Addr::from(x).into()),
+            },
+            _ => None,
+        }
+    }
 }
 
+#[cfg(feature = "display")]
 impl<'a> Printable for GeneralName<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
         match self {
@@ -261,13 +493,32 @@ impl<'a> Printable for GeneralName<'a> {
             GeneralName::DnsName(value) => {
                 printer.println_fmt(&format_args!("DNS Name: {}", value))
             }
+            GeneralName::UserPrincipalName(value) => {
+                printer.println_fmt(&format_args!("User Principal Name: {}", value))
+            }
             GeneralName::UniformResourceIdentifier(value) => {
                 printer.println_fmt(&format_args!("Uniform Resource Identifier: {}", value))
             }
-            GeneralName::IpAddress(value) => print_type("IP Address", value, printer),
+            GeneralName::IpAddress(value) => match self.ip_addr() {
+                Some(address) => printer.println_fmt(&format_args!("IP Address: {}", address)),
+                None => print_type("IP Address", value, printer),
+            },
             GeneralName::RegisteredId(value) => {
                 printer.println_fmt(&format_args!("Registered ID: {}", value))
             }
+            GeneralName::OtherName { type_id, value } => {
+                printer.println_fmt(&format_args!("Other Name: {}", type_id));
+                printer.begin_type();
+                value.print(printer);
+                printer.end_type();
+            }
+            GeneralName::DirectoryName(value) => match value.parse() {
+                Ok(name) => printer.println_fmt(&format_args!(
+                    "Directory Name: {}",
+                    name.to_rfc4514_string()
+                )),
+                Err(err) => printer.println_fmt(&format_args!("Directory Name: {}", err)),
+            },
             _ => printer.println_str("Unsupported name type"),
         }
     }
@@ -279,42 +530,337 @@ pub struct SubjectAlternativeName<'a> {
 }
 
 impl<'a> SubjectAlternativeName<'a> {
-    fn parse(input: &[u8]) -> Result<SubjectAlternativeName, ASNErrorVariant> {
-        let mut parser = Parser::unwrap_outer_sequence(input)?;
-        let mut names: Vec<GeneralName> = Vec::new();
-
-        while let Some(tag) = parser.expect_or_end::<ExplicitTag>()? {
-            let mut parser = Parser::new(tag.contents);
-            match tag.value {
-                // TODO: parse the other types
-                1 => names.push(GeneralName::Rfc822Name(
-                    parser.parse_implicit::<IA5String>()?,
-                )),
-                2 => names.push(GeneralName::DnsName(parser.parse_implicit::<IA5String>()?)),
-                6 => names.push(GeneralName::UniformResourceIdentifier(
-                    parser.parse_implicit::<IA5String>()?,
-                )),
-                7 => names.push(GeneralName::IpAddress(
-                    parser.parse_implicit::<OctetString>()?,
-                )),
-                8 => names.push(GeneralName::RegisteredId(
-                    parser.parse_implicit::<ObjectIdentifier>()?,
-                )),
+    fn parse(input: &[u8]) -> Result<SubjectAlternativeName<'_>, ASNError> {
+        Ok(SubjectAlternativeName {
+            names: parse_general_names(input)?,
+        })
+    }
+}
+
+// GeneralNames ::= SEQUENCE SIZE (1..MAX) OF GeneralName
+fn parse_general_names(input: &[u8]) -> Result<Vec<GeneralName<'_>>, ASNError> {
+    parse_general_names_from(Parser::unwrap_outer_sequence(input)?)
+}
+
+// reads GeneralName elements until the end, for use on SEQUENCE contents or an IMPLICIT tag
+fn parse_general_names_from(mut parser: Parser) -> Result<Vec<GeneralName>, ASNError> {
+    let mut names: Vec<GeneralName> = Vec::new();
+
+    while let Some(tag) = parser.expect_or_end::<ExplicitTag>()? {
+        names.push(parse_general_name(tag)?);
+    }
+
+    Ok(names)
+}
 
-                _ => return Err(ASNErrorVariant::UnexpectedTag(tag.value)),
-            };
+// a single GeneralName, from the context-specific tag that selects the CHOICE
+fn parse_general_name(tag: ASNExplicitTag) -> Result<GeneralName, ASNError> {
+    let mut parser = Parser::new(tag.contents);
+    let name = match tag.value {
+        0 => parse_other_name(tag.contents)?,
+        1 => GeneralName::Rfc822Name(parser.parse_implicit::<IA5String>()?),
+        2 => GeneralName::DnsName(parser.parse_implicit::<IA5String>()?),
+        // Name is a CHOICE so the tag is EXPLICIT
+        4 => GeneralName::DirectoryName(Parser::parse_all(tag.contents, Name::parse_from)?),
+        6 => GeneralName::UniformResourceIdentifier(parser.parse_implicit::<IA5String>()?),
+        7 => GeneralName::IpAddress(parser.parse_implicit::<OctetString>()?),
+        8 => GeneralName::RegisteredId(parser.parse_implicit::<ObjectIdentifier>()?),
+
+        _ => return Err(ASNErrorVariant::UnexpectedTag(tag.value).into()),
+    };
+    Ok(name)
+}
+
+// OtherName ::= SEQUENCE {
+//      type-id    OBJECT IDENTIFIER,
+//      value      [0] EXPLICIT ANY DEFINED BY type-id }
+fn parse_other_name(input: &[u8]) -> Result<GeneralName<'_>, ASNError> {
+    let mut parser = Parser::new(input);
+    let type_id = parser.expect::<ObjectIdentifier>()?;
+    let value = parser.expect::<ExplicitTag>()?;
+    if value.value != 0 {
+        return Err(ASNErrorVariant::UnexpectedTag(value.value).into());
+    }
+    parser.expect_end()?;
+    match type_id.values() {
+        [1, 3, 6, 1, 4, 1, 311, 20, 2, 3] => {
+            let upn = Parser::parse_all(value.contents, |parser| parser.expect::<UTF8String>())?;
+            Ok(GeneralName::UserPrincipalName(upn))
         }
+        _ => Ok(GeneralName::OtherName {
+            type_id,
+            value: value.contents,
+        }),
+    }
+}
 
-        Ok(SubjectAlternativeName { names })
+// the GeneralNames of SubjectAlternativeName, IssuerAlternativeName and CertificateIssuer
+#[cfg(feature = "display")]
+fn print_general_names(names: &[GeneralName], printer: &mut dyn LinePrinter) {
+    printer.begin_line();
+    printer.println_str("names:");
+    printer.begin_type();
+    for name in names {
+        printer.begin_line();
+        name.print(printer);
     }
+    printer.end_type();
 }
 
+#[cfg(feature = "display")]
 impl<'a> Printable for SubjectAlternativeName<'a> {
+    fn print(&self, printer: &mut dyn LinePrinter) {
+        print_general_names(&self.names, printer);
+    }
+}
+
+impl<'a> From<SubjectAlternativeName<'a>> for SpecificExtension<'a> {
+    fn from(from: SubjectAlternativeName<'a>) -> Self {
+        SpecificExtension::SubjectAlternativeName(from)
+    }
+}
+
+#[derive(Debug)]
+pub struct IssuerAlternativeName<'a> {
+    pub names: Vec<GeneralName<'a>>,
+}
+
+impl<'a> IssuerAlternativeName<'a> {
+    fn parse(input: &[u8]) -> Result<IssuerAlternativeName<'_>, ASNError> {
+        Ok(IssuerAlternativeName {
+            names: parse_general_names(input)?,
+        })
+    }
+}
+
+#[cfg(feature = "display")]
+impl<'a> Printable for IssuerAlternativeName<'a> {
+    fn print(&self, printer: &mut dyn LinePrinter) {
+        print_general_names(&self.names, printer);
+    }
+}
+
+impl<'a> From<IssuerAlternativeName<'a>> for SpecificExtension<'a> {
+    fn from(from: IssuerAlternativeName<'a>) -> Self {
+        SpecificExtension::IssuerAlternativeName(from)
+    }
+}
+
+/// CRL entry extension identifying the issuer of the revoked certificate in an indirect CRL
+#[derive(Debug)]
+pub struct CertificateIssuer<'a> {
+    pub names: Vec<GeneralName<'a>>,
+}
+
+impl<'a> CertificateIssuer<'a> {
+    fn parse(input: &[u8]) -> Result<CertificateIssuer<'_>, ASNError> {
+        Ok(CertificateIssuer {
+            names: parse_general_names(input)?,
+        })
+    }
+}
+
+#[cfg(feature = "display")]
+impl<'a> Printable for CertificateIssuer<'a> {
+    fn print(&self, printer: &mut dyn LinePrinter) {
+        print_general_names(&self.names, printer);
+    }
+}
+
+impl<'a> From<CertificateIssuer<'a>> for SpecificExtension<'a> {
+    fn from(from: CertificateIssuer<'a>) -> Self {
+        SpecificExtension::CertificateIssuer(from)
+    }
+}
+
+// NameConstraints ::= SEQUENCE {
+//      permittedSubtrees       [0]     GeneralSubtrees OPTIONAL,
+//      excludedSubtrees        [1]     GeneralSubtrees OPTIONAL }
+//
+// GeneralSubtrees ::= SEQUENCE SIZE (1..MAX) OF GeneralSubtree
+#[derive(Debug)]
+pub struct NameConstraints<'a> {
+    pub permitted_subtrees: Vec<GeneralSubtree<'a>>,
+    pub excluded_subtrees: Vec<GeneralSubtree<'a>>,
+}
+
+// GeneralSubtree ::= SEQUENCE {
+//      base                    GeneralName,
+//      minimum         [0]     BaseDistance DEFAULT 0,
+//      maximum         [1]     BaseDistance OPTIONAL }
+#[derive(Debug)]
+pub struct GeneralSubtree<'a> {
+    pub base: GeneralName<'a>,
+    /// RFC 5280 requires this to be absent, but some CAs encode it anyway
+    pub minimum: Option<ASNInteger<'a>>,
+    /// RFC 5280 requires this to be absent, but some CAs encode it anyway
+    pub maximum: Option<ASNInteger<'a>>,
+}
+
+impl<'a> NameConstraints<'a> {
+    fn parse(input: &[u8]) -> Result<NameConstraints<'_>, ASNError> {
+        let mut parser = Parser::unwrap_outer_sequence(input)?;
+        let permitted_subtrees = match parser.get_optional_context_field(0)? {
+            Some(contents) => GeneralSubtree::parse_all(contents)?,
+            None => Vec::new(),
+        };
+        let excluded_subtrees = match parser.get_optional_context_field(1)? {
+            Some(contents) => GeneralSubtree::parse_all(contents)?,
+            None => Vec::new(),
+        };
+        parser.expect_end()?;
+
+        Ok(NameConstraints {
+            permitted_subtrees,
+            excluded_subtrees,
+        })
+    }
+}
+
+impl<'a> GeneralSubtree<'a> {
+    // reads GeneralSubtree elements until the end of the IMPLICIT GeneralSubtrees tag
+    fn parse_all(input: &[u8]) -> Result<Vec<GeneralSubtree<'_>>, ASNError> {
+        let mut parser = Parser::new(input);
+        let mut subtrees = Vec::new();
+        while let Some(subtree) = parser.expect_or_end::<Sequence>()? {
+            subtrees.push(GeneralSubtree::parse(subtree)?);
+        }
+        Ok(subtrees)
+    }
+
+    fn parse(input: &[u8]) -> Result<GeneralSubtree<'_>, ASNError> {
+        let mut parser = Parser::new(input);
+        let base = parse_general_name(parser.expect::<ExplicitTag>()?)?;
+        let minimum = parser
+            .get_optional_context_field(0)?
+            .map(|x| Parser::new(x).parse_implicit::<Integer>())
+            .transpose()?;
+        let maximum = parser
+            .get_optional_context_field(1)?
+            .map(|x| Parser::new(x).parse_implicit::<Integer>())
+            .transpose()?;
+        parser.expect_end()?;
+
+        Ok(GeneralSubtree {
+            base,
+            minimum,
+            maximum,
+        })
+    }
+}
+
+#[cfg(feature = "display")]
+impl<'a> Printable for NameConstraints<'a> {
+    fn print(&self, printer: &mut dyn LinePrinter) {
+        for (label, subtrees) in [
+            ("permitted:", &self.permitted_subtrees),
+            ("excluded:", &self.excluded_subtrees),
+        ] {
+            if subtrees.is_empty() {
+                continue;
+            }
+            printer.begin_line();
+            printer.println_str(label);
+            printer.begin_type();
+            for subtree in subtrees {
+                printer.begin_line();
+                subtree.base.print(printer);
+            }
+            printer.end_type();
+        }
+    }
+}
+
+impl<'a> From<NameConstraints<'a>> for SpecificExtension<'a> {
+    fn from(from: NameConstraints<'a>) -> Self {
+        SpecificExtension::NameConstraints(from)
+    }
+}
+
+// CRLDistributionPoints ::= SEQUENCE SIZE (1..MAX) OF DistributionPoint
+#[derive(Debug)]
+pub struct CrlDistributionPoints<'a> {
+    pub points: Vec<DistributionPoint<'a>>,
+}
+
+// DistributionPoint ::= SEQUENCE {
+//      distributionPoint       [0]     DistributionPointName OPTIONAL,
+//      reasons                 [1]     ReasonFlags OPTIONAL,
+//      cRLIssuer               [2]     GeneralNames OPTIONAL }
+//
+// DistributionPointName ::= CHOICE {
+//      fullName                [0]     GeneralNames,
+//      nameRelativeToCRLIssuer [1]     RelativeDistinguishedName }
+#[derive(Debug)]
+pub struct DistributionPoint<'a> {
+    /// empty if distributionPoint is absent or uses nameRelativeToCRLIssuer
+    pub full_name: Vec<GeneralName<'a>>,
+    /// raw contents of nameRelativeToCRLIssuer
+    pub name_relative_to_crl_issuer: Option<&'a [u8]>,
+    /// raw contents of the reasons BIT STRING
+    pub reasons: Option<&'a [u8]>,
+    /// raw contents of cRLIssuer
+    pub crl_issuer: Option<&'a [u8]>,
+}
+
+impl<'a> CrlDistributionPoints<'a> {
+    fn parse(input: &[u8]) -> Result<CrlDistributionPoints<'_>, ASNError> {
+        let mut parser = Parser::unwrap_outer_sequence(input)?;
+        let mut points = Vec::new();
+        while let Some(point) = parser.expect_or_end::<Sequence>()? {
+            points.push(DistributionPoint::parse(point)?);
+        }
+        Ok(CrlDistributionPoints { points })
+    }
+
+    /// Every URI of the fullName of each distribution point, in order
+    pub fn uris(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.points
+            .iter()
+            .flat_map(|point| &point.full_name)
+            .filter_map(|name| match name {
+                GeneralName::UniformResourceIdentifier(uri) => Some(*uri),
+                _ => None,
+            })
+    }
+}
+
+impl<'a> DistributionPoint<'a> {
+    fn parse(input: &[u8]) -> Result<DistributionPoint<'_>, ASNError> {
+        let mut parser = Parser::new(input);
+
+        let mut full_name = Vec::new();
+        let mut name_relative_to_crl_issuer = None;
+        // DistributionPointName is a CHOICE so the tag is EXPLICIT
+        if let Some(contents) = parser.get_optional_context_field(0)? {
+            let name = Parser::parse_all(contents, |parser| parser.expect::<ExplicitTag>())?;
+            match name.value {
+                0 => full_name = parse_general_names_from(Parser::new(name.contents))?,
+                1 => name_relative_to_crl_issuer = Some(name.contents),
+                x => return Err(ASNErrorVariant::UnexpectedTag(x).into()),
+            }
+        }
+
+        let reasons = parser.get_optional_context_field(1)?;
+        let crl_issuer = parser.get_optional_context_field(2)?;
+        parser.expect_end()?;
+
+        Ok(DistributionPoint {
+            full_name,
+            name_relative_to_crl_issuer,
+            reasons,
+            crl_issuer,
+        })
+    }
+}
+
+#[cfg(feature = "display")]
+impl<'a> Printable for CrlDistributionPoints<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
         printer.begin_line();
-        printer.println_str("names:");
+        printer.println_str("full names:");
         printer.begin_type();
-        for name in &self.names {
+        for name in self.points.iter().flat_map(|point| &point.full_name) {
             printer.begin_line();
             name.print(printer);
         }
@@ -322,9 +868,9 @@ impl<'a> Printable for SubjectAlternativeName<'a> {
     }
 }
 
-impl<'a> From<SubjectAlternativeName<'a>> for SpecificExtension<'a> {
-    fn from(from: SubjectAlternativeName<'a>) -> Self {
-        SpecificExtension::SubjectAlternativeName(from)
+impl<'a> From<CrlDistributionPoints<'a>> for SpecificExtension<'a> {
+    fn from(from: CrlDistributionPoints<'a>) -> Self {
+        SpecificExtension::CrlDistributionPoints(from)
     }
 }
 
@@ -335,12 +881,16 @@ pub struct BasicConstraints {
 }
 
 impl BasicConstraints {
-    fn parse(input: &[u8]) -> Result<BasicConstraints, ASNErrorVariant> {
+    fn parse(input: &[u8]) -> Result<BasicConstraints, ASNError> {
         let mut parser = Parser::unwrap_outer_sequence(input)?;
         let ca = parser.get_optional_or_default::<Boolean>(false)?;
         let constraint = parser.get_optional::<Integer>()?;
         let constraint = match constraint {
             Some(value) => match value.as_i32() {
+                // RFC 5280: pathLenConstraint INTEGER (0..MAX)
+                Some(value) if value < 0 => {
+                    Err(ASNErrorVariant::NegativeValue("pathLenConstraint", value))
+                }
                 Some(value) => Ok(Some(value)),
                 None => Err(ASNErrorVariant::IntegerTooLarge(value.bytes.len())),
             },
@@ -352,8 +902,16 @@ impl BasicConstraints {
             path_length_constraint: constraint,
         })
     }
+
+    /// Maximum number of intermediate certificates that may follow this one in a certification path,
+    /// or `None` if the path length is unconstrained
+    pub fn max_intermediate_certs(&self) -> Option<usize> {
+        self.path_length_constraint
+            .and_then(|value| usize::try_from(value).ok())
+    }
 }
 
+#[cfg(feature = "display")]
 impl Printable for BasicConstraints {
     fn print(&self, printer: &mut dyn LinePrinter) {
         printer.begin_line();
@@ -371,6 +929,55 @@ impl<'a> From<BasicConstraints> for SpecificExtension<'a> {
     }
 }
 
+// PrivateKeyUsagePeriod ::= SEQUENCE {
+//      notBefore       [0]     GeneralizedTime OPTIONAL,
+//      notAfter        [1]     GeneralizedTime OPTIONAL }
+#[derive(Debug)]
+pub struct PrivateKeyUsagePeriod {
+    pub not_before: Option<UtcTime>,
+    pub not_after: Option<UtcTime>,
+}
+
+impl PrivateKeyUsagePeriod {
+    fn parse(input: &[u8]) -> Result<PrivateKeyUsagePeriod, ASNError> {
+        let mut parser = Parser::unwrap_outer_sequence(input)?;
+        let not_before = parser
+            .get_optional_context_field(0)?
+            .map(|x| Parser::new(x).parse_implicit::<GeneralizedTime>())
+            .transpose()?;
+        let not_after = parser
+            .get_optional_context_field(1)?
+            .map(|x| Parser::new(x).parse_implicit::<GeneralizedTime>())
+            .transpose()?;
+        parser.expect_end()?;
+
+        Ok(PrivateKeyUsagePeriod {
+            not_before,
+            not_after,
+        })
+    }
+}
+
+#[cfg(feature = "display")]
+impl Printable for PrivateKeyUsagePeriod {
+    fn print(&self, printer: &mut dyn LinePrinter) {
+        if let Some(time) = self.not_before {
+            printer.begin_line();
+            printer.println_fmt(&format_args!("not before: {}", time.to_iso8601()));
+        }
+        if let Some(time) = self.not_after {
+            printer.begin_line();
+            printer.println_fmt(&format_args!("not after: {}", time.to_iso8601()));
+        }
+    }
+}
+
+impl<'a> From<PrivateKeyUsagePeriod> for SpecificExtension<'a> {
+    fn from(from: PrivateKeyUsagePeriod) -> Self {
+        SpecificExtension::PrivateKeyUsagePeriod(from)
+    }
+}
+
 #[derive(Debug)]
 pub enum ExtendedKeyUsagePurpose {
     ServerAuth,
@@ -379,6 +986,13 @@ pub enum ExtendedKeyUsagePurpose {
     EmailProtection,
     TimeStamping,
     OCSPSigning,
+    IpsecEndSystem,
+    IpsecTunnel,
+    IpsecUser,
+    AnyExtendedKeyUsage,
+    MicrosoftSmartcardLogon,
+    MicrosoftDocumentSigning,
+    Other(ASNObjectIdentifier<'static>),
 }
 
 impl ExtendedKeyUsagePurpose {
@@ -390,6 +1004,17 @@ impl ExtendedKeyUsagePurpose {
             [1, 3, 6, 1, 5, 5, 7, 3, 4] => Some(ExtendedKeyUsagePurpose::EmailProtection),
             [1, 3, 6, 1, 5, 5, 7, 3, 8] => Some(ExtendedKeyUsagePurpose::TimeStamping),
             [1, 3, 6, 1, 5, 5, 7, 3, 9] => Some(ExtendedKeyUsagePurpose::OCSPSigning),
+            // deprecated by RFC 4945, but still found in the wild
+            [1, 3, 6, 1, 5, 5, 7, 3, 5] => Some(ExtendedKeyUsagePurpose::IpsecEndSystem),
+            [1, 3, 6, 1, 5, 5, 7, 3, 6] => Some(ExtendedKeyUsagePurpose::IpsecTunnel),
+            [1, 3, 6, 1, 5, 5, 7, 3, 7] => Some(ExtendedKeyUsagePurpose::IpsecUser),
+            [2, 5, 29, 37, 0] => Some(ExtendedKeyUsagePurpose::AnyExtendedKeyUsage),
+            [1, 3, 6, 1, 4, 1, 311, 20, 2, 2] => {
+                Some(ExtendedKeyUsagePurpose::MicrosoftSmartcardLogon)
+            }
+            [1, 3, 6, 1, 4, 1, 311, 10, 3, 12] => {
+                Some(ExtendedKeyUsagePurpose::MicrosoftDocumentSigning)
+            }
             _ => None,
         }
     }
@@ -401,20 +1026,14 @@ pub struct ExtendedKeyUsage {
 }
 
 impl ExtendedKeyUsage {
-    fn parse(input: &[u8]) -> Result<ExtendedKeyUsage, ASNErrorVariant> {
-        let mut parser = Parser::unwrap_outer_sequence(input)?;
-        let mut purposes: Vec<ExtendedKeyUsagePurpose> = Vec::new();
-
-        while let Some(oid) = parser.expect_or_end::<ObjectIdentifier>()? {
-            match ExtendedKeyUsagePurpose::try_from_id(&oid) {
-                Some(purpose) => purposes.push(purpose),
-                None => return Err(ASNErrorVariant::UnexpectedOid(oid)),
-            }
-        }
-
-        Ok(ExtendedKeyUsage {
-            ext_key_usages: purposes,
-        })
+    fn parse(input: &[u8]) -> Result<ExtendedKeyUsage, ASNError> {
+        let ext_key_usages = Parser::parse_all(input, |parser| {
+            parser.expect_sequence_of::<ObjectIdentifier, _, _>(|oid| {
+                Ok(ExtendedKeyUsagePurpose::try_from_id(&oid)
+                    .unwrap_or(ExtendedKeyUsagePurpose::Other(oid.into_owned())))
+            })
+        })?;
+        Ok(ExtendedKeyUsage { ext_key_usages })
     }
 }
 
@@ -424,6 +1043,7 @@ impl<'a> From<ExtendedKeyUsage> for SpecificExtension<'a> {
     }
 }
 
+#[cfg(feature = "display")]
 impl Printable for ExtendedKeyUsage {
     fn print(&self, printer: &mut dyn LinePrinter) {
         printer.begin_line();
@@ -431,19 +1051,226 @@ impl Printable for ExtendedKeyUsage {
         printer.begin_type();
         for purpose in &self.ext_key_usages {
             printer.begin_line();
-            printer.println_fmt(&format_args!("{:?}", purpose));
+            match purpose {
+                ExtendedKeyUsagePurpose::Other(oid) => {
+                    printer.println_fmt(&format_args!("{}", oid))
+                }
+                _ => printer.println_fmt(&format_args!("{:?}", purpose)),
+            }
+        }
+        printer.end_type();
+    }
+}
+
+// certificatePolicies ::= SEQUENCE SIZE (1..MAX) OF PolicyInformation
+//
+// PolicyInformation ::= SEQUENCE {
+//      policyIdentifier   CertPolicyId,
+//      policyQualifiers   SEQUENCE SIZE (1..MAX) OF
+//                              PolicyQualifierInfo OPTIONAL }
+#[derive(Debug)]
+pub struct CertificatePolicies<'a> {
+    pub policies: Vec<PolicyInformation<'a>>,
+}
+
+#[derive(Debug)]
+pub struct PolicyInformation<'a> {
+    pub policy_identifier: ASNObjectIdentifier<'a>,
+    /// complete DER encoding of the policyQualifiers SEQUENCE
+    pub policy_qualifiers: Option<&'a [u8]>,
+}
+
+impl<'a> CertificatePolicies<'a> {
+    fn parse(input: &[u8]) -> Result<CertificatePolicies<'_>, ASNError> {
+        let mut parser = Parser::unwrap_outer_sequence(input)?;
+        let mut policies = Vec::new();
+        while let Some(policy) = parser.expect_or_end::<Sequence>()? {
+            policies.push(Parser::parse_all(policy, |parser| {
+                Ok(PolicyInformation {
+                    policy_identifier: parser.expect::<ObjectIdentifier>()?,
+                    policy_qualifiers: parser
+                        .get_optional_with_der::<Sequence>()?
+                        .map(|(_, der)| der),
+                })
+            })?);
+        }
+        Ok(CertificatePolicies { policies })
+    }
+}
+
+impl<'a> PolicyInformation<'a> {
+    /// True for the special anyPolicy (2.5.29.32.0) identifier
+    pub fn is_any_policy(&self) -> bool {
+        self.policy_identifier.values() == [2, 5, 29, 32, 0]
+    }
+}
+
+#[cfg(feature = "display")]
+impl<'a> Printable for CertificatePolicies<'a> {
+    fn print(&self, printer: &mut dyn LinePrinter) {
+        for policy in &self.policies {
+            printer.begin_line();
+            printer.println_fmt(&format_args!("policy: {}", policy.policy_identifier));
+            if let Some(qualifiers) = policy.policy_qualifiers {
+                printer.begin_type();
+                print_type("qualifiers (raw)", &qualifiers, printer);
+                printer.end_type();
+            }
+        }
+    }
+}
+
+impl<'a> From<CertificatePolicies<'a>> for SpecificExtension<'a> {
+    fn from(from: CertificatePolicies<'a>) -> Self {
+        SpecificExtension::CertificatePolicies(from)
+    }
+}
+
+// AuthorityInfoAccessSyntax ::= SEQUENCE SIZE (1..MAX) OF AccessDescription
+//
+// AccessDescription ::= SEQUENCE {
+//      accessMethod          OBJECT IDENTIFIER,
+//      accessLocation        GeneralName  }
+#[derive(Debug)]
+pub struct AuthorityInfoAccess<'a> {
+    pub descriptions: Vec<(AccessMethod, GeneralName<'a>)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessMethod {
+    /// id-ad-ocsp, the location of an OCSP responder
+    Ocsp,
+    /// id-ad-caIssuers, the location of certificates issued to the issuer
+    CaIssuers,
+    Other(ASNObjectIdentifier<'static>),
+}
+
+impl AccessMethod {
+    fn from_oid(oid: ASNObjectIdentifier) -> Self {
+        match oid.values() {
+            [1, 3, 6, 1, 5, 5, 7, 48, 1] => AccessMethod::Ocsp,
+            [1, 3, 6, 1, 5, 5, 7, 48, 2] => AccessMethod::CaIssuers,
+            _ => AccessMethod::Other(oid.into_owned()),
+        }
+    }
+}
+
+impl<'a> AuthorityInfoAccess<'a> {
+    fn parse(input: &[u8]) -> Result<AuthorityInfoAccess<'_>, ASNError> {
+        let mut parser = Parser::unwrap_outer_sequence(input)?;
+        let mut descriptions = Vec::new();
+        while let Some(description) = parser.expect_or_end::<Sequence>()? {
+            descriptions.push(Parser::parse_all(description, |parser| {
+                let method = AccessMethod::from_oid(parser.expect::<ObjectIdentifier>()?);
+                let location = parse_general_name(parser.expect::<ExplicitTag>()?)?;
+                Ok((method, location))
+            })?);
+        }
+        Ok(AuthorityInfoAccess { descriptions })
+    }
+
+    /// URIs of the locations with the given access method, in order
+    pub fn uris<'b>(&'b self, method: &'b AccessMethod) -> impl Iterator<Item = &'a str> + 'b {
+        self.descriptions
+            .iter()
+            .filter(move |(x, _)| x == method)
+            .filter_map(|(_, location)| match location {
+                GeneralName::UniformResourceIdentifier(uri) => Some(*uri),
+                _ => None,
+            })
+    }
+}
+
+#[cfg(feature = "display")]
+impl<'a> Printable for AuthorityInfoAccess<'a> {
+    fn print(&self, printer: &mut dyn LinePrinter) {
+        for (method, location) in &self.descriptions {
+            printer.begin_line();
+            match method {
+                AccessMethod::Ocsp => printer.print_str("OCSP - "),
+                AccessMethod::CaIssuers => printer.print_str("CA Issuers - "),
+                AccessMethod::Other(oid) => printer.print_fmt(&format_args!("{} - ", oid)),
+            }
+            location.print(printer);
+        }
+    }
+}
+
+impl<'a> From<AuthorityInfoAccess<'a>> for SpecificExtension<'a> {
+    fn from(from: AuthorityInfoAccess<'a>) -> Self {
+        SpecificExtension::AuthorityInfoAccess(from)
+    }
+}
+
+#[derive(Debug)]
+pub struct TlsFeature {
+    pub features: Vec<i32>,
+}
+
+impl TlsFeature {
+    /// TLS extension number of status_request, i.e. OCSP must-staple
+    pub const STATUS_REQUEST: i32 = 5;
+
+    fn parse(input: &[u8]) -> Result<TlsFeature, ASNError> {
+        let mut parser = Parser::unwrap_outer_sequence(input)?;
+        let mut features: Vec<i32> = Vec::new();
+
+        while let Some(value) = parser.expect_or_end::<Integer>()? {
+            match value.as_i32() {
+                Some(x) => features.push(x),
+                None => return Err(ASNErrorVariant::IntegerTooLarge(value.bytes.len()).into()),
+            }
+        }
+
+        Ok(TlsFeature { features })
+    }
+
+    pub fn status_request(&self) -> bool {
+        self.features.contains(&Self::STATUS_REQUEST)
+    }
+}
+
+impl<'a> From<TlsFeature> for SpecificExtension<'a> {
+    fn from(from: TlsFeature) -> Self {
+        SpecificExtension::TlsFeature(from)
+    }
+}
+
+#[cfg(feature = "display")]
+impl Printable for TlsFeature {
+    fn print(&self, printer: &mut dyn LinePrinter) {
+        printer.begin_line();
+        printer.println_str("features:");
+        printer.begin_type();
+        for feature in &self.features {
+            printer.begin_line();
+            match *feature {
+                Self::STATUS_REQUEST => printer.println_str("status_request"),
+                x => printer.println_fmt(&format_args!("{}", x)),
+            }
         }
         printer.end_type();
     }
 }
 
+// RFC 6960 specifies a NULL value, but the contents are ignored so tolerate an empty value as well
+fn parse_ocsp_no_check(input: &[u8]) -> Result<(), ASNError> {
+    Parser::parse_all(input, |parser| {
+        if parser.is_empty() {
+            Ok(())
+        } else {
+            parser.expect_null()
+        }
+    })
+}
+
 #[derive(Debug)]
 pub struct ModbusRole<'a> {
     pub role: &'a str,
 }
 
 impl<'a> ModbusRole<'a> {
-    fn parse(input: &'a [u8]) -> Result<ModbusRole<'a>, ASNErrorVariant> {
+    fn parse(input: &'a [u8]) -> Result<ModbusRole<'a>, ASNError> {
         let role = Parser::parse_all(input, |parser| parser.expect::<UTF8String>())?;
 
         Ok(Self { role })
@@ -456,9 +1283,150 @@ impl<'a> From<ModbusRole<'a>> for SpecificExtension<'a> {
     }
 }
 
+#[cfg(feature = "display")]
 impl<'a> Printable for ModbusRole<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
         printer.begin_line();
         printer.println_fmt(&format_args!("role: {}", self.role))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_whether_the_critical_flag_was_encoded() {
+        // OID 2.5.29.14, BOOLEAN FALSE, OCTET STRING { OCTET STRING 01 02 }
+        let input = [
+            0x06, 0x03, 0x55, 0x1D, 0x0E, 0x01, 0x01, 0x00, 0x04, 0x04, 0x04, 0x02, 0x01, 0x02,
+        ];
+        let extension = Extension::parse(&input).unwrap();
+        assert!(!extension.critical);
+        assert!(extension.critical_was_explicit);
+
+        // the same extension with the DEFAULT omitted
+        let input = [&input[..5], &input[8..]].concat();
+        let extension = Extension::parse(&input).unwrap();
+        assert!(!extension.critical);
+        assert!(!extension.critical_was_explicit);
+        assert!(matches!(
+            extension.content,
+            SpecificExtension::SubjectKeyIdentifier(_)
+        ));
+    }
+
+    #[test]
+    fn distribution_point_keeps_reasons_and_crl_issuer_raw() {
+        // SEQUENCE { SEQUENCE {
+        //   [0] { [0] { [6] "http://a" } },
+        //   [1] keyCompromise | cACompromise,
+        //   [2] { [2] "ca" } } }
+        let input = [
+            0x30, 0x1A, 0x30, 0x18, 0xA0, 0x0C, 0xA0, 0x0A, 0x86, 0x08, 0x68, 0x74, 0x74, 0x70,
+            0x3A, 0x2F, 0x2F, 0x61, 0x81, 0x02, 0x05, 0x60, 0xA2, 0x04, 0x82, 0x02, 0x63, 0x61,
+        ];
+        let points = CrlDistributionPoints::parse(&input).unwrap();
+        assert_eq!(points.uris().collect::<Vec<_>>(), ["http://a"]);
+        assert_eq!(points.points[0].reasons, Some(&[0x05, 0x60][..]));
+        assert_eq!(
+            points.points[0].crl_issuer,
+            Some(&[0x82, 0x02, 0x63, 0x61][..])
+        );
+        assert_eq!(points.points[0].name_relative_to_crl_issuer, None);
+    }
+
+    #[test]
+    fn general_subtree_tolerates_minimum_and_maximum() {
+        // SEQUENCE { [0] { SEQUENCE { [2] "a.com", [0] 0, [1] 2 } } }
+        let input = [
+            0x30, 0x11, 0xA0, 0x0F, 0x30, 0x0D, 0x82, 0x05, 0x61, 0x2E, 0x63, 0x6F, 0x6D, 0x80,
+            0x01, 0x00, 0x81, 0x01, 0x02,
+        ];
+        let constraints = NameConstraints::parse(&input).unwrap();
+        assert!(constraints.excluded_subtrees.is_empty());
+        let subtree = &constraints.permitted_subtrees[0];
+        assert!(matches!(subtree.base, GeneralName::DnsName("a.com")));
+        assert_eq!(subtree.minimum.as_ref().and_then(|x| x.as_i32()), Some(0));
+        assert_eq!(subtree.maximum.as_ref().and_then(|x| x.as_i32()), Some(2));
+    }
+
+    #[test]
+    fn basic_constraints_path_length_as_usize() {
+        // SEQUENCE { BOOLEAN TRUE, INTEGER 3 }
+        let constraints =
+            BasicConstraints::parse(&[0x30, 0x06, 0x01, 0x01, 0xFF, 0x02, 0x01, 0x03]).unwrap();
+        assert_eq!(constraints.max_intermediate_certs(), Some(3));
+
+        // SEQUENCE { BOOLEAN TRUE }
+        let constraints = BasicConstraints::parse(&[0x30, 0x03, 0x01, 0x01, 0xFF]).unwrap();
+        assert_eq!(constraints.max_intermediate_certs(), None);
+    }
+
+    #[test]
+    fn basic_constraints_rejects_negative_path_length() {
+        // SEQUENCE { BOOLEAN TRUE, INTEGER -1 }
+        assert_eq!(
+            BasicConstraints::parse(&[0x30, 0x06, 0x01, 0x01, 0xFF, 0x02, 0x01, 0xFF])
+                .unwrap_err()
+                .variant,
+            ASNErrorVariant::NegativeValue("pathLenConstraint", -1)
+        );
+    }
+
+    #[test]
+    fn key_usage_maps_each_bit_to_its_flag() {
+        // keyCertSign and cRLSign
+        let usage = KeyUsage::parse(&[0x03, 0x02, 0x01, 0x06]).unwrap();
+        assert!(usage.key_cert_sign);
+        assert!(usage.crl_sign);
+        assert!(!usage.digital_signature);
+        assert!(!usage.content_commitment);
+        assert!(!usage.key_encipherment);
+        assert!(!usage.data_encipherment);
+        assert!(!usage.key_agreement);
+        assert!(!usage.encipher_only);
+        assert!(!usage.decipher_only);
+        assert!(KeyUsage::parse_strict(&[0x03, 0x02, 0x01, 0x06]).is_ok());
+    }
+
+    #[test]
+    fn key_usage_with_ten_bits_is_only_accepted_leniently() {
+        // digitalSignature and an undefined 10th bit
+        let input = [0x03, 0x03, 0x06, 0x80, 0x40];
+
+        let usage = KeyUsage::parse(&input).unwrap();
+        assert!(usage.digital_signature);
+        assert!(!usage.decipher_only);
+
+        assert_eq!(
+            KeyUsage::parse_strict(&input).unwrap_err().variant,
+            ASNErrorVariant::KeyUsageTooManyBits(10)
+        );
+    }
+
+    #[test]
+    fn key_usage_with_trailing_bytes_is_only_accepted_leniently() {
+        let input = [0x03, 0x02, 0x07, 0x80, 0x00];
+
+        assert!(KeyUsage::parse(&input).unwrap().digital_signature);
+        assert!(KeyUsage::parse_strict(&input).is_err());
+    }
+
+    #[test]
+    fn private_key_usage_period_fields_are_optional() {
+        // SEQUENCE { [1] "20310101000000Z" }
+        let mut input = vec![0x30, 0x11, 0x81, 0x0F];
+        input.extend_from_slice(b"20310101000000Z");
+        let period = PrivateKeyUsagePeriod::parse(&input).unwrap();
+        assert_eq!(period.not_before, None);
+        assert_eq!(
+            period.not_after,
+            Some(UtcTime::from_seconds_since_epoch(1924992000))
+        );
+
+        let period = PrivateKeyUsagePeriod::parse(&[0x30, 0x00]).unwrap();
+        assert_eq!(period.not_before, None);
+        assert_eq!(period.not_after, None);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::x509::Certificate;
+
+use sha2::{Digest, Sha256};
+
+impl<'a> Certificate<'a> {
+    /// SHA-256 of the complete DER encoding, as shown by browsers and `openssl x509 -fingerprint`
+    pub fn fingerprint_sha256(&self) -> [u8; 32] {
+        Sha256::digest(self.raw).into()
+    }
+
+    /// SHA-1 of the complete DER encoding, for matching against legacy thumbprints
+    pub fn fingerprint_sha1(&self) -> [u8; 20] {
+        sha1_smol::Sha1::from(self.raw).digest().bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::der::to_hex;
+    use crate::x509::Certificate;
+
+    #[test]
+    fn fingerprints_the_encoded_certificate() {
+        let cert =
+            Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
+        assert_eq!(
+            to_hex(&cert.fingerprint_sha256()),
+            concat!(
+                "5F:0F:B5:16:65:81:AA:E6:4A:10:1C:15:83:B1:BE:BE:",
+                "74:E8:14:A9:1E:7A:8A:14:BA:1E:83:5D:78:F6:E9:E7"
+            )
+        );
+        assert_eq!(
+            to_hex(&cert.fingerprint_sha1()),
+            "07:1C:B9:4F:0C:C8:51:4D:02:41:24:70:8E:E8:B2:68:7B:D7:D9:D5"
+        );
+    }
+}
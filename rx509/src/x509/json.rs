@@ -0,0 +1,332 @@
+use crate::der::{to_hex_digits, ASNObjectIdentifier};
+use crate::x509::printer::{LinePrinter, Printable};
+use crate::x509::{Ava, AvaValue, Certificate, Name, Version};
+
+use core::fmt::Write;
+
+/// Minimal JSON document model, object members are kept in insertion order
+enum Value {
+    Null,
+    Bool(bool),
+    Number(u64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    fn write(&self, out: &mut String) {
+        match self {
+            Value::Null => out.push_str("null"),
+            Value::Bool(x) => out.push_str(if *x { "true" } else { "false" }),
+            Value::Number(x) => out.push_str(&x.to_string()),
+            Value::String(x) => write_string(x, out),
+            Value::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    item.write(out);
+                }
+                out.push(']');
+            }
+            Value::Object(members) => {
+                out.push('{');
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    write_string(key, out);
+                    out.push(':');
+                    value.write(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+// escaping rules from RFC 8259 section 7
+fn write_string(value: &str, out: &mut String) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// A [`LinePrinter`] that collects the printed output into a JSON document
+///
+/// Each line becomes a string in an array. A line followed by a nested type becomes an object
+/// whose only key is the line, without a trailing ':', and whose value is the array of the
+/// nested lines. A nested type with no line before it becomes a nested array.
+pub struct JsonLinePrinter {
+    current: String,
+    // the enclosing levels, each with the line that introduced it
+    levels: Vec<(Option<String>, Vec<Value>)>,
+    last_was_line: bool,
+}
+
+impl JsonLinePrinter {
+    pub fn new() -> Self {
+        Self {
+            current: String::new(),
+            levels: vec![(None, Vec::new())],
+            last_was_line: false,
+        }
+    }
+
+    /// Finish the document, closing any nested types that were left open
+    pub fn into_json(mut self) -> String {
+        let mut out = String::new();
+        self.finish().write(&mut out);
+        out
+    }
+
+    fn finish(&mut self) -> Value {
+        while self.levels.len() > 1 {
+            self.end_type();
+        }
+        match self.levels.pop() {
+            Some((_, items)) => Value::Array(items),
+            None => Value::Array(Vec::new()),
+        }
+    }
+
+    fn items(&mut self) -> &mut Vec<Value> {
+        // the root level is only removed by finish
+        &mut self.levels.last_mut().expect("root level").1
+    }
+
+    fn end_line(&mut self) {
+        let line = std::mem::take(&mut self.current);
+        self.items().push(Value::String(line));
+        self.last_was_line = true;
+    }
+}
+
+impl Default for JsonLinePrinter {
+    fn default() -> Self {
+        JsonLinePrinter::new()
+    }
+}
+
+impl LinePrinter for JsonLinePrinter {
+    fn begin_type(&mut self) {
+        let header = match self.last_was_line {
+            true => match self.items().pop() {
+                Some(Value::String(line)) => Some(match line.strip_suffix(':') {
+                    Some(key) => key.to_string(),
+                    None => line,
+                }),
+                _ => None,
+            },
+            false => None,
+        };
+        self.levels.push((header, Vec::new()));
+        self.last_was_line = false;
+    }
+
+    fn begin_line(&mut self) {}
+
+    fn print_fmt(&mut self, fmt: &core::fmt::Arguments) {
+        let _ = self.current.write_fmt(*fmt);
+    }
+
+    fn print_str(&mut self, s: &str) {
+        self.current.push_str(s);
+    }
+
+    fn println_fmt(&mut self, fmt: &core::fmt::Arguments) {
+        self.print_fmt(fmt);
+        self.end_line();
+    }
+
+    fn println_str(&mut self, line: &str) {
+        self.print_str(line);
+        self.end_line();
+    }
+
+    fn end_type(&mut self) {
+        // an unbalanced end_type at the root is ignored
+        if self.levels.len() > 1 {
+            if let Some((header, items)) = self.levels.pop() {
+                let value = match header {
+                    Some(key) => Value::Object(vec![(key, Value::Array(items))]),
+                    None => Value::Array(items),
+                };
+                self.items().push(value);
+            }
+        }
+        self.last_was_line = false;
+    }
+}
+
+fn oid(value: &ASNObjectIdentifier) -> Value {
+    Value::String(value.to_dotted_string())
+}
+
+// an array of the attributes of each RDN in encoding order, or null if the name can't be parsed
+fn name(value: &Name) -> Value {
+    match value.parse() {
+        Ok(name) => Value::Array(
+            name.rdns
+                .iter()
+                .map(|rdn| Value::Array(rdn.iter().map(ava).collect()))
+                .collect(),
+        ),
+        Err(_) => Value::Null,
+    }
+}
+
+fn ava(ava: &Ava) -> Value {
+    let attr_type = match ava.short_name() {
+        Some(short_name) => short_name.to_string(),
+        None => ava.attr_type.to_dotted_string(),
+    };
+    // the RFC 4514 form of values that aren't strings
+    let value = match &ava.value {
+        AvaValue::String(value) => value.to_string(),
+        AvaValue::Der(der) => format!("#{}", to_hex_digits(der)),
+    };
+    Value::Object(vec![
+        ("type".to_string(), Value::String(attr_type)),
+        ("value".to_string(), Value::String(value)),
+    ])
+}
+
+impl<'a> Certificate<'a> {
+    /// Render the main fields of the certificate as a JSON document
+    ///
+    /// Object identifiers are in dotted-decimal form and times are ISO 8601 in UTC. Names are
+    /// arrays of RDNs, each an array of its attributes. The value of each extension is the
+    /// output of its printer, see [`JsonLinePrinter`]. The issuer, subject, or extensions are
+    /// null if they can't be parsed.
+    pub fn to_json(&self) -> String {
+        let tbs = &self.tbs_certificate.value;
+
+        let version = match tbs.version {
+            Version::V1 => 1,
+            Version::V2 => 2,
+            Version::V3 => 3,
+        };
+
+        let extensions = match &tbs.extensions {
+            None => Value::Array(Vec::new()),
+            Some(extensions) => match extensions.parse() {
+                Ok(extensions) => Value::Array(
+                    extensions
+                        .iter()
+                        .map(|extension| {
+                            let mut printer = JsonLinePrinter::new();
+                            extension.content.print(&mut printer);
+                            Value::Object(vec![
+                                ("id".to_string(), oid(&extension.extn_id)),
+                                ("critical".to_string(), Value::Bool(extension.critical)),
+                                ("value".to_string(), printer.finish()),
+                            ])
+                        })
+                        .collect(),
+                ),
+                Err(_) => Value::Null,
+            },
+        };
+
+        let document = Value::Object(vec![
+            ("version".to_string(), Value::Number(version)),
+            (
+                "serial".to_string(),
+                Value::String(to_hex_digits(tbs.serial_number.bytes)),
+            ),
+            (
+                "signature_algorithm".to_string(),
+                oid(&tbs.signature.algorithm),
+            ),
+            ("issuer".to_string(), name(&tbs.issuer)),
+            ("subject".to_string(), name(&tbs.subject)),
+            (
+                "validity".to_string(),
+                Value::Object(vec![
+                    (
+                        "not_before".to_string(),
+                        Value::String(tbs.validity.not_before.to_iso8601()),
+                    ),
+                    (
+                        "not_after".to_string(),
+                        Value::String(tbs.validity.not_after.to_iso8601()),
+                    ),
+                ]),
+            ),
+            (
+                "public_key_algorithm".to_string(),
+                oid(&tbs.subject_public_key_info.algorithm.algorithm),
+            ),
+            ("extensions".to_string(), extensions),
+        ]);
+
+        let mut out = String::new();
+        document.write(&mut out);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_rsa_certificate_fields() {
+        let cert =
+            Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
+        let json = cert.to_json();
+
+        assert!(json.starts_with(r#"{"version":1,"serial":"0DFA","#));
+        assert!(json.contains(
+            r#""subject":[[{"type":"C","value":"JP"}],[{"type":"ST","value":"Tokyo"}],[{"type":"O","value":"Frank4DD"}],[{"type":"CN","value":"www.example.com"}]]"#
+        ));
+        assert!(json.contains(
+            r#""validity":{"not_before":"2012-08-22T05:26:54Z","not_after":"2017-08-21T05:26:54Z"}"#
+        ));
+        assert!(json.contains(r#""public_key_algorithm":"1.2.840.113549.1.1.1""#));
+        assert!(json.ends_with(r#""extensions":[]}"#));
+    }
+
+    #[test]
+    fn keeps_the_attributes_of_multi_valued_rdns_together() {
+        let cert =
+            Certificate::parse(include_bytes!("../../../certs/multi_valued_rdn.der")).unwrap();
+
+        assert!(cert.to_json().contains(
+            r#""subject":[[{"type":"C","value":"US"}],[{"type":"O","value":"Step Function"}],[{"type":"OU","value":"A"},{"type":"OU","value":"B"}],[{"type":"CN","value":"C"}]]"#
+        ));
+    }
+
+    #[test]
+    fn nests_printed_types_as_objects() {
+        let mut printer = JsonLinePrinter::new();
+        printer.println_str("version: 3");
+        printer.println_str("issuer:");
+        printer.begin_type();
+        printer.println_str("CN: \"quoted\"\tname");
+        printer.end_type();
+        printer.begin_type();
+        printer.println_str("AB:CD");
+        printer.end_type();
+
+        assert_eq!(
+            printer.into_json(),
+            r#"["version: 3",{"issuer":["CN: \"quoted\"\tname"]},["AB:CD"]]"#
+        );
+    }
+}
@@ -1,34 +1,205 @@
+#[cfg(feature = "verify")]
+pub mod builder;
+pub mod crl;
+pub mod csr;
+mod encode;
 pub mod ext;
+#[cfg(feature = "fingerprint")]
+mod fingerprint;
+#[cfg(feature = "display")]
+pub mod json;
+#[cfg(feature = "display")]
 pub mod printer;
+pub mod tls;
+#[cfg(feature = "verify")]
+pub mod verify;
 
 #[cfg(test)]
 mod tests;
 
+use std::borrow::Cow;
+
 use crate::der::parser::Parser;
 use crate::der::*;
-use crate::x509::ext::Extensions;
+use crate::pem::{self, PemError};
+use crate::x509::ext::{
+    ExtendedKeyUsagePurpose, Extension, Extensions, GeneralName, SpecificExtension,
+};
+#[cfg(feature = "display")]
 use crate::x509::printer::{print_type, LinePrinter, Printable};
+use crate::x509::tls::TlsServerCheck;
+
+/// Parse each certificate of a bundle of concatenated DER certificates
+///
+/// An entry that fails to parse, including one that isn't a SEQUENCE, is recorded with its index
+/// and scanning continues with the next one. If the outer framing of an entry can't be read, the
+/// remainder of the bundle can't be located, so the error is recorded and scanning stops.
+pub fn scan(bundle_der: &[u8]) -> Vec<(usize, Result<Certificate<'_>, ASNError>)> {
+    let mut results = Vec::new();
+    let mut parser = Parser::new(bundle_der);
+    while !parser.is_empty() {
+        let index = results.len();
+        match parser.expect_any_with_der() {
+            Ok((_, der)) => {
+                let result = Certificate::parse_der(Parser::new(der))
+                    .map_err(|err| err.relative_to(bundle_der));
+                results.push((index, result));
+            }
+            Err(err) => {
+                results.push((index, Err(err.relative_to(bundle_der))));
+                break;
+            }
+        }
+    }
+    results
+}
+
+/// Parse every certificate of a chain encoded as a DER SEQUENCE OF Certificate
+///
+/// Fails with the error of the first certificate that can't be parsed.
+pub fn parse_chain(input: &[u8]) -> Result<Vec<Certificate<'_>>, ASNError> {
+    parse_chain_der(input).map_err(|err| err.relative_to(input))
+}
+
+fn parse_chain_der(input: &[u8]) -> Result<Vec<Certificate<'_>>, ASNError> {
+    let mut parser = Parser::unwrap_outer_sequence(input)?;
+    let mut chain = Vec::new();
+    // expect_or_end would mistake a truncated final certificate for the end of the chain
+    while !parser.is_empty() {
+        let (contents, der) = parser.expect_with_der::<Sequence>()?;
+        chain.push(Certificate::parse_contents(&parser, contents, der)?);
+    }
+    Ok(chain)
+}
+
+/// Errors that can occur while reading a certificate from a file
+#[derive(Debug)]
+pub enum CertReadError {
+    /// the file couldn't be read
+    Io(std::io::Error),
+    /// the file starts like PEM but holds no decodable CERTIFICATE block
+    Pem(PemError),
+    /// the DER encoding failed to parse
+    Der(ASNError),
+}
+
+impl core::fmt::Display for CertReadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            CertReadError::Io(err) => write!(f, "read failed: {}", err),
+            CertReadError::Pem(err) => write!(f, "{}", err),
+            CertReadError::Der(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for CertReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CertReadError::Io(err) => Some(err),
+            CertReadError::Pem(err) => Some(err),
+            CertReadError::Der(err) => Some(err),
+        }
+    }
+}
+
+impl From<std::io::Error> for CertReadError {
+    fn from(err: std::io::Error) -> Self {
+        CertReadError::Io(err)
+    }
+}
+
+impl From<PemError> for CertReadError {
+    fn from(err: PemError) -> Self {
+        CertReadError::Pem(err)
+    }
+}
+
+impl From<ASNError> for CertReadError {
+    fn from(err: ASNError) -> Self {
+        CertReadError::Der(err)
+    }
+}
 
 #[derive(Debug)]
 pub struct Constructed<'a, T> {
+    /// complete DER encoding (tag, length, and contents) of the value, as covered by a signature
     pub bytes: &'a [u8],
     pub value: T,
 }
 
 impl<'a, T> Constructed<'a, T> {
-    pub fn new(bytes: &'a [u8], value: T) -> Constructed<T> {
+    pub fn new(bytes: &'a [u8], value: T) -> Constructed<'a, T> {
         Constructed { bytes, value }
     }
 }
 
 #[derive(Debug)]
 pub struct Certificate<'a> {
+    /// complete encoding of the certificate exactly as parsed, from the identifier of the outer
+    /// SEQUENCE through its last content byte, e.g. to forward the certificate unchanged
+    pub raw: &'a [u8],
     // preserve raw bytes for signature validation using Constructed<T>
     pub tbs_certificate: Constructed<'a, TBSCertificate<'a>>,
     pub signature_algorithm: AlgorithmIdentifier<'a>,
     pub signature_value: ASNBitString<'a>,
 }
 
+/// The (r, s) pair of an ECDSA-Sig-Value or of a DSA Dss-Sig-Value, which share a shape (RFC 3279)
+#[derive(Debug, PartialEq, Eq)]
+pub struct EcdsaSignature<'a> {
+    pub r: ASNInteger<'a>,
+    pub s: ASNInteger<'a>,
+}
+
+impl<'a> EcdsaSignature<'a> {
+    /// Parse the DER SEQUENCE of two INTEGERs held in the signature BIT STRING
+    pub fn parse(input: &[u8]) -> Result<EcdsaSignature<'_>, ASNError> {
+        EcdsaSignature::parse_der(input).map_err(|err| err.relative_to(input))
+    }
+
+    fn parse_der(input: &[u8]) -> Result<EcdsaSignature<'_>, ASNError> {
+        let mut parser = Parser::unwrap_outer_sequence(input)?;
+        let r = parser.expect::<Integer>()?;
+        let s = parser.expect::<Integer>()?;
+        parser.expect_end()?;
+        Ok(EcdsaSignature { r, s })
+    }
+}
+
+/// The RSAPublicKey held in the subject public key BIT STRING of an rsaEncryption key (RFC 8017)
+///
+/// Both INTEGERs are positive, so the modulus usually starts with a 0x00 sign byte.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RsaPublicKey<'a> {
+    pub modulus: ASNInteger<'a>,
+    pub exponent: ASNInteger<'a>,
+}
+
+impl<'a> RsaPublicKey<'a> {
+    /// Parse the DER SEQUENCE of the modulus and the public exponent
+    pub fn parse(input: &[u8]) -> Result<RsaPublicKey<'_>, ASNError> {
+        RsaPublicKey::parse_der(input).map_err(|err| err.relative_to(input))
+    }
+
+    fn parse_der(input: &[u8]) -> Result<RsaPublicKey<'_>, ASNError> {
+        let mut parser = Parser::unwrap_outer_sequence(input)?;
+        let modulus = parser.expect::<Integer>()?;
+        let exponent = parser.expect::<Integer>()?;
+        parser.expect_end()?;
+        Ok(RsaPublicKey { modulus, exponent })
+    }
+}
+
+/// The named curve and the uncompressed point of an EC public key (RFC 5480)
+#[derive(Debug, PartialEq, Eq)]
+pub struct EcPublicKey<'a> {
+    pub curve: ASNObjectIdentifier<'a>,
+    /// SEC 1 encoding, the 0x04 marker followed by the X and Y coordinates
+    pub point: &'a [u8],
+}
+
+#[cfg(feature = "display")]
 impl<'a> Printable for Certificate<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
         print_type("tbs certificate", &self.tbs_certificate.value, printer);
@@ -37,20 +208,17 @@ impl<'a> Printable for Certificate<'a> {
     }
 }
 
-impl<'a> Printable for &'a [u8] {
+#[cfg(feature = "display")]
+impl Printable for &[u8] {
     fn print(&self, printer: &mut dyn LinePrinter) {
         for chunk in self.chunks(16) {
             printer.begin_line();
-            if let Some((last, first)) = chunk.split_last() {
-                for byte in first {
-                    printer.print_fmt(&format_args!("{:02X}:", byte));
-                }
-                printer.println_fmt(&format_args!("{:02X}", last));
-            }
+            printer.println_str(&to_hex(chunk));
         }
     }
 }
 
+#[cfg(feature = "display")]
 impl<'a> Printable for ASNBitString<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
         if let Some(octets) = self.octets() {
@@ -61,17 +229,159 @@ impl<'a> Printable for ASNBitString<'a> {
 
 #[derive(Debug)]
 pub struct AlgorithmIdentifier<'a> {
-    pub algorithm: ASNObjectIdentifier,
+    pub algorithm: ASNObjectIdentifier<'a>,
     pub parameters: Option<ASNType<'a>>,
 }
 
+const RSASSA_PSS: [u128; 7] = [1, 2, 840, 113549, 1, 1, 10];
+const MGF1: [u128; 7] = [1, 2, 840, 113549, 1, 1, 8];
+const SHA1: [u128; 6] = [1, 3, 14, 3, 2, 26];
+// contents of sha1Identifier, SEQUENCE { id-sha1, NULL }
+const SHA1_IDENTIFIER: &[u8] = &[0x06, 0x05, 0x2B, 0x0E, 0x03, 0x02, 0x1A, 0x05, 0x00];
+
+/// RSASSA-PSS-params from RFC 4055, with the defaults filled in for absent fields
+#[derive(Debug)]
+pub struct RsaPssParams<'a> {
+    /// defaults to SHA-1
+    pub hash_algorithm: AlgorithmIdentifier<'a>,
+    /// defaults to MGF1 with SHA-1
+    pub mask_gen_algorithm: AlgorithmIdentifier<'a>,
+    /// defaults to 20
+    pub salt_length: u32,
+    /// defaults to 1, the only value RFC 4055 allows
+    pub trailer_field: u32,
+}
+
+impl<'a> RsaPssParams<'a> {
+    fn parse(input: &'a [u8]) -> Result<RsaPssParams<'a>, ASNError> {
+        Parser::parse_all(input, |parser| {
+            let hash_algorithm =
+                match parser.get_optional_explicit(0, |parser| parser.expect::<Sequence>())? {
+                    Some(contents) => AlgorithmIdentifier::parse(contents)?,
+                    None => AlgorithmIdentifier::new(
+                        ASNObjectIdentifier::from_slice(&SHA1),
+                        Some(ASNType::Null),
+                    ),
+                };
+            let mask_gen_algorithm =
+                match parser.get_optional_explicit(1, |parser| parser.expect::<Sequence>())? {
+                    Some(contents) => AlgorithmIdentifier::parse(contents)?,
+                    None => AlgorithmIdentifier::new(
+                        ASNObjectIdentifier::from_slice(&MGF1),
+                        Some(Sequence::asn(SHA1_IDENTIFIER)),
+                    ),
+                };
+            let salt_length =
+                match parser.get_optional_explicit(2, |parser| parser.expect::<Integer>())? {
+                    Some(value) => non_negative("saltLength", value)?,
+                    None => 20,
+                };
+            let trailer_field =
+                match parser.get_optional_explicit(3, |parser| parser.expect::<Integer>())? {
+                    Some(value) => non_negative("trailerField", value)?,
+                    None => 1,
+                };
+            Ok(RsaPssParams {
+                hash_algorithm,
+                mask_gen_algorithm,
+                salt_length,
+                trailer_field,
+            })
+        })
+    }
+
+    /// The hash used by MGF1, or None if the mask generation function is not MGF1
+    pub fn mgf1_hash_algorithm(&self) -> Result<Option<AlgorithmIdentifier<'a>>, ASNError> {
+        if self.mask_gen_algorithm.algorithm.values() != MGF1 {
+            return Ok(None);
+        }
+        match &self.mask_gen_algorithm.parameters {
+            Some(ASNType::Sequence(wrapper)) => AlgorithmIdentifier::parse(wrapper.value).map(Some),
+            Some(other) => {
+                Err(ASNErrorVariant::UnexpectedType(ASNTypeId::Sequence, other.get_id()).into())
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+fn non_negative(name: &'static str, value: ASNInteger) -> Result<u32, ASNError> {
+    match value.as_i32() {
+        Some(x) if x < 0 => Err(ASNErrorVariant::NegativeValue(name, x).into()),
+        Some(x) => Ok(x as u32),
+        None => Err(ASNErrorVariant::IntegerTooLarge(value.bytes.len()).into()),
+    }
+}
+
+#[cfg(feature = "display")]
 impl<'a> Printable for AlgorithmIdentifier<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
         printer.begin_line();
         printer.println_fmt(&format_args!("algorithm: {}", self.algorithm));
+        if let Ok(Some(params)) = self.rsa_pss_params() {
+            print_type("parameters", &params, printer);
+            return;
+        }
+        printer.begin_line();
+        match &self.parameters {
+            None => printer.println_str("parameters: (absent)"),
+            Some(ASNType::Null) => printer.println_str("parameters: NULL"),
+            Some(ASNType::ObjectIdentifier(wrapper)) => {
+                printer.println_fmt(&format_args!("parameters: {}", wrapper.value))
+            }
+            Some(other) => printer.println_fmt(&format_args!("parameters: {}", other)),
+        }
+    }
+}
+
+#[cfg(feature = "display")]
+impl<'a> Printable for RsaPssParams<'a> {
+    fn print(&self, printer: &mut dyn LinePrinter) {
+        printer.begin_line();
+        printer.println_fmt(&format_args!(
+            "hash algorithm: {}",
+            self.hash_algorithm.algorithm
+        ));
+        printer.begin_line();
+        match self.mgf1_hash_algorithm() {
+            Ok(Some(hash)) => printer.println_fmt(&format_args!(
+                "mask generation: MGF1 with {}",
+                hash.algorithm
+            )),
+            _ => printer.println_fmt(&format_args!(
+                "mask generation: {}",
+                self.mask_gen_algorithm.algorithm
+            )),
+        }
+        printer.begin_line();
+        printer.println_fmt(&format_args!("salt length: {}", self.salt_length));
+        printer.begin_line();
+        printer.println_fmt(&format_args!("trailer field: {}", self.trailer_field));
     }
 }
 
+/// Algorithm of a subject public key
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KeyAlgorithm {
+    Rsa,
+    Ec,
+    Ed25519,
+    Ed448,
+    X25519,
+    X448,
+}
+
+/// Hash function used by a signature algorithm
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+    /// the signature scheme hashes the message internally (e.g. Ed25519)
+    None,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Version {
     V1,
@@ -93,6 +403,7 @@ pub struct TBSCertificate<'a> {
     pub extensions: Option<Extensions<'a>>,
 }
 
+#[cfg(feature = "display")]
 impl<'a> Printable for TBSCertificate<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
         printer.begin_line();
@@ -151,25 +462,60 @@ impl<'a> Printable for TBSCertificate<'a> {
     }
 }
 
+// Time ::= CHOICE { utcTime UTCTime, generalTime GeneralizedTime }
+fn parse_time(parser: &mut Parser) -> Result<UtcTime, ASNError> {
+    Ok(parse_time_with_kind(parser)?.0)
+}
+
+// like parse_time, also returning whether the Time was a UTCTime or a GeneralizedTime
+fn parse_time_with_kind(parser: &mut Parser) -> Result<(UtcTime, ASNTypeId), ASNError> {
+    match parser.expect_any_with_der()? {
+        (ASNType::UTCTime(time), _) => Ok((time, ASNTypeId::UTCTime)),
+        (ASNType::GeneralizedTime(time), _) => Ok((time, ASNTypeId::GeneralizedTime)),
+        (other, der) => Err(ASNError::at(
+            ASNErrorVariant::UnexpectedType(ASNTypeId::UTCTime, other.get_id()),
+            der,
+        )),
+    }
+}
+
+fn parse_optional_time(parser: &mut Parser) -> Result<Option<UtcTime>, ASNError> {
+    match parser.get_optional::<UtcTime>()? {
+        Some(time) => Ok(Some(time)),
+        None => parser.get_optional::<GeneralizedTime>(),
+    }
+}
+
 #[derive(Debug)]
 pub struct Validity {
     pub not_before: UtcTime,
     pub not_after: UtcTime,
+    /// how not_before was encoded, either UTCTime or GeneralizedTime
+    pub not_before_kind: ASNTypeId,
+    /// how not_after was encoded, either UTCTime or GeneralizedTime
+    pub not_after_kind: ASNTypeId,
 }
 
 impl Validity {
-    fn new(not_before: UtcTime, not_after: UtcTime) -> Validity {
+    fn new(
+        (not_before, not_before_kind): (UtcTime, ASNTypeId),
+        (not_after, not_after_kind): (UtcTime, ASNTypeId),
+    ) -> Validity {
         Validity {
             not_before,
             not_after,
+            not_before_kind,
+            not_after_kind,
         }
     }
 
-    fn parse(input: &[u8]) -> Result<Validity, ASNErrorVariant> {
-        Parser::parse_all(input, |parser| {
+    fn parse_from(parser: &mut Parser) -> Result<Validity, ASNError> {
+        // RFC 5280 allows each bound to independently be a UTCTime or a GeneralizedTime
+        let contents = parser.expect::<Sequence>()?;
+        parser.parse_nested(contents, |parser| {
             Ok(Validity::new(
-                parser.expect::<UtcTime>()?,
-                parser.expect::<UtcTime>()?,
+                parse_time_with_kind(parser)?,
+                parse_time_with_kind(parser)?,
             ))
         })
     }
@@ -177,25 +523,176 @@ impl Validity {
     pub fn is_valid(&self, now: UtcTime) -> bool {
         now >= self.not_before && now <= self.not_after
     }
+
+    /// Where `now` falls relative to the validity window, whose bounds are inclusive
+    pub fn status(&self, now: UtcTime) -> ValidityStatus {
+        if now < self.not_before {
+            ValidityStatus::NotYetValid
+        } else if now > self.not_after {
+            ValidityStatus::Expired
+        } else {
+            ValidityStatus::Valid
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ValidityStatus {
+    /// the time is before notBefore
+    NotYetValid,
+    Valid,
+    /// the time is after notAfter
+    Expired,
 }
 
+#[cfg(feature = "display")]
 impl Printable for Validity {
     fn print(&self, printer: &mut dyn LinePrinter) {
         printer.begin_line();
-        printer.println_fmt(&format_args!("not before: {}", self.not_before.value));
+        printer.println_fmt(&format_args!("not before: {}", self.not_before));
 
         printer.begin_line();
-        printer.println_fmt(&format_args!("not after: {}", self.not_after.value));
+        printer.println_fmt(&format_args!("not after: {}", self.not_after));
+    }
+}
+
+/// The value of an AVA
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AvaValue<'a> {
+    /// value of one of the string types, borrowed from the input unless it was decoded from a
+    /// BMPString or TeletexString
+    String(Cow<'a, str>),
+    /// complete DER encoding of a value of any other type, e.g. a UniversalString
+    Der(&'a [u8]),
+}
+
+impl<'a> AvaValue<'a> {
+    /// The value if it is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AvaValue::String(value) => Some(value),
+            AvaValue::Der(_) => None,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for AvaValue<'a> {
+    fn from(value: &'a str) -> Self {
+        AvaValue::String(value.into())
+    }
+}
+
+impl<'a> From<String> for AvaValue<'a> {
+    fn from(value: String) -> Self {
+        AvaValue::String(value.into())
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for AvaValue<'a> {
+    fn from(value: Cow<'a, str>) -> Self {
+        AvaValue::String(value)
+    }
+}
+
+/// A single attribute type and value assertion within a RelativeDistinguishedName SET
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ava<'a> {
+    pub attr_type: ASNObjectIdentifier<'a>,
+    pub value: AvaValue<'a>,
+}
+
+impl<'a> Ava<'a> {
+    pub fn new<T: Into<AvaValue<'a>>>(attr_type: ASNObjectIdentifier<'a>, value: T) -> Self {
+        Self {
+            attr_type,
+            value: value.into(),
+        }
+    }
+
+    /// Compare string values with LDAP caseIgnoreMatch: leading and trailing whitespace is
+    /// ignored, internal runs of whitespace are treated as a single space, and ASCII case is
+    /// folded. Values that aren't strings match if their encodings are identical.
+    pub fn matches(&self, other: &Ava) -> bool {
+        fn normalized(value: &str) -> impl Iterator<Item = char> + '_ {
+            value
+                .split_whitespace()
+                .enumerate()
+                .flat_map(|(i, word)| (i != 0).then_some(' ').into_iter().chain(word.chars()))
+                .map(|c| c.to_ascii_lowercase())
+        }
+
+        self.attr_type == other.attr_type
+            && match (&self.value, &other.value) {
+                (AvaValue::String(a), AvaValue::String(b)) => normalized(a).eq(normalized(b)),
+                (AvaValue::Der(a), AvaValue::Der(b)) => a == b,
+                _ => false,
+            }
+    }
+
+    // the RFC 4514 short name of the attribute type, if it has one
+    fn short_name(&self) -> Option<&'static str> {
+        match self.attr_type.values() {
+            [2, 5, 4, 3] => Some("CN"),
+            [2, 5, 4, 6] => Some("C"),
+            [2, 5, 4, 7] => Some("L"),
+            [2, 5, 4, 8] => Some("ST"),
+            [2, 5, 4, 9] => Some("STREET"),
+            [2, 5, 4, 10] => Some("O"),
+            [2, 5, 4, 11] => Some("OU"),
+            [0, 9, 2342, 19_200_300, 100, 1, 1] => Some("UID"),
+            [0, 9, 2342, 19_200_300, 100, 1, 25] => Some("DC"),
+            _ => None,
+        }
+    }
+
+    fn write_rfc4514(&self, out: &mut String) {
+        match self.short_name() {
+            Some(name) => out.push_str(name),
+            // types without a short name are rendered in dotted-decimal form
+            None => out.push_str(&self.attr_type.to_dotted_string()),
+        }
+        out.push('=');
+
+        let value = match &self.value {
+            AvaValue::String(value) => value,
+            // RFC 4514 section 2.4 renders values that aren't strings as '#' followed by the
+            // hex digits of their encoding
+            AvaValue::Der(der) => {
+                out.push('#');
+                out.push_str(&to_hex_digits(der));
+                return;
+            }
+        };
+
+        // escaping rules from RFC 4514 section 2.4
+        let last = value.chars().count().saturating_sub(1);
+        for (i, c) in value.chars().enumerate() {
+            match c {
+                '"' | '+' | ',' | ';' | '<' | '>' | '\\' => {
+                    out.push('\\');
+                    out.push(c);
+                }
+                '#' if i == 0 => out.push_str("\\#"),
+                ' ' if i == 0 || i == last => out.push_str("\\ "),
+                '\0' => out.push_str("\\00"),
+                _ => out.push(c),
+            }
+        }
     }
 }
 
 pub struct RelativeDistinguishedName<'a> {
-    pub country_name: Option<&'a str>,
-    pub state_or_province_unit_name: Option<&'a str>,
-    pub locality_name: Option<&'a str>,
-    pub organization: Option<&'a str>,
-    pub organizational_unit_name: Option<&'a str>,
-    pub common_name: Option<&'a str>,
+    pub country_name: Option<Cow<'a, str>>,
+    pub state_or_province_unit_name: Option<Cow<'a, str>>,
+    pub locality_name: Option<Cow<'a, str>>,
+    pub organization: Option<Cow<'a, str>>,
+    pub organizational_unit_name: Option<Cow<'a, str>>,
+    pub common_name: Option<Cow<'a, str>>,
+    pub email_address: Option<Cow<'a, str>>,
+    /// every domainComponent in DN order, e.g. `["com", "example"]` for `DC=example,DC=com`
+    pub domain_components: Vec<Cow<'a, str>>,
+    /// every RDN of the sequence in encoding order, each holding the AVAs of its SET in encoding order
+    pub rdns: Vec<Vec<Ava<'a>>>,
 }
 
 impl<'a> RelativeDistinguishedName<'a> {
@@ -207,122 +704,264 @@ impl<'a> RelativeDistinguishedName<'a> {
             organization: None,
             organizational_unit_name: None,
             common_name: None,
+            email_address: None,
+            domain_components: Vec::new(),
+            rdns: Vec::new(),
         }
     }
 
-    fn parse(input: &'a [u8]) -> Result<Self, ASNErrorVariant> {
+    fn parse(input: &'a [u8], options: ParseOptions) -> Result<Self, ASNError> {
         let mut result = Self::empty();
-        let mut parser = Parser::new(input);
+        let mut parser = Parser::with_options(input, options);
 
         // Iterate on the RDNSequence (the only choice of Name)
-        while let Some(set) = parser.expect_or_end::<Set>()? {
-            let mut parser = Parser::new(set);
+        while !parser.is_empty() {
+            let mut parser = parser.expect_set_contents()?;
+            let mut rdn: Vec<Ava> = Vec::new();
 
             // Parse the RelativeDistinguishedName
             // expect at least one entry!
-            result.parse_single(parser.expect::<Sequence>()?)?;
+            let seq = parser.expect::<Sequence>()?;
+            result.parse_single(&parser, seq, &mut rdn)?;
             while let Some(seq) = parser.expect_or_end::<Sequence>()? {
-                result.parse_single(seq)?;
+                result.parse_single(&parser, seq, &mut rdn)?;
             }
+
+            result.rdns.push(rdn);
         }
 
         Ok(result)
     }
 
-    fn parse_single(&mut self, input: &'a [u8]) -> Result<(), ASNErrorVariant> {
-        fn fill_name_component<'b>(
-            value: &ASNType<'b>,
-            component: &mut Option<&'b str>,
-            oid: &ASNObjectIdentifier,
-        ) -> Result<(), ASNErrorVariant> {
-            let str_value = match &value {
-                ASNType::IA5String(value) => value.value,
-                ASNType::PrintableString(value) => value.value,
-                ASNType::UTF8String(value) => value.value,
-                _ => {
-                    return Err(ASNErrorVariant::UnexpectedType(
-                        ASNTypeId::PrintableString,
-                        value.get_id(),
-                    ))
-                }
-            };
+    /// Every string value of the given attribute type, in DN order
+    ///
+    /// Unlike the convenience fields, this returns all instances of the type, e.g. each OU
+    /// of a name with several organizational units. Values that aren't strings are only
+    /// available in `rdns`.
+    pub fn values_of<'b>(&'b self, attr_type: &'b [u128]) -> impl Iterator<Item = &'b str> + 'b {
+        self.rdns
+            .iter()
+            .flatten()
+            .filter(move |ava| ava.attr_type.values() == attr_type)
+            .filter_map(|ava| ava.value.as_str())
+    }
+
+    /// Compare two names per RFC 5280 7.1: the same number of RDNs, each holding the same
+    /// set of AVAs in any order, with values compared using [`Ava::matches`]
+    pub fn matches(&self, other: &RelativeDistinguishedName) -> bool {
+        // each AVA pairs up with a different one, matching is an equivalence so the first
+        // unused match will do
+        fn same_set(a: &[Ava], b: &[Ava]) -> bool {
+            let mut used = vec![false; b.len()];
+            a.len() == b.len()
+                && a.iter().all(
+                    |x| match (0..b.len()).find(|i| !used[*i] && x.matches(&b[*i])) {
+                        Some(i) => {
+                            used[i] = true;
+                            true
+                        }
+                        None => false,
+                    },
+                )
+        }
+
+        self.rdns.len() == other.rdns.len()
+            && self
+                .rdns
+                .iter()
+                .zip(&other.rdns)
+                .all(|(a, b)| same_set(a, b))
+    }
 
-            // We only accept a single instance of each AVA type
-            match component {
-                Some(_) => Err(ASNErrorVariant::UnexpectedOid(oid.clone())),
-                None => {
-                    *component = Some(str_value);
-                    Ok(())
+    /// Render the name as a string according to RFC 4514, i.e. starting with the last RDN
+    /// of the sequence and joining the AVAs of a multi-valued RDN with '+'
+    pub fn to_rfc4514_string(&self) -> String {
+        let mut out = String::new();
+        for (i, rdn) in self.rdns.iter().rev().enumerate() {
+            if i != 0 {
+                out.push(',');
+            }
+            for (j, ava) in rdn.iter().enumerate() {
+                if j != 0 {
+                    out.push('+');
                 }
+                ava.write_rfc4514(&mut out);
+            }
+        }
+        out
+    }
+
+    fn parse_single(
+        &mut self,
+        set: &Parser<'a>,
+        input: &'a [u8],
+        rdn: &mut Vec<Ava<'a>>,
+    ) -> Result<(), ASNError> {
+        fn get_str(value: ASNType<'_>) -> Option<Cow<'_, str>> {
+            match value {
+                ASNType::IA5String(value) => Some(value.value.into()),
+                ASNType::PrintableString(value) => Some(value.value.into()),
+                ASNType::NumericString(value) => Some(value.value.into()),
+                ASNType::VisibleString(value) => Some(value.value.into()),
+                ASNType::UTF8String(value) => Some(value.value.into()),
+                ASNType::BMPString(value) => Some(value.value.into()),
+                ASNType::TeletexString(value) => Some(value.value.into()),
+                _ => None,
             }
         }
 
-        Parser::parse_all(input, |parser| {
+        fn expect_str(value: ASNType<'_>) -> Result<Cow<'_, str>, ASNError> {
+            let id = value.get_id();
+            get_str(value).ok_or_else(|| {
+                ASNErrorVariant::UnexpectedType(ASNTypeId::PrintableString, id).into()
+            })
+        }
+
+        fn fill_name_component<'b>(
+            value: ASNType<'b>,
+            component: &mut Option<Cow<'b, str>>,
+        ) -> Result<Cow<'b, str>, ASNError> {
+            let str_value = expect_str(value)?;
+
+            // the convenience fields hold the first instance of each AVA type,
+            // all of the instances are available in the RDN sequence
+            if component.is_none() {
+                *component = Some(str_value.clone());
+            }
+
+            Ok(str_value)
+        }
+
+        set.parse_nested(input, |parser| {
             let oid = parser.expect::<ObjectIdentifier>()?;
-            let value = parser.expect_any()?;
+            // values of types we can't decode, e.g. UniversalString, are kept as their encoding
+            if let Some(Err(_)) = parser.peek_id() {
+                let der = parser.remaining();
+                parser.skip()?;
+                let der = &der[..der.len() - parser.remaining().len()];
+                rdn.push(Ava::new(oid, AvaValue::Der(der)));
+                return Ok(());
+            }
+            let (value, der) = parser.expect_any_with_der()?;
 
-            match oid.values() {
-                [2, 5, 4, 3] => fill_name_component(&value, &mut self.common_name, &oid),
-                [2, 5, 4, 6] => fill_name_component(&value, &mut self.country_name, &oid),
-                [2, 5, 4, 7] => fill_name_component(&value, &mut self.locality_name, &oid),
+            let value = match oid.values() {
+                [2, 5, 4, 3] => fill_name_component(value, &mut self.common_name)?.into(),
+                [2, 5, 4, 6] => fill_name_component(value, &mut self.country_name)?.into(),
+                [2, 5, 4, 7] => fill_name_component(value, &mut self.locality_name)?.into(),
                 [2, 5, 4, 8] => {
-                    fill_name_component(&value, &mut self.state_or_province_unit_name, &oid)
+                    fill_name_component(value, &mut self.state_or_province_unit_name)?.into()
                 }
-                [2, 5, 4, 10] => fill_name_component(&value, &mut self.organization, &oid),
+                [2, 5, 4, 10] => fill_name_component(value, &mut self.organization)?.into(),
                 [2, 5, 4, 11] => {
-                    fill_name_component(&value, &mut self.organizational_unit_name, &oid)
+                    fill_name_component(value, &mut self.organizational_unit_name)?.into()
                 }
-                _ => Ok(()), // ignore the AVAs we don't recognize
-            }
+                [1, 2, 840, 113_549, 1, 9, 1] => {
+                    fill_name_component(value, &mut self.email_address)?.into()
+                }
+                [0, 9, 2342, 19_200_300, 100, 1, 25] => {
+                    let str_value = expect_str(value)?;
+                    self.domain_components.push(str_value.clone());
+                    str_value.into()
+                }
+                _ => match get_str(value) {
+                    Some(x) => x.into(),
+                    None => AvaValue::Der(der),
+                },
+            };
+
+            rdn.push(Ava::new(oid, value));
+            Ok(())
         })
     }
 }
 
+#[cfg(feature = "display")]
 impl<'a> Printable for RelativeDistinguishedName<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
-        if let Some(value) = self.country_name {
+        if let Some(value) = &self.country_name {
             printer.begin_line();
             printer.println_fmt(&format_args!("C: {}", value));
         }
-        if let Some(value) = self.state_or_province_unit_name {
+        if let Some(value) = &self.state_or_province_unit_name {
             printer.begin_line();
             printer.println_fmt(&format_args!("ST: {}", value));
         }
-        if let Some(value) = self.locality_name {
+        if let Some(value) = &self.locality_name {
             printer.begin_line();
             printer.println_fmt(&format_args!("L: {}", value));
         }
-        if let Some(value) = self.organization {
+        if let Some(value) = &self.organization {
             printer.begin_line();
             printer.println_fmt(&format_args!("O: {}", value));
         }
-        if let Some(value) = self.organizational_unit_name {
+        if let Some(value) = &self.organizational_unit_name {
             printer.begin_line();
             printer.println_fmt(&format_args!("OU: {}", value));
         }
-        if let Some(value) = self.common_name {
+        if let Some(value) = &self.common_name {
             printer.begin_line();
             printer.println_fmt(&format_args!("CN: {}", value));
         }
+        if let Some(value) = &self.email_address {
+            printer.begin_line();
+            printer.println_fmt(&format_args!("E: {}", value));
+        }
+        for value in &self.domain_components {
+            printer.begin_line();
+            printer.println_fmt(&format_args!("DC: {}", value));
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Name<'a> {
+    /// contents of the RDNSequence, i.e. without the outer SEQUENCE tag and length
     pub inner: &'a [u8],
+    der: &'a [u8],
 }
 
 impl<'a> Name<'a> {
-    fn new(input: &'a [u8]) -> Self {
-        Self { inner: input }
+    fn new(input: &'a [u8], der: &'a [u8]) -> Self {
+        Self { inner: input, der }
+    }
+
+    fn parse_from(parser: &mut Parser<'a>) -> Result<Self, ASNError> {
+        let (inner, der) = parser.expect_with_der::<Sequence>()?;
+        Ok(Self::new(inner, der))
+    }
+
+    /// Complete DER encoding of the Name, including the outer SEQUENCE tag and length.
+    ///
+    /// This is the value hashed to produce the issuerNameHash of an OCSP CertID.
+    pub fn der(&self) -> &'a [u8] {
+        self.der
     }
 
-    pub fn parse(&self) -> Result<RelativeDistinguishedName, ASNError> {
-        let name = RelativeDistinguishedName::parse(self.inner)?;
-        Ok(name)
+    /// Parse the RDNs, accepting PrintableString values that use characters outside
+    /// its restricted set as many deployed CAs emit them
+    pub fn parse(&self) -> Result<RelativeDistinguishedName<'_>, ASNError> {
+        self.parse_with_options(ParseOptions::default())
+    }
+
+    /// Parse the RDNs, failing if a PrintableString value contains a character outside
+    /// A-Z, a-z, 0-9, space and '()+,-./:=?
+    pub fn parse_strict(&self) -> Result<RelativeDistinguishedName<'_>, ASNError> {
+        self.parse_with_options(ParseOptions {
+            printable_character_set: true,
+            ..ParseOptions::default()
+        })
+    }
+
+    /// Parse the RDNs, applying the checks selected by `options` to every value
+    pub fn parse_with_options(
+        &self,
+        options: ParseOptions,
+    ) -> Result<RelativeDistinguishedName<'_>, ASNError> {
+        RelativeDistinguishedName::parse(self.inner, options)
     }
 }
 
+#[cfg(feature = "display")]
 impl<'a> Printable for Name<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
         printer.begin_type();
@@ -338,7 +977,7 @@ pub struct SubjectPublicKeyInfo<'a> {
 }
 
 impl<'a> SubjectPublicKeyInfo<'a> {
-    fn new(
+    pub fn new(
         algorithm: AlgorithmIdentifier<'a>,
         subject_public_key: ASNBitString<'a>,
     ) -> SubjectPublicKeyInfo<'a> {
@@ -348,16 +987,91 @@ impl<'a> SubjectPublicKeyInfo<'a> {
         }
     }
 
-    fn parse(input: &[u8]) -> Result<SubjectPublicKeyInfo, ASNErrorVariant> {
-        Parser::parse_all(input, |parser| {
+    /// Algorithm of the key, or None if the algorithm is not recognized
+    pub fn key_algorithm(&self) -> Option<KeyAlgorithm> {
+        match self.algorithm.algorithm.values() {
+            [1, 2, 840, 113549, 1, 1, 1] => Some(KeyAlgorithm::Rsa),
+            [1, 2, 840, 10045, 2, 1] => Some(KeyAlgorithm::Ec),
+            [1, 3, 101, 110] => Some(KeyAlgorithm::X25519),
+            [1, 3, 101, 111] => Some(KeyAlgorithm::X448),
+            [1, 3, 101, 112] => Some(KeyAlgorithm::Ed25519),
+            [1, 3, 101, 113] => Some(KeyAlgorithm::Ed448),
+            _ => None,
+        }
+    }
+
+    /// The 57 byte public key if this is an Ed448 key (RFC 8410)
+    pub fn ed448_public_key(&self) -> Option<&'a [u8]> {
+        if self.key_algorithm() != Some(KeyAlgorithm::Ed448) {
+            return None;
+        }
+        self.eddsa_public_key()
+    }
+
+    /// The 32 byte Ed25519 or 57 byte Ed448 public key
+    ///
+    /// None for any other algorithm, or if the algorithm has parameters, which RFC 8410 forbids.
+    pub fn eddsa_public_key(&self) -> Option<&'a [u8]> {
+        let len = match self.key_algorithm()? {
+            KeyAlgorithm::Ed25519 => 32,
+            KeyAlgorithm::Ed448 => 57,
+            _ => return None,
+        };
+        if self.algorithm.parameters.is_some() {
+            return None;
+        }
+        self.subject_public_key
+            .octets()
+            .filter(|key| key.len() == len)
+    }
+
+    /// The modulus and exponent, or None unless this is an rsaEncryption key
+    pub fn rsa_public_key(&self) -> Option<Result<RsaPublicKey<'a>, ASNError>> {
+        if self.key_algorithm() != Some(KeyAlgorithm::Rsa) {
+            return None;
+        }
+        Some(match self.subject_public_key.octets() {
+            Some(octets) => RsaPublicKey::parse(octets),
+            None => {
+                Err(ASNErrorVariant::UnalignedBitString(self.subject_public_key.unused_bits).into())
+            }
+        })
+    }
+
+    /// The curve and point of an EC key
+    ///
+    /// None for any other algorithm, for implicit or specified curve parameters, and for a
+    /// compressed point.
+    pub fn ec_public_key(&self) -> Option<EcPublicKey<'a>> {
+        if self.key_algorithm() != Some(KeyAlgorithm::Ec) {
+            return None;
+        }
+        let curve = match &self.algorithm.parameters {
+            Some(ASNType::ObjectIdentifier(wrapper)) => wrapper.value.clone(),
+            _ => return None,
+        };
+        let point = self.subject_public_key.octets()?;
+        // the X and Y coordinates that follow the marker have the same length
+        match point.split_first() {
+            Some((0x04, coordinates)) if coordinates.len() % 2 == 0 => {
+                Some(EcPublicKey { curve, point })
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_from(parser: &mut Parser<'a>) -> Result<SubjectPublicKeyInfo<'a>, ASNError> {
+        let contents = parser.expect::<Sequence>()?;
+        parser.parse_nested(contents, |parser| {
             Ok(SubjectPublicKeyInfo::new(
-                AlgorithmIdentifier::parse(parser.expect::<Sequence>()?)?,
+                AlgorithmIdentifier::parse_from(parser)?,
                 parser.expect::<BitString>()?,
             ))
         })
     }
 }
 
+#[cfg(feature = "display")]
 impl<'a> Printable for SubjectPublicKeyInfo<'a> {
     fn print(&self, printer: &mut dyn LinePrinter) {
         print_type("algorithm", &self.algorithm, printer);
@@ -366,25 +1080,299 @@ impl<'a> Printable for SubjectPublicKeyInfo<'a> {
 }
 
 impl<'a> Certificate<'a> {
-    pub fn parse(input: &[u8]) -> Result<Certificate, ASNError> {
-        let ret = Parser::parse_all(input, |p1| {
-            Parser::parse_all(p1.expect::<Sequence>()?, |p2| {
-                Ok(Certificate::new(
-                    TBSCertificate::parse(p2.expect::<Sequence>()?)?,
-                    AlgorithmIdentifier::parse(p2.expect::<Sequence>()?)?,
-                    p2.expect::<BitString>()?,
-                ))
+    /// Complete DER encoding of the TBSCertificate, the bytes covered by the signature
+    pub fn tbs_der(&self) -> &'a [u8] {
+        self.tbs_certificate.bytes
+    }
+
+    /// Parse the first CERTIFICATE block of a PEM document
+    ///
+    /// The certificate borrows from its DER encoding, so the decoded bytes are stored in `der`.
+    pub fn from_pem(input: &str, der: &'a mut Vec<u8>) -> Result<Certificate<'a>, PemError> {
+        const LABEL: &str = "CERTIFICATE";
+        *der = pem::decode(input)?
+            .into_iter()
+            .find(|(label, _)| label == LABEL)
+            .map(|(_, contents)| contents)
+            .ok_or(PemError::MissingBlock(LABEL))?;
+        Ok(Certificate::parse(der)?)
+    }
+
+    /// Read and parse a certificate file, either PEM if it starts with `-----BEGIN` or DER
+    ///
+    /// The certificate borrows from its DER encoding, so the DER bytes are stored in `der`.
+    pub fn parse_file(
+        path: impl AsRef<std::path::Path>,
+        der: &'a mut Vec<u8>,
+    ) -> Result<Certificate<'a>, CertReadError> {
+        let contents = std::fs::read(path)?;
+        if contents.starts_with(b"-----BEGIN") {
+            // a byte that isn't UTF-8 can only fail the base64 decoding of a block
+            let text = String::from_utf8_lossy(&contents);
+            return Ok(Certificate::from_pem(&text, der)?);
+        }
+        *der = contents;
+        Ok(Certificate::parse(der)?)
+    }
+
+    pub fn parse(input: &[u8]) -> Result<Certificate<'_>, ASNError> {
+        Certificate::parse_with_options(input, ParseOptions::default_single_value())
+    }
+
+    /// Parse a certificate, applying the checks selected by `options` to every element read
+    /// while parsing it
+    ///
+    /// The extensions and the RDNs of the names are parsed on demand, and their own parse
+    /// functions select the checks applied to them.
+    pub fn parse_with_options(
+        input: &[u8],
+        options: ParseOptions,
+    ) -> Result<Certificate<'_>, ASNError> {
+        Parser::non_empty_with_options(input, options)
+            .and_then(Certificate::parse_der)
+            .map_err(|err| err.relative_to(input))
+    }
+
+    /// Parse a certificate, decoding the arcs of its OIDs into `arena` instead of the heap
+    ///
+    /// For bulk processing, where resetting the arena between certificates replaces the
+    /// allocations and frees of each one. See [`Extensions::parse_in`] for the extensions.
+    #[cfg(feature = "arena")]
+    pub fn parse_in(
+        input: &'a [u8],
+        arena: &'a bumpalo::Bump,
+    ) -> Result<Certificate<'a>, ASNError> {
+        Parser::non_empty_in(input, ParseOptions::default_single_value(), arena)
+            .and_then(Certificate::parse_der)
+            .map_err(|err| err.relative_to(input))
+    }
+
+    /// Parse the certificate at the start of the input, also returning the bytes that follow it
+    ///
+    /// Calling it again on the returned bytes walks a concatenation of DER certificates. Also
+    /// useful for DER read from files that carry a trailing newline or other padding.
+    pub fn parse_allowing_trailing(input: &[u8]) -> Result<(Certificate<'_>, &[u8]), ASNError> {
+        Certificate::parse_allowing_trailing_with_options(input, ParseOptions::default())
+    }
+
+    /// Like [`Certificate::parse_allowing_trailing`], but applying the checks selected by
+    /// `options` as [`Certificate::parse_with_options`] does
+    ///
+    /// The bytes that follow the certificate are always returned, whatever the value of
+    /// `reject_trailing_data`.
+    pub fn parse_allowing_trailing_with_options(
+        input: &[u8],
+        options: ParseOptions,
+    ) -> Result<(Certificate<'_>, &[u8]), ASNError> {
+        Parser::non_empty_with_options(input, options)
+            .and_then(|mut parser| {
+                let (contents, der) = parser.expect_with_der::<Sequence>()?;
+                let cert = Certificate::parse_contents(&parser, contents, der)?;
+                Ok((cert, parser.remaining()))
             })
-        })?;
+            .map_err(|err| err.relative_to(input))
+    }
+
+    fn parse_der(mut parser: Parser<'_>) -> Result<Certificate<'_>, ASNError> {
+        let (contents, der) = parser.expect_with_der::<Sequence>()?;
+        let ret = Certificate::parse_contents(&parser, contents, der)?;
+        if parser.options().reject_trailing_data {
+            parser.expect_end()?;
+        }
         Ok(ret)
     }
 
+    fn parse_contents<'b>(
+        outer: &Parser<'b>,
+        input: &'b [u8],
+        der: &'b [u8],
+    ) -> Result<Certificate<'b>, ASNError> {
+        outer.parse_nested(input, |parser| {
+            let (tbs, tbs_der) = parser.expect_with_der::<Sequence>()?;
+            Ok(Certificate::new(
+                der,
+                TBSCertificate::parse(parser, tbs, tbs_der)?,
+                AlgorithmIdentifier::parse_from(parser)?,
+                parser.expect::<BitString>()?,
+            ))
+        })
+    }
+
+    /// The (r, s) pair of the signature, or None unless the certificate is signed with ECDSA or DSA
+    pub fn ecdsa_signature(&self) -> Result<Option<EcdsaSignature<'a>>, ASNError> {
+        match self.signature_algorithm.algorithm.values() {
+            // ecdsa-with-SHA1, ecdsa-with-SHA224 through SHA512
+            [1, 2, 840, 10045, 4, 1] | [1, 2, 840, 10045, 4, 3, 1..=4] => {}
+            // dsa-with-sha1, dsa-with-sha224, dsa-with-sha256
+            [1, 2, 840, 10040, 4, 3] | [2, 16, 840, 1, 101, 3, 4, 3, 1..=2] => {}
+            _ => return Ok(None),
+        }
+        match self.signature_value.octets() {
+            Some(octets) => EcdsaSignature::parse(octets).map(Some),
+            None => {
+                Err(ASNErrorVariant::UnalignedBitString(self.signature_value.unused_bits).into())
+            }
+        }
+    }
+
+    /// True if the current system time lies within the validity window
+    ///
+    /// Fails with `BadUTCTime` if the system clock is set before the UNIX epoch.
+    pub fn is_currently_valid(&self) -> Result<bool, ASNError> {
+        Ok(self
+            .tbs_certificate
+            .value
+            .validity
+            .is_valid(UtcTime::now()?))
+    }
+
+    /// Where the current system time falls relative to the validity window
+    ///
+    /// Fails with `BadUTCTime` if the system clock is set before the UNIX epoch.
+    pub fn validity_status(&self) -> Result<ValidityStatus, ASNError> {
+        Ok(self.tbs_certificate.value.validity.status(UtcTime::now()?))
+    }
+
+    /// True if the certificate carries the TLS Feature extension with status_request (OCSP must-staple)
+    pub fn has_must_staple(&self) -> Result<bool, ASNError> {
+        self.contains_extension(|ext| {
+            matches!(ext, SpecificExtension::TlsFeature(features) if features.status_request())
+        })
+    }
+
+    /// True if the certificate carries the id-pkix-ocsp-nocheck extension
+    pub fn is_ocsp_nocheck(&self) -> Result<bool, ASNError> {
+        self.contains_extension(|ext| matches!(ext, SpecificExtension::OcspNoCheck))
+    }
+
+    /// True if the hostname matches a SAN dNSName or iPAddress entry
+    ///
+    /// The subject CN is only consulted when there is no SAN with a DNS-ID, URI-ID or SRV-ID
+    /// (RFC 6125 §6.4.4), and never for IP addresses.
+    pub fn matches_hostname(&self, hostname: &str) -> Result<bool, ASNError> {
+        self.matches_hostname_with(&self.parse_extensions()?, hostname)
+    }
+
+    /// Check whether the certificate is acceptable to a TLS client connecting to `hostname` at `now`
+    pub fn is_valid_tls_server(
+        &self,
+        hostname: &str,
+        now: UtcTime,
+    ) -> Result<TlsServerCheck, ASNError> {
+        let extensions = self.parse_extensions()?;
+
+        let key_usage = extensions
+            .iter()
+            .find_map(|ext| match &ext.content {
+                SpecificExtension::KeyUsage(usage) => {
+                    Some(usage.digital_signature || usage.key_encipherment)
+                }
+                _ => None,
+            })
+            .unwrap_or(true);
+
+        let extended_key_usage = extensions
+            .iter()
+            .find_map(|ext| match &ext.content {
+                SpecificExtension::ExtendedKeyUsage(usage) => {
+                    Some(usage.ext_key_usages.iter().any(|purpose| {
+                        matches!(
+                            purpose,
+                            ExtendedKeyUsagePurpose::ServerAuth
+                                | ExtendedKeyUsagePurpose::AnyExtendedKeyUsage
+                        )
+                    }))
+                }
+                _ => None,
+            })
+            .unwrap_or(true);
+
+        Ok(TlsServerCheck {
+            hostname: self.matches_hostname_with(&extensions, hostname)?,
+            validity: self.tbs_certificate.value.validity.is_valid(now),
+            key_usage,
+            extended_key_usage,
+        })
+    }
+
+    fn matches_hostname_with(
+        &self,
+        extensions: &[Extension],
+        hostname: &str,
+    ) -> Result<bool, ASNError> {
+        let names: Vec<&GeneralName> = extensions
+            .iter()
+            .filter_map(|ext| match &ext.content {
+                SpecificExtension::SubjectAlternativeName(san) => Some(san.names.iter()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+
+        if let Ok(address) = hostname.parse::<std::net::IpAddr>() {
+            return Ok(names.iter().any(|name| name.ip_addr() == Some(address)));
+        }
+
+        let dns_names: Vec<&str> = names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::DnsName(x) => Some(*x),
+                _ => None,
+            })
+            .collect();
+
+        if !dns_names.is_empty() {
+            return Ok(dns_names
+                .iter()
+                .any(|pattern| tls::dns_name_matches(pattern, hostname)));
+        }
+
+        // a URI-ID or SRV-ID means the issuer identified the service, so the CN isn't a fallback
+        let has_other_ids = names.iter().any(|name| match name {
+            GeneralName::UniformResourceIdentifier(_) => true,
+            GeneralName::OtherName { type_id, .. } => {
+                type_id.values() == [1, 3, 6, 1, 5, 5, 7, 8, 7]
+            }
+            _ => false,
+        });
+        if has_other_ids {
+            return Ok(false);
+        }
+
+        Ok(self
+            .tbs_certificate
+            .value
+            .subject
+            .parse()?
+            .common_name
+            .is_some_and(|cn| tls::dns_name_matches(&cn, hostname)))
+    }
+
+    fn parse_extensions(&self) -> Result<Vec<Extension<'a>>, ASNError> {
+        match &self.tbs_certificate.value.extensions {
+            Some(extensions) => extensions.parse(),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn contains_extension<F>(&self, predicate: F) -> Result<bool, ASNError>
+    where
+        F: Fn(&SpecificExtension) -> bool,
+    {
+        match &self.tbs_certificate.value.extensions {
+            Some(extensions) => extensions.contains(predicate),
+            None => Ok(false),
+        }
+    }
+
     pub(crate) fn new(
+        raw: &'a [u8],
         tbs_certificate: Constructed<'a, TBSCertificate<'a>>,
         signature_algorithm: AlgorithmIdentifier<'a>,
         signature_value: ASNBitString<'a>,
     ) -> Certificate<'a> {
         Certificate {
+            raw,
             tbs_certificate,
             signature_algorithm,
             signature_value,
@@ -393,21 +1381,74 @@ impl<'a> Certificate<'a> {
 }
 
 impl<'a> AlgorithmIdentifier<'a> {
-    fn parse(input: &[u8]) -> Result<AlgorithmIdentifier, ASNErrorVariant> {
-        let mut parser = Parser::new(input);
+    fn parse(input: &[u8]) -> Result<AlgorithmIdentifier<'_>, ASNError> {
+        AlgorithmIdentifier::parse_contents(&mut Parser::new(input))
+    }
+
+    fn parse_from(parser: &mut Parser<'a>) -> Result<AlgorithmIdentifier<'a>, ASNError> {
+        AlgorithmIdentifier::parse_contents(&mut parser.expect_sequence_contents()?)
+    }
 
+    fn parse_contents(parser: &mut Parser<'a>) -> Result<AlgorithmIdentifier<'a>, ASNError> {
         Ok(AlgorithmIdentifier::new(
             parser.expect::<ObjectIdentifier>()?,
             parser.expect_any_or_end()?,
         ))
     }
 
-    pub fn new(algorithm: ASNObjectIdentifier, parameters: Option<ASNType>) -> AlgorithmIdentifier {
+    pub fn new(
+        algorithm: ASNObjectIdentifier<'a>,
+        parameters: Option<ASNType<'a>>,
+    ) -> AlgorithmIdentifier<'a> {
         AlgorithmIdentifier {
             algorithm,
             parameters,
         }
     }
+
+    /// The structured parameters of RSASSA-PSS, or None for any other algorithm
+    ///
+    /// Absent parameters mean every field takes its default.
+    pub fn rsa_pss_params(&self) -> Result<Option<RsaPssParams<'a>>, ASNError> {
+        if self.algorithm.values() != RSASSA_PSS {
+            return Ok(None);
+        }
+        match &self.parameters {
+            Some(ASNType::Sequence(wrapper)) => RsaPssParams::parse(wrapper.value).map(Some),
+            Some(other) => {
+                Err(ASNErrorVariant::UnexpectedType(ASNTypeId::Sequence, other.get_id()).into())
+            }
+            None => RsaPssParams::parse(&[]).map(Some),
+        }
+    }
+
+    /// Digest used by this signature algorithm, or None if the algorithm is not recognized
+    ///
+    /// RSASSA-PSS carries its hash in the parameters and is not covered here, see
+    /// [`AlgorithmIdentifier::rsa_pss_params`].
+    pub fn digest_algorithm(&self) -> Option<DigestAlgorithm> {
+        match self.algorithm.values() {
+            // sha1WithRSAEncryption, ecdsa-with-SHA1, dsa-with-sha1
+            [1, 2, 840, 113549, 1, 1, 5] | [1, 2, 840, 10045, 4, 1] | [1, 2, 840, 10040, 4, 3] => {
+                Some(DigestAlgorithm::Sha1)
+            }
+            // sha256WithRSAEncryption, ecdsa-with-SHA256, dsa-with-sha256
+            [1, 2, 840, 113549, 1, 1, 11]
+            | [1, 2, 840, 10045, 4, 3, 2]
+            | [2, 16, 840, 1, 101, 3, 4, 3, 2] => Some(DigestAlgorithm::Sha256),
+            // sha384WithRSAEncryption, ecdsa-with-SHA384
+            [1, 2, 840, 113549, 1, 1, 12] | [1, 2, 840, 10045, 4, 3, 3] => {
+                Some(DigestAlgorithm::Sha384)
+            }
+            // sha512WithRSAEncryption, ecdsa-with-SHA512
+            [1, 2, 840, 113549, 1, 1, 13] | [1, 2, 840, 10045, 4, 3, 4] => {
+                Some(DigestAlgorithm::Sha512)
+            }
+            // Ed25519, Ed448
+            [1, 3, 101, 112] | [1, 3, 101, 113] => Some(DigestAlgorithm::None),
+            _ => None,
+        }
+    }
 }
 
 impl<'a> TBSCertificate<'a> {
@@ -439,55 +1480,54 @@ impl<'a> TBSCertificate<'a> {
         }
     }
 
-    fn parse(input: &[u8]) -> Result<Constructed<TBSCertificate>, ASNErrorVariant> {
-        fn parse_version(parser: &mut Parser) -> Result<Version, ASNErrorVariant> {
-            match parser.get_optional_explicit_tag_value::<Integer>(0)? {
-                Some(value) => match value.as_i32() {
-                    Some(0) => Ok(Version::V1),
-                    Some(1) => Ok(Version::V2),
-                    Some(2) => Ok(Version::V3),
-                    Some(x) => Err(ASNErrorVariant::BadEnumValue("version", x)),
-                    None => Err(ASNErrorVariant::IntegerTooLarge(value.bytes.len())),
-                },
-                None => Ok(Version::V1),
+    fn parse(
+        outer: &Parser<'a>,
+        input: &'a [u8],
+        der: &'a [u8],
+    ) -> Result<Constructed<'a, Self>, ASNError> {
+        fn parse_version(parser: &mut Parser) -> Result<Version, ASNError> {
+            fn version(value: i32) -> Option<Version> {
+                match value {
+                    0 => Some(Version::V1),
+                    1 => Some(Version::V2),
+                    2 => Some(Version::V3),
+                    _ => None,
+                }
             }
+
+            Ok(parser
+                .get_optional_explicit(0, |parser| parser.expect_enum("version", version))?
+                .unwrap_or(Version::V1))
         }
 
         fn parse_optional_bitstring<'a>(
             parser: &mut Parser<'a>,
-            tag: u8,
-        ) -> Result<Option<ASNBitString<'a>>, ASNErrorVariant> {
+            tag: u32,
+        ) -> Result<Option<ASNBitString<'a>>, ASNError> {
             // TODO: check minimum version
-            match parser.get_optional_explicit_tag(tag)? {
-                Some(tag) => Parser::parse_all(tag.contents, |parser| {
-                    Ok(Some(parser.expect::<BitString>()?))
-                }),
-                None => Ok(None),
-            }
+            // UniqueIdentifier is [1] or [2] IMPLICIT BIT STRING
+            parser
+                .get_optional_context_field(tag)?
+                .map(|x| parser.nested(x)?.parse_implicit::<BitString>())
+                .transpose()
         }
 
         fn parse_extensions<'a>(
             parser: &mut Parser<'a>,
-        ) -> Result<Option<Extensions<'a>>, ASNErrorVariant> {
+        ) -> Result<Option<Extensions<'a>>, ASNError> {
             // TODO: check minimum version
-            if let Some(tag) = parser.get_optional_explicit_tag(3)? {
-                Ok(Some(Extensions::new(tag.contents)))
-            } else {
-                Ok(None)
-            }
+            Ok(parser.get_optional_context_field(3)?.map(Extensions::new))
         }
 
-        fn parse_tbs_cert<'a>(
-            parser: &mut Parser<'a>,
-        ) -> Result<TBSCertificate<'a>, ASNErrorVariant> {
+        fn parse_tbs_cert<'a>(parser: &mut Parser<'a>) -> Result<TBSCertificate<'a>, ASNError> {
             Ok(TBSCertificate::new(
                 parse_version(parser)?,
                 parser.expect::<Integer>()?,
-                AlgorithmIdentifier::parse(parser.expect::<Sequence>()?)?,
-                Name::new(parser.expect::<Sequence>()?),
-                Validity::parse(parser.expect::<Sequence>()?)?,
-                Name::new(parser.expect::<Sequence>()?),
-                SubjectPublicKeyInfo::parse(parser.expect::<Sequence>()?)?,
+                AlgorithmIdentifier::parse_from(parser)?,
+                Name::parse_from(parser)?,
+                Validity::parse_from(parser)?,
+                Name::parse_from(parser)?,
+                SubjectPublicKeyInfo::parse_from(parser)?,
                 parse_optional_bitstring(parser, 1)?,
                 parse_optional_bitstring(parser, 2)?,
                 parse_extensions(parser)?,
@@ -495,8 +1535,8 @@ impl<'a> TBSCertificate<'a> {
         }
 
         Ok(Constructed::new(
-            input,
-            Parser::parse_all(input, parse_tbs_cert)?,
+            der,
+            outer.parse_nested(input, parse_tbs_cert)?,
         ))
     }
 }
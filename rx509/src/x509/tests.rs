@@ -1,16 +1,1223 @@
-use crate::x509::Certificate;
+use crate::der::encode::encode;
+use crate::der::parser::Parser;
+use crate::der::{
+    ASNBitString, ASNErrorVariant, ASNObjectIdentifier, ASNType, ASNTypeId, ParseOptions, Sequence,
+    UtcTime,
+};
+use crate::pem::PemError;
+use crate::x509::ext::{AccessMethod, ExtendedKeyUsagePurpose, GeneralName, SpecificExtension};
+use crate::x509::tls::TlsServerCheck;
+use crate::x509::{
+    parse_chain, scan, AlgorithmIdentifier, Ava, AvaValue, CertReadError, Certificate,
+    DigestAlgorithm, EcdsaSignature, KeyAlgorithm, RelativeDistinguishedName, SubjectPublicKeyInfo,
+    Validity, ValidityStatus, Version,
+};
 
 #[test]
 fn parses_rsa_cert() {
     Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
 }
 
+#[test]
+fn parse_allowing_trailing_ignores_a_trailing_newline() {
+    let der = include_bytes!("../../../certs/512b-rsa-example-cert.der");
+    let mut padded = der.to_vec();
+    padded.push(0x0A);
+
+    assert!(Certificate::parse(&padded).is_err());
+    let strict = Certificate::parse(der).unwrap();
+    let (lenient, rest) = Certificate::parse_allowing_trailing(&padded).unwrap();
+    assert_eq!(
+        strict.tbs_certificate.value.serial_number,
+        lenient.tbs_certificate.value.serial_number
+    );
+    assert_eq!(rest, [0x0A]);
+    assert!(Certificate::parse_allowing_trailing(der)
+        .unwrap()
+        .1
+        .is_empty());
+}
+
+#[test]
+fn parses_concatenated_certs_one_at_a_time() {
+    let rsa = include_bytes!("../../../certs/512b-rsa-example-cert.der");
+    let ed25519 = include_bytes!("../../../certs/ed25519-example-cert.der");
+    let bundle = [&rsa[..], &ed25519[..]].concat();
+
+    let (first, rest) = Certificate::parse_allowing_trailing(&bundle).unwrap();
+    assert_eq!(first.tbs_der(), Certificate::parse(rsa).unwrap().tbs_der());
+    assert_eq!(rest, ed25519);
+
+    let (second, rest) = Certificate::parse_allowing_trailing(rest).unwrap();
+    assert_eq!(
+        second.tbs_der(),
+        Certificate::parse(ed25519).unwrap().tbs_der()
+    );
+    assert!(rest.is_empty());
+
+    assert_eq!(
+        Certificate::parse_allowing_trailing(rest)
+            .unwrap_err()
+            .variant,
+        ASNErrorVariant::EmptyInput
+    );
+}
+
+#[test]
+fn walks_concatenated_certs_with_the_given_options() {
+    let rsa = include_bytes!("../../../certs/512b-rsa-example-cert.der");
+    let ed25519 = include_bytes!("../../../certs/ed25519-example-cert.der");
+    // pad the last arc of the signature OID of the first cert as in
+    // applies_parse_options_to_nested_elements
+    let mut padded = rsa.to_vec();
+    padded.insert(24, 0x80);
+    for offset in [15, 13, 7, 3] {
+        padded[offset] += 1;
+    }
+    let bundle = [&padded[..], &ed25519[..]].concat();
+
+    assert_eq!(
+        Certificate::parse_allowing_trailing(&bundle)
+            .unwrap_err()
+            .variant,
+        ASNErrorVariant::NonMinimalOid
+    );
+
+    let mut rest = &bundle[..];
+    let mut count = 0;
+    while !rest.is_empty() {
+        let (_, next) =
+            Certificate::parse_allowing_trailing_with_options(rest, ParseOptions::lenient())
+                .unwrap();
+        rest = next;
+        count += 1;
+    }
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn keeps_the_encoding_of_the_whole_certificate() {
+    let rsa = include_bytes!("../../../certs/512b-rsa-example-cert.der");
+    let ed25519 = include_bytes!("../../../certs/ed25519-example-cert.der");
+
+    let cert = Certificate::parse(rsa).unwrap();
+    assert_eq!(cert.raw, rsa);
+    let reparsed = Certificate::parse(cert.raw).unwrap();
+    assert_eq!(reparsed.tbs_der(), cert.tbs_der());
+    assert_eq!(reparsed.signature_value, cert.signature_value);
+    assert_eq!(reparsed.reencode().unwrap(), cert.reencode().unwrap());
+
+    // trailing bytes and the framing of a chain are not part of it
+    let bundle = [&rsa[..], &ed25519[..]].concat();
+    let (cert, _) = Certificate::parse_allowing_trailing(&bundle).unwrap();
+    assert_eq!(cert.raw, rsa);
+    let mut chain = Vec::new();
+    encode(&Sequence::asn(&bundle), &mut chain).unwrap();
+    let certs = parse_chain(&chain).unwrap();
+    assert_eq!(certs[0].raw, rsa);
+    assert_eq!(certs[1].raw, ed25519);
+}
+
+#[test]
+fn maps_signature_algorithms_to_digests() {
+    fn digest(oid: &[u128]) -> Option<DigestAlgorithm> {
+        AlgorithmIdentifier::new(ASNObjectIdentifier::new(oid.to_vec()), None).digest_algorithm()
+    }
+
+    assert_eq!(
+        digest(&[1, 2, 840, 113549, 1, 1, 11]),
+        Some(DigestAlgorithm::Sha256)
+    );
+    assert_eq!(
+        digest(&[1, 2, 840, 10045, 4, 3, 3]),
+        Some(DigestAlgorithm::Sha384)
+    );
+    assert_eq!(digest(&[1, 2, 840, 113549, 1, 1, 10]), None);
+
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/ed25519-example-cert.der")).unwrap();
+    assert_eq!(
+        cert.signature_algorithm.digest_algorithm(),
+        Some(DigestAlgorithm::None)
+    );
+}
+
+#[test]
+fn extracts_user_principal_name_from_smartcard_cert() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/smartcard_upn.der")).unwrap();
+    let extensions = cert.tbs_certificate.value.extensions.as_ref().unwrap();
+    let names = extensions
+        .parse()
+        .unwrap()
+        .into_iter()
+        .find_map(|ext| match ext.content {
+            SpecificExtension::SubjectAlternativeName(x) => Some(x.names),
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(names.len(), 3);
+    assert!(matches!(
+        names[0],
+        GeneralName::UserPrincipalName("jdoe@corp.example.com")
+    ));
+    // otherNames with other type-ids keep the DER of their value
+    match &names[1] {
+        GeneralName::OtherName { type_id, value } => {
+            assert_eq!(type_id.values(), [1, 2, 3, 4]);
+            assert_eq!(*value, b"\x0c\x05other");
+        }
+        other => panic!("unexpected name: {:?}", other),
+    }
+    assert!(matches!(
+        names[2],
+        GeneralName::DnsName("host.corp.example.com")
+    ));
+}
+
+#[test]
+fn parses_directory_name_subject_alternative_name() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/directory_name_san.der")).unwrap();
+    let extensions = cert.tbs_certificate.value.extensions.as_ref().unwrap();
+    let names = extensions
+        .parse()
+        .unwrap()
+        .into_iter()
+        .find_map(|ext| match ext.content {
+            SpecificExtension::SubjectAlternativeName(x) => Some(x.names),
+            _ => None,
+        })
+        .unwrap();
+
+    match names.as_slice() {
+        [GeneralName::DirectoryName(name), GeneralName::DnsName("device.example.com")] => {
+            assert_eq!(
+                name.parse().unwrap().to_rfc4514_string(),
+                "CN=Device 42,O=Example Devices,C=US"
+            );
+        }
+        other => panic!("unexpected names: {:?}", other),
+    }
+}
+
+#[test]
+fn validity_status_distinguishes_not_yet_valid_from_expired() {
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
+    let validity = &cert.tbs_certificate.value.validity;
+    let at = |offset: i64| {
+        UtcTime::from_seconds_since_epoch(validity.not_before.value.wrapping_add_signed(offset))
+    };
+    let length = (validity.not_after.value - validity.not_before.value) as i64;
+
+    assert_eq!(validity.status(at(-1)), ValidityStatus::NotYetValid);
+    assert_eq!(validity.status(at(0)), ValidityStatus::Valid);
+    assert_eq!(validity.status(at(length / 2)), ValidityStatus::Valid);
+    assert_eq!(validity.status(at(length)), ValidityStatus::Valid);
+    assert_eq!(validity.status(at(length + 1)), ValidityStatus::Expired);
+}
+
+#[test]
+fn checks_validity_against_the_current_time() {
+    // expired in 2017
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
+    assert!(!cert.is_currently_valid().unwrap());
+    assert_eq!(cert.validity_status().unwrap(), ValidityStatus::Expired);
+
+    // valid from 2023 through 2123
+    let cert = Certificate::parse(include_bytes!(
+        "../../../certs/cert_with_generalized_time.der"
+    ))
+    .unwrap();
+    assert!(cert.is_currently_valid().unwrap());
+    assert_eq!(cert.validity_status().unwrap(), ValidityStatus::Valid);
+}
+
+#[test]
+fn parses_issuer_alternative_name() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/issuer_alt_name.der")).unwrap();
+    let extensions = cert.tbs_certificate.value.extensions.as_ref().unwrap();
+    let names = extensions
+        .parse()
+        .unwrap()
+        .into_iter()
+        .find_map(|ext| match ext.content {
+            SpecificExtension::IssuerAlternativeName(x) => Some(x.names),
+            _ => None,
+        })
+        .unwrap();
+
+    assert!(matches!(
+        names.as_slice(),
+        [
+            GeneralName::DnsName("ca.example.com"),
+            GeneralName::UniformResourceIdentifier("http://ca.example.com/")
+        ]
+    ));
+    // the issuer's names don't take part in hostname matching
+    assert!(cert.matches_hostname("device.example.com").unwrap());
+    assert!(!cert.matches_hostname("ca.example.com").unwrap());
+}
+
+#[test]
+fn ignores_the_common_name_if_the_san_has_a_uri_or_srv_id() {
+    // both have the subject CN=www.example.com, but only a URI-ID or an SRV-ID in the SAN
+    for der in [
+        &include_bytes!("../../../certs/uri_san.der")[..],
+        include_bytes!("../../../certs/srv_san.der"),
+    ] {
+        let cert = Certificate::parse(der).unwrap();
+        assert_eq!(
+            cert.tbs_certificate
+                .value
+                .subject
+                .parse()
+                .unwrap()
+                .common_name,
+            Some("www.example.com".into())
+        );
+        assert!(!cert.matches_hostname("www.example.com").unwrap());
+    }
+}
+
+#[test]
+fn accepts_valid_tls_server_cert() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/tls_server.der")).unwrap();
+    let validity = &cert.tbs_certificate.value.validity;
+    let now = UtcTime::from_seconds_since_epoch(validity.not_before.value + 60);
+
+    let check = cert.is_valid_tls_server("www.example.com", now).unwrap();
+    assert!(check.is_valid());
+    assert!(cert.matches_hostname("api.example.org").unwrap());
+    assert!(cert.matches_hostname("127.0.0.1").unwrap());
+    assert!(!cert.matches_hostname("example.org").unwrap());
+    assert!(!cert.matches_hostname("127.0.0.2").unwrap());
+
+    let check = cert.is_valid_tls_server("www.example.net", now).unwrap();
+    assert!(!check.hostname);
+    assert!(!check.is_valid());
+
+    let expired = UtcTime::from_seconds_since_epoch(validity.not_after.value + 1);
+    let check = cert
+        .is_valid_tls_server("www.example.com", expired)
+        .unwrap();
+    assert!(!check.validity);
+    assert!(check.hostname && check.key_usage && check.extended_key_usage);
+}
+
+#[test]
+fn rejects_tls_server_cert_without_server_auth() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/tls_client_only.der")).unwrap();
+    let now = UtcTime::from_seconds_since_epoch(
+        cert.tbs_certificate.value.validity.not_before.value + 60,
+    );
+
+    let check = cert.is_valid_tls_server("www.example.com", now).unwrap();
+    assert_eq!(
+        check,
+        TlsServerCheck {
+            hostname: true,
+            validity: true,
+            key_usage: true,
+            extended_key_usage: false,
+        }
+    );
+    assert!(!check.is_valid());
+}
+
+#[test]
+fn serial_number_der_content_preserves_sign_byte() {
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/serial_with_sign_byte.der")).unwrap();
+    // serial 0x8000000000000001 needs a leading zero to remain positive
+    assert_eq!(
+        cert.tbs_certificate.value.serial_number.der_content(),
+        [0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01]
+    );
+}
+
+#[test]
+fn parses_authority_key_identifier_issuer_and_serial() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/aki_issuer_serial.der")).unwrap();
+    let extensions = cert.tbs_certificate.value.extensions.as_ref().unwrap();
+    let aki = extensions
+        .parse()
+        .unwrap()
+        .into_iter()
+        .find_map(|ext| match ext.content {
+            SpecificExtension::AuthorityKeyIdentifier(x) => Some(x),
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(aki.key_identifier, None);
+    assert_eq!(aki.serial().unwrap().der_content(), [0x01, 0x02]);
+    match aki.issuer_names() {
+        [GeneralName::DirectoryName(name)] => {
+            // self-signed, so the issuer's issuer is the certificate's own issuer
+            assert_eq!(name.der(), cert.tbs_certificate.value.issuer.der());
+            assert_eq!(
+                name.parse().unwrap().to_rfc4514_string(),
+                "CN=Legacy Root,O=Legacy CA,C=US"
+            );
+        }
+        other => panic!("unexpected issuer names: {:?}", other),
+    }
+}
+
+#[test]
+fn authority_key_identifier_of_ca_matches_its_subject_key_identifier() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/ca_pathlen_0.der")).unwrap();
+    let extensions = cert
+        .tbs_certificate
+        .value
+        .extensions
+        .as_ref()
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    let ski = extensions
+        .iter()
+        .find_map(|ext| match &ext.content {
+            SpecificExtension::SubjectKeyIdentifier(x) => Some(x.key_identifier),
+            _ => None,
+        })
+        .unwrap();
+    let aki = extensions
+        .iter()
+        .find_map(|ext| match &ext.content {
+            SpecificExtension::AuthorityKeyIdentifier(x) => Some(x),
+            _ => None,
+        })
+        .unwrap();
+
+    // self-signed, so the AKI refers to its own key
+    assert_eq!(aki.key_identifier, Some(ski));
+    assert_eq!(
+        ski,
+        [
+            0x83, 0x6C, 0xB7, 0xF7, 0x40, 0x10, 0x38, 0xFE, 0x56, 0x98, 0x85, 0xAD, 0x85, 0x72,
+            0x02, 0x92, 0xDB, 0xCC, 0x77, 0xE4
+        ]
+    );
+    assert!(aki.issuer_names().is_empty());
+    assert!(aki.serial().is_none());
+}
+
+#[test]
+fn parses_crl_distribution_point_uris() {
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/crl_distribution_points.der")).unwrap();
+    let extensions = cert
+        .tbs_certificate
+        .value
+        .extensions
+        .as_ref()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let points = extensions
+        .iter()
+        .find_map(|ext| match &ext.content {
+            SpecificExtension::CrlDistributionPoints(x) => Some(x),
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(points.points.len(), 2);
+    assert!(points
+        .points
+        .iter()
+        .all(|x| x.reasons.is_none() && x.crl_issuer.is_none()));
+    assert_eq!(
+        points.uris().collect::<Vec<_>>(),
+        [
+            "http://crl.example.com/ca.crl",
+            "ldap://ldap.example.com/cn=CA?certificateRevocationList"
+        ]
+    );
+}
+
+#[test]
+fn parses_authority_info_access_locations() {
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/authority_info_access.der")).unwrap();
+    let extensions = cert
+        .tbs_certificate
+        .value
+        .extensions
+        .as_ref()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let aia = extensions
+        .iter()
+        .find_map(|ext| match &ext.content {
+            SpecificExtension::AuthorityInfoAccess(x) => Some(x),
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(aia.descriptions.len(), 2);
+    assert_eq!(
+        aia.uris(&AccessMethod::Ocsp).collect::<Vec<_>>(),
+        ["http://ocsp.example.com"]
+    );
+    assert_eq!(
+        aia.uris(&AccessMethod::CaIssuers).collect::<Vec<_>>(),
+        ["http://ca.example.com/issuer.der"]
+    );
+}
+
+#[test]
+fn parses_private_key_usage_period() {
+    let cert = Certificate::parse(include_bytes!(
+        "../../../certs/private_key_usage_period.der"
+    ))
+    .unwrap();
+    let extensions = cert
+        .tbs_certificate
+        .value
+        .extensions
+        .as_ref()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let period = extensions
+        .iter()
+        .find_map(|ext| match &ext.content {
+            SpecificExtension::PrivateKeyUsagePeriod(x) => Some(x),
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(
+        period.not_before.map(|x| x.to_iso8601()).as_deref(),
+        Some("2026-01-01T00:00:00Z")
+    );
+    assert_eq!(
+        period.not_after.map(|x| x.to_iso8601()).as_deref(),
+        Some("2031-01-01T00:00:00Z")
+    );
+}
+
+#[test]
+fn parses_name_constraints() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/name_constraints.der")).unwrap();
+    let extensions = cert
+        .tbs_certificate
+        .value
+        .extensions
+        .as_ref()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let (critical, constraints) = extensions
+        .iter()
+        .find_map(|ext| match &ext.content {
+            SpecificExtension::NameConstraints(x) => Some((ext.critical, x)),
+            _ => None,
+        })
+        .unwrap();
+
+    assert!(critical);
+    assert_eq!(constraints.permitted_subtrees.len(), 1);
+    assert!(matches!(
+        constraints.permitted_subtrees[0].base,
+        GeneralName::DnsName(".example.com")
+    ));
+    assert!(constraints
+        .permitted_subtrees
+        .iter()
+        .all(|x| x.minimum.is_none() && x.maximum.is_none()));
+
+    assert_eq!(constraints.excluded_subtrees.len(), 2);
+    assert!(matches!(
+        constraints.excluded_subtrees[0].base,
+        GeneralName::DnsName("secret.example.com")
+    ));
+    assert!(matches!(
+        constraints.excluded_subtrees[1].base,
+        GeneralName::IpAddress(&[10, 0, 0, 0, 255, 0, 0, 0])
+    ));
+}
+
+#[test]
+fn parses_ip_address_subject_alternative_names() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/ip_address_san.der")).unwrap();
+    let extensions = cert.tbs_certificate.value.extensions.as_ref().unwrap();
+    let names = extensions
+        .parse()
+        .unwrap()
+        .into_iter()
+        .find_map(|ext| match ext.content {
+            SpecificExtension::SubjectAlternativeName(x) => Some(x.names),
+            _ => None,
+        })
+        .unwrap();
+
+    let addresses: Vec<_> = names.iter().map(|name| name.ip_addr()).collect();
+    assert_eq!(
+        addresses,
+        [
+            Some("192.0.2.1".parse().unwrap()),
+            Some("2001:db8::1".parse().unwrap())
+        ]
+    );
+    assert!(cert.matches_hostname("2001:db8::1").unwrap());
+
+    // an address and mask from NameConstraints
+    assert_eq!(
+        GeneralName::IpAddress(&[10, 0, 0, 0, 255, 0, 0, 0]).ip_addr(),
+        None
+    );
+    assert_eq!(GeneralName::DnsName("192.0.2.1").ip_addr(), None);
+}
+
+#[test]
+fn parses_certificate_policies() {
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/certificate_policies.der")).unwrap();
+    let extensions = cert
+        .tbs_certificate
+        .value
+        .extensions
+        .as_ref()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let policies = extensions
+        .iter()
+        .find_map(|ext| match &ext.content {
+            SpecificExtension::CertificatePolicies(x) => Some(x),
+            _ => None,
+        })
+        .unwrap();
+
+    let ids: Vec<&[u128]> = policies
+        .policies
+        .iter()
+        .map(|x| x.policy_identifier.values())
+        .collect();
+    assert_eq!(
+        ids,
+        [
+            &[2, 23, 140, 1, 2, 1][..],
+            &[1, 3, 6, 1, 4, 1, 99999, 1],
+            &[2, 5, 29, 32, 0]
+        ]
+    );
+    assert!(policies.policies[0].policy_qualifiers.is_none());
+    // SEQUENCE OF PolicyQualifierInfo holding the CPS URI
+    assert_eq!(
+        policies.policies[1].policy_qualifiers.map(|x| x[0]),
+        Some(0x30)
+    );
+    assert!(!policies.policies[1].is_any_policy());
+    assert!(policies.policies[2].is_any_policy());
+}
+
+#[test]
+fn encodes_subject_public_key_info() {
+    // the original encodings of the SubjectPublicKeyInfo and of its AlgorithmIdentifier, the
+    // SubjectPublicKeyInfo follows the optional version and five other fields of the TBS
+    fn original_der(input: &[u8]) -> (&[u8], &[u8]) {
+        let mut cert = Parser::new(input).expect_sequence_contents().unwrap();
+        let mut tbs = cert.expect_sequence_contents().unwrap();
+        tbs.get_optional_context_field(0).unwrap();
+        for _ in 0..5 {
+            tbs.skip().unwrap();
+        }
+        let (contents, spki) = tbs.expect_with_der::<Sequence>().unwrap();
+        let algorithm = Parser::new(contents)
+            .expect_with_der::<Sequence>()
+            .unwrap()
+            .1;
+        (spki, algorithm)
+    }
+
+    for input in [
+        &include_bytes!("../../../certs/512b-rsa-example-cert.der")[..],
+        &include_bytes!("../../../certs/ec_p256_cert.der")[..],
+        &include_bytes!("../../../certs/ed25519-example-cert.der")[..],
+    ] {
+        let cert = Certificate::parse(input).unwrap();
+        let spki = &cert.tbs_certificate.value.subject_public_key_info;
+        let (spki_der, algorithm_der) = original_der(input);
+        assert_eq!(spki.to_der().unwrap(), spki_der);
+        assert_eq!(spki.algorithm.to_der().unwrap(), algorithm_der);
+    }
+
+    // Ed25519 key without parameters
+    let key = [0xAB; 32];
+    let spki = SubjectPublicKeyInfo::new(
+        AlgorithmIdentifier::new(ASNObjectIdentifier::new(vec![1, 3, 101, 112]), None),
+        ASNBitString::new(0, &key),
+    );
+    let der = spki.to_der().unwrap();
+    assert_eq!(
+        der[..12],
+        [0x30, 0x2A, 0x30, 0x05, 0x06, 0x03, 0x2B, 0x65, 0x70, 0x03, 0x21, 0x00]
+    );
+    assert_eq!(der[12..], key);
+}
+
+#[test]
+fn extracts_rsa_public_key() {
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
+    let spki = &cert.tbs_certificate.value.subject_public_key_info;
+    let key = spki.rsa_public_key().unwrap().unwrap();
+    // the leading 0x00 only keeps the INTEGER positive
+    let modulus = key.modulus.der_content().strip_prefix(&[0x00]).unwrap();
+    assert_eq!(modulus.len(), 64);
+    assert_eq!(modulus[0], 0x9B);
+    assert_eq!(key.exponent.as_u64(), Some(65537));
+
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/ed25519-example-cert.der")).unwrap();
+    assert!(cert
+        .tbs_certificate
+        .value
+        .subject_public_key_info
+        .rsa_public_key()
+        .is_none());
+}
+
+#[test]
+fn extracts_ec_public_key() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/ec_p256_cert.der")).unwrap();
+    let spki = &cert.tbs_certificate.value.subject_public_key_info;
+    let key = spki.ec_public_key().unwrap();
+    assert_eq!(key.curve.values(), [1, 2, 840, 10045, 3, 1, 7]);
+    assert_eq!(key.point.len(), 65);
+    assert_eq!(key.point[0], 0x04);
+    assert!(spki.rsa_public_key().is_none());
+
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
+    let spki = &cert.tbs_certificate.value.subject_public_key_info;
+    assert_eq!(spki.ec_public_key(), None);
+}
+
+#[test]
+fn extracts_eddsa_public_keys() {
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/ed25519-example-cert.der")).unwrap();
+    let spki = &cert.tbs_certificate.value.subject_public_key_info;
+    let key = spki.eddsa_public_key().unwrap();
+    assert_eq!(key.len(), 32);
+    assert_eq!(Some(key), spki.subject_public_key.octets());
+
+    // the same key with NULL parameters
+    let spki = SubjectPublicKeyInfo::new(
+        AlgorithmIdentifier::new(spki.algorithm.algorithm.clone(), Some(ASNType::Null)),
+        ASNBitString::new(0, key),
+    );
+    assert_eq!(spki.eddsa_public_key(), None);
+
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
+    let spki = &cert.tbs_certificate.value.subject_public_key_info;
+    assert_eq!(spki.eddsa_public_key(), None);
+}
+
+#[test]
+fn extracts_ed448_public_key() {
+    // SEQUENCE { SEQUENCE { OID 1.3.101.113 }, BIT STRING (57 bytes) }
+    let mut spki = vec![
+        0x30, 0x43, 0x30, 0x05, 0x06, 0x03, 0x2B, 0x65, 0x71, 0x03, 0x3A, 0x00,
+    ];
+    spki.extend(0..57u8);
+
+    let spki = SubjectPublicKeyInfo::parse_from(&mut Parser::new(&spki)).unwrap();
+    assert_eq!(spki.key_algorithm(), Some(KeyAlgorithm::Ed448));
+    assert_eq!(spki.ed448_public_key().unwrap().len(), 57);
+
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/ed25519-example-cert.der")).unwrap();
+    let spki = &cert.tbs_certificate.value.subject_public_key_info;
+    assert_eq!(spki.key_algorithm(), Some(KeyAlgorithm::Ed25519));
+    assert_eq!(spki.ed448_public_key(), None);
+}
+
+#[test]
+fn reencodes_certificates_to_identical_der() {
+    let inputs: [&[u8]; 6] = [
+        include_bytes!("../../../certs/512b-rsa-example-cert.der"),
+        include_bytes!("../../../certs/ed25519-example-cert.der"),
+        include_bytes!("../../../certs/google_root_cert.cer"),
+        include_bytes!("../../../certs/cert_with_generalized_time.der"),
+        include_bytes!("../../../certs/ec_p256_cert.der"),
+        include_bytes!("../../../certs/multi_valued_rdn.der"),
+    ];
+    for input in inputs {
+        let cert = Certificate::parse(input).unwrap();
+        assert_eq!(cert.reencode().unwrap(), input);
+    }
+}
+
+#[test]
+fn names_match_ignoring_case_and_whitespace() {
+    fn name<'a>(rdns: &[&[(u128, &'a str)]]) -> RelativeDistinguishedName<'a> {
+        let mut name = RelativeDistinguishedName::empty();
+        for rdn in rdns {
+            name.rdns.push(
+                rdn.iter()
+                    .map(|(arc, value)| {
+                        Ava::new(ASNObjectIdentifier::new(vec![2, 5, 4, *arc]), *value)
+                    })
+                    .collect(),
+            );
+        }
+        name
+    }
+
+    let anchor = name(&[&[(6, "US")], &[(10, "Example  Corp")], &[(3, "Root CA")]]);
+    assert!(anchor.matches(&name(&[
+        &[(6, "us")],
+        &[(10, " example corp ")],
+        &[(3, "ROOT\tCA")],
+    ])));
+    assert!(!anchor.matches(&name(&[
+        &[(6, "US")],
+        &[(10, "Example Corp")],
+        &[(3, "RootCA")],
+    ])));
+    // the order of the RDNs matters but the order of AVAs within an RDN doesn't
+    assert!(!anchor.matches(&name(&[
+        &[(3, "Root CA")],
+        &[(10, "Example Corp")],
+        &[(6, "US")]
+    ])));
+    assert!(name(&[&[(3, "a"), (11, "b")]]).matches(&name(&[&[(11, "B"), (3, "A")]])));
+    assert!(!name(&[&[(3, "a"), (11, "b")]]).matches(&name(&[&[(3, "a")]])));
+    // a repeated AVA can't stand in for a different one, in either direction
+    let repeated = name(&[&[(3, "a"), (3, "a")]]);
+    let distinct = name(&[&[(3, "a"), (3, "b")]]);
+    assert!(!repeated.matches(&distinct));
+    assert!(!distinct.matches(&repeated));
+    assert!(repeated.matches(&name(&[&[(3, "A"), (3, "a ")]])));
+}
+
+#[test]
+fn names_match_values_that_arent_strings_exactly() {
+    // RDNSequence contents: CN=a, then serialNumber as the UniversalString `serial`
+    fn name(serial: u8) -> [u8; 27] {
+        [
+            0x31, 0x0A, 0x30, 0x08, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0C, 0x01, 0x61, 0x31, 0x0D,
+            0x30, 0x0B, 0x06, 0x03, 0x55, 0x04, 0x05, 0x1C, 0x04, 0x00, 0x00, 0x00, serial,
+        ]
+    }
+
+    let (x, y, lower_x) = (name(b'X'), name(b'Y'), name(b'x'));
+    let parse = |input| RelativeDistinguishedName::parse(input, ParseOptions::default()).unwrap();
+    assert!(parse(&x).matches(&parse(&x)));
+    assert!(!parse(&x).matches(&parse(&y)));
+    // case is only folded for strings the parser could decode
+    assert!(!parse(&x).matches(&parse(&lower_x)));
+}
+
+#[test]
+fn empty_input_is_distinct_from_truncated_input() {
+    assert_eq!(
+        Certificate::parse(&[]).unwrap_err().variant,
+        ASNErrorVariant::EmptyInput
+    );
+    assert_eq!(
+        Certificate::parse_allowing_trailing(&[])
+            .unwrap_err()
+            .variant,
+        ASNErrorVariant::EmptyInput
+    );
+    let der = include_bytes!("../../../certs/512b-rsa-example-cert.der");
+    assert_eq!(
+        Certificate::parse(&der[..der.len() / 2])
+            .unwrap_err()
+            .variant,
+        ASNErrorVariant::EndOfStream
+    );
+}
+
+#[test]
+fn reports_offset_of_the_failing_element() {
+    let mut der = include_bytes!("../../../certs/512b-rsa-example-cert.der").to_vec();
+    // the outer SEQUENCE is cut short, so it fails at its own identifier
+    let err = Certificate::parse(&der[..der.len() - 1]).unwrap_err();
+    assert_eq!(err.offset(), Some(0));
+
+    // turn the TBSCertificate SEQUENCE, following the 4 byte outer header, into a SET
+    der[4] = 0x31;
+    let err = Certificate::parse(&der).unwrap_err();
+    assert_eq!(
+        err.variant,
+        ASNErrorVariant::UnexpectedType(ASNTypeId::Sequence, ASNTypeId::Set)
+    );
+    assert_eq!(err.offset(), Some(4));
+    assert!(err.to_string().ends_with(" at byte 4"));
+}
+
+#[test]
+fn applies_parse_options_to_nested_elements() {
+    let original = include_bytes!("../../../certs/512b-rsa-example-cert.der");
+    // pad the last arc of the OID in the signature field of the TBSCertificate with a 0x80
+    // byte, growing the OID, the AlgorithmIdentifier, the TBSCertificate and the outer SEQUENCE
+    let mut der = original.to_vec();
+    assert_eq!(der[12..16], [0x30, 0x0D, 0x06, 0x09]);
+    der.insert(24, 0x80);
+    der[15] += 1;
+    der[13] += 1;
+    der[7] += 1;
+    der[3] += 1;
+
+    let err = Certificate::parse_with_options(&der, ParseOptions::strict()).unwrap_err();
+    assert_eq!(err.variant, ASNErrorVariant::NonMinimalOid);
+    assert_eq!(err.offset(), Some(14));
+
+    let cert = Certificate::parse_with_options(&der, ParseOptions::lenient()).unwrap();
+    let expected = Certificate::parse(original).unwrap();
+    assert_eq!(
+        cert.tbs_certificate.value.signature.algorithm,
+        expected.tbs_certificate.value.signature.algorithm
+    );
+}
+
+#[test]
+fn rejects_trailing_data_if_the_options_select_it() {
+    let original = include_bytes!("../../../certs/512b-rsa-example-cert.der");
+    let mut der = original.to_vec();
+    der.push(0x0A);
+
+    assert!(Certificate::parse(&der).is_err());
+    assert!(Certificate::parse_with_options(&der, ParseOptions::strict()).is_err());
+    let cert = Certificate::parse_with_options(&der, ParseOptions::lenient()).unwrap();
+    assert_eq!(
+        cert.tbs_der(),
+        Certificate::parse(original).unwrap().tbs_der()
+    );
+}
+
+#[test]
+fn scan_reports_each_certificate_of_a_bundle() {
+    let first = include_bytes!("../../../certs/ed25519-example-cert.der");
+    let second = include_bytes!("../../../certs/512b-rsa-example-cert.der");
+    let third = include_bytes!("../../../certs/tls_server.der");
+
+    let mut bundle = first.to_vec();
+    let corrupted = bundle.len() + 4;
+    bundle.extend_from_slice(second);
+    bundle.extend_from_slice(third);
+    // turn the TBSCertificate SEQUENCE of the second cert into a SET
+    assert_eq!(bundle[corrupted], 0x30);
+    bundle[corrupted] = 0x31;
+
+    let results = scan(&bundle);
+    assert_eq!(results.len(), 3);
+    assert!(matches!(results[0], (0, Ok(_))));
+    match &results[1] {
+        (1, Err(err)) => {
+            assert_eq!(
+                err.variant,
+                ASNErrorVariant::UnexpectedType(ASNTypeId::Sequence, ASNTypeId::Set)
+            );
+            // offsets are relative to the bundle
+            assert_eq!(err.offset(), Some(corrupted));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match &results[2] {
+        (2, Ok(cert)) => assert_eq!(
+            cert.tbs_certificate.value.serial_number.der_content(),
+            [0x2A]
+        ),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn scan_continues_past_entries_that_are_not_sequences() {
+    let cert = include_bytes!("../../../certs/ed25519-example-cert.der");
+    let mut bundle = cert.to_vec();
+    // INTEGER 5
+    bundle.extend_from_slice(&[0x02, 0x01, 0x05]);
+    bundle.extend_from_slice(cert);
+
+    let results = scan(&bundle);
+    assert_eq!(results.len(), 3);
+    assert!(results[0].1.is_ok());
+    assert_eq!(
+        results[1].1.as_ref().unwrap_err().variant,
+        ASNErrorVariant::UnexpectedType(ASNTypeId::Sequence, ASNTypeId::Integer)
+    );
+    assert!(results[2].1.is_ok());
+}
+
+#[test]
+fn scan_stops_when_framing_is_lost() {
+    let mut bundle = include_bytes!("../../../certs/ed25519-example-cert.der").to_vec();
+    bundle.extend_from_slice(&[0x30, 0x82, 0x10]);
+
+    let results = scan(&bundle);
+    assert_eq!(results.len(), 2);
+    assert!(results[0].1.is_ok());
+    assert_eq!(
+        results[1].1.as_ref().unwrap_err().variant,
+        ASNErrorVariant::EndOfStream
+    );
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn serial_numbers_round_trip_through_bignum() {
+    use num_bigint::BigInt;
+
+    for (der, serial) in [
+        (
+            &include_bytes!("../../../certs/ed25519-example-cert.der")[..],
+            "13E8CF3965E3D985FF57940132B75F7153090F28",
+        ),
+        (
+            include_bytes!("../../../certs/ec_p256_cert.der"),
+            "5FE3FF2881F6DED9B3A2D9B959DAD678D4C518A0",
+        ),
+        (
+            include_bytes!("../../../certs/serial_with_sign_byte.der"),
+            "8000000000000001",
+        ),
+        (
+            include_bytes!("../../../certs/512b-rsa-example-cert.der"),
+            "0DFA",
+        ),
+    ] {
+        let cert = Certificate::parse(der).unwrap();
+        let serial_number = &cert.tbs_certificate.value.serial_number;
+        let value = serial_number.as_bignum();
+        assert_eq!(value, BigInt::parse_bytes(serial.as_bytes(), 16).unwrap());
+        assert_eq!(value.to_signed_bytes_be(), serial_number.der_content());
+    }
+}
+
+#[test]
+fn parses_bmp_string_common_name() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/bmp_common_name.der")).unwrap();
+    let subject = cert.tbs_certificate.value.subject.parse().unwrap();
+    assert_eq!(subject.common_name.as_deref(), Some("Zoë Smith ✓"));
+    assert_eq!(subject.organization.as_deref(), Some("Example"));
+    assert_eq!(subject.rdns[0][0].value.as_str(), Some("Zoë Smith ✓"));
+    assert_eq!(
+        cert.reencode().unwrap(),
+        include_bytes!("../../../certs/bmp_common_name.der")
+    );
+}
+
+#[test]
+fn parses_teletex_string_organization() {
+    let der = include_bytes!("../../../certs/t61_organization.der");
+    let cert = Certificate::parse(der).unwrap();
+    let subject = cert.tbs_certificate.value.subject.parse().unwrap();
+    assert_eq!(subject.organization.as_deref(), Some("Société Générale"));
+    assert_eq!(subject.common_name.as_deref(), Some("legacy"));
+    assert_eq!(cert.reencode().unwrap(), der);
+}
+
+#[test]
+fn reads_and_writes_implicitly_tagged_unique_identifiers() {
+    let rsa = include_bytes!("../../../certs/512b-rsa-example-cert.der");
+    let cert = Certificate::parse(rsa).unwrap();
+    let cert_contents = Parser::new(rsa).expect::<Sequence>().unwrap();
+    let tbs_contents = Parser::new(cert.tbs_der()).expect::<Sequence>().unwrap();
+
+    // v2, the fields of the v1 certificate, then [1] issuerUniqueID and [2] subjectUniqueID
+    let mut tbs = vec![0xA0, 0x03, 0x02, 0x01, 0x01];
+    tbs.extend_from_slice(tbs_contents);
+    tbs.extend_from_slice(&[0x81, 0x02, 0x00, 0xAB, 0x82, 0x03, 0x04, 0xCD, 0xE0]);
+    let mut contents = Vec::new();
+    encode(&Sequence::asn(&tbs), &mut contents).unwrap();
+    contents.extend_from_slice(&cert_contents[cert.tbs_der().len()..]);
+    let mut der = Vec::new();
+    encode(&Sequence::asn(&contents), &mut der).unwrap();
+
+    let cert = Certificate::parse(&der).unwrap();
+    let tbs = &cert.tbs_certificate.value;
+    assert_eq!(tbs.version, Version::V2);
+    assert_eq!(tbs.issuer_unique_id, Some(ASNBitString::new(0, &[0xAB])));
+    assert_eq!(
+        tbs.subject_unique_id,
+        Some(ASNBitString::new(4, &[0xCD, 0xE0]))
+    );
+    assert_eq!(cert.reencode().unwrap(), der);
+}
+
+#[test]
+fn parses_certificate_from_pem() {
+    let mut der = Vec::new();
+    let cert = Certificate::from_pem(
+        include_str!("../../../certs/512b-rsa-example-cert.pem"),
+        &mut der,
+    )
+    .unwrap();
+    assert_eq!(
+        cert.tbs_certificate.value.serial_number.as_i32(),
+        Some(0x0DFA)
+    );
+    assert_eq!(
+        cert.reencode().unwrap(),
+        include_bytes!("../../../certs/512b-rsa-example-cert.der")
+    );
+
+    let mut der = Vec::new();
+    assert_eq!(
+        Certificate::from_pem("no blocks", &mut der).unwrap_err(),
+        PemError::MissingBlock("CERTIFICATE")
+    );
+}
+
+#[test]
+fn parses_certificate_files_of_either_encoding() {
+    let certs = concat!(env!("CARGO_MANIFEST_DIR"), "/../certs/");
+    let expected = include_bytes!("../../../certs/512b-rsa-example-cert.der");
+
+    for file in ["512b-rsa-example-cert.der", "512b-rsa-example-cert.pem"] {
+        let mut der = Vec::new();
+        let cert = Certificate::parse_file(format!("{}{}", certs, file), &mut der).unwrap();
+        assert_eq!(cert.reencode().unwrap(), expected);
+    }
+
+    let mut der = Vec::new();
+    assert!(matches!(
+        Certificate::parse_file(format!("{}missing.der", certs), &mut der),
+        Err(CertReadError::Io(_))
+    ));
+}
+
 #[test]
 fn parses_cert_with_generalized_time() {
-    Certificate::parse(include_bytes!(
+    let cert = Certificate::parse(include_bytes!(
         "../../../certs/cert_with_generalized_time.der"
     ))
     .unwrap();
+
+    let validity = &cert.tbs_certificate.value.validity;
+    // UTCTime 230803232640Z
+    assert_eq!(
+        validity.not_before,
+        UtcTime::from_seconds_since_epoch(1691105200)
+    );
+    // GeneralizedTime 21230710232640Z
+    assert_eq!(
+        validity.not_after,
+        UtcTime::from_seconds_since_epoch(4844705200)
+    );
+    assert_eq!(validity.not_before_kind, ASNTypeId::UTCTime);
+    assert_eq!(validity.not_after_kind, ASNTypeId::GeneralizedTime);
+}
+
+#[test]
+fn parses_validity_bounds_of_either_time_type() {
+    let mixed = [
+        &b"\x30\x20\x17\x0d160101000000Z"[..],
+        b"\x18\x0f21160101000000Z",
+    ]
+    .concat();
+    let validity = Validity::parse_from(&mut Parser::new(&mixed)).unwrap();
+    assert_eq!(
+        validity.not_before,
+        UtcTime::from_seconds_since_epoch(1451606400)
+    );
+    assert_eq!(
+        validity.not_after,
+        UtcTime::from_seconds_since_epoch(4607280000)
+    );
+    assert_eq!(validity.not_before_kind, ASNTypeId::UTCTime);
+    assert_eq!(validity.not_after_kind, ASNTypeId::GeneralizedTime);
+
+    // the same bounds with the types swapped
+    let swapped = [
+        &b"\x30\x20\x18\x0f20160101000000Z"[..],
+        b"\x17\x0d160101000000Z",
+    ]
+    .concat();
+    let validity = Validity::parse_from(&mut Parser::new(&swapped)).unwrap();
+    assert_eq!(validity.not_before_kind, ASNTypeId::GeneralizedTime);
+    assert_eq!(validity.not_after_kind, ASNTypeId::UTCTime);
+
+    let not_a_time = [&b"\x30\x12\x17\x0d160101000000Z"[..], b"\x02\x01\x05"].concat();
+    let err = Validity::parse_from(&mut Parser::new(&not_a_time))
+        .unwrap_err()
+        .relative_to(&not_a_time);
+    assert_eq!(
+        err.variant,
+        ASNErrorVariant::UnexpectedType(ASNTypeId::UTCTime, ASNTypeId::Integer)
+    );
+    assert_eq!(err.offset(), Some(17));
+}
+
+#[test]
+fn parses_rsa_pss_signature_parameters() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/rsa_pss_cert.der")).unwrap();
+    let params = cert.signature_algorithm.rsa_pss_params().unwrap().unwrap();
+    assert_eq!(
+        params.hash_algorithm.algorithm.values(),
+        [2, 16, 840, 1, 101, 3, 4, 2, 1]
+    );
+    assert_eq!(
+        params.mask_gen_algorithm.algorithm.values(),
+        [1, 2, 840, 113549, 1, 1, 8]
+    );
+    assert_eq!(
+        params
+            .mgf1_hash_algorithm()
+            .unwrap()
+            .unwrap()
+            .algorithm
+            .values(),
+        [2, 16, 840, 1, 101, 3, 4, 2, 1]
+    );
+    assert_eq!(params.salt_length, 32);
+    // absent, so the default
+    assert_eq!(params.trailer_field, 1);
+
+    let rsa =
+        Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
+    assert!(rsa.signature_algorithm.rsa_pss_params().unwrap().is_none());
+}
+
+#[test]
+fn fills_in_rsa_pss_defaults() {
+    let pss = ASNObjectIdentifier::new(vec![1, 2, 840, 113549, 1, 1, 10]);
+    for parameters in [None, Some(Sequence::asn(&[]))] {
+        let params = AlgorithmIdentifier::new(pss.clone(), parameters)
+            .rsa_pss_params()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            params.hash_algorithm.algorithm.values(),
+            [1, 3, 14, 3, 2, 26]
+        );
+        assert_eq!(
+            params
+                .mgf1_hash_algorithm()
+                .unwrap()
+                .unwrap()
+                .algorithm
+                .values(),
+            [1, 3, 14, 3, 2, 26]
+        );
+        assert_eq!(params.salt_length, 20);
+        assert_eq!(params.trailer_field, 1);
+    }
+
+    // [2] EXPLICIT INTEGER -1
+    let negative_salt = [0xA2, 0x03, 0x02, 0x01, 0xFF];
+    let err = AlgorithmIdentifier::new(pss, Some(Sequence::asn(&negative_salt)))
+        .rsa_pss_params()
+        .unwrap_err();
+    assert_eq!(
+        err.variant,
+        ASNErrorVariant::NegativeValue("saltLength", -1)
+    );
 }
 
 #[test]
@@ -22,3 +1229,541 @@ fn parses_ed25519_cert() {
 fn google_root_cert() {
     Certificate::parse(include_bytes!("../../../certs/google_root_cert.cer")).unwrap();
 }
+
+#[test]
+fn issuer_der_hashes_to_ocsp_issuer_name_hash() {
+    // Issuer Name Hash from the CertID of an OCSP request generated by:
+    // openssl ocsp -issuer cert.pem -cert cert.pem -reqout req.der -no_nonce
+    const ISSUER_NAME_HASH: [u8; 20] = [
+        0xB0, 0xA4, 0xAC, 0x8C, 0x3B, 0xFC, 0xD0, 0x42, 0xBA, 0x12, 0xA3, 0x6C, 0x58, 0x19, 0xE9,
+        0x56, 0x02, 0x0C, 0x68, 0x5D,
+    ];
+
+    let cert = Certificate::parse(include_bytes!(
+        "../../../certs/cert_with_generalized_time.der"
+    ))
+    .unwrap();
+    let issuer = &cert.tbs_certificate.value.issuer;
+
+    // the DER includes the outer SEQUENCE header in front of the contents
+    assert_eq!(issuer.der()[0], 0x30);
+    assert!(issuer.der().ends_with(issuer.inner));
+
+    let hash = sha1_smol::Sha1::from(issuer.der()).digest().bytes();
+    assert_eq!(hash, ISSUER_NAME_HASH);
+}
+
+#[test]
+fn preserves_multi_valued_rdn_grouping() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/multi_valued_rdn.der")).unwrap();
+    let subject = cert.tbs_certificate.value.subject.parse().unwrap();
+
+    let grouping: Vec<Vec<&str>> = subject
+        .rdns
+        .iter()
+        .map(|rdn| rdn.iter().map(|ava| ava.value.as_str().unwrap()).collect())
+        .collect();
+    assert_eq!(
+        grouping,
+        vec![vec!["US"], vec!["Step Function"], vec!["A", "B"], vec!["C"]]
+    );
+
+    assert_eq!(subject.organizational_unit_name.as_deref(), Some("A"));
+    assert_eq!(
+        subject.to_rfc4514_string(),
+        "CN=C,OU=A+OU=B,O=Step Function,C=US"
+    );
+}
+
+#[test]
+fn renders_every_ava_of_a_multi_valued_rdn() {
+    // RDNSequence contents: a single RDN of CN=a and serialNumber as a UniversalString
+    let name = RelativeDistinguishedName::parse(
+        &[
+            0x31, 0x17, 0x30, 0x08, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0C, 0x01, 0x61, 0x30, 0x0B,
+            0x06, 0x03, 0x55, 0x04, 0x05, 0x1C, 0x04, 0x00, 0x00, 0x00, 0x58,
+        ],
+        ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(name.rdns.len(), 1);
+    assert_eq!(name.to_rfc4514_string(), "CN=a+2.5.4.5=#1C0400000058");
+}
+
+#[test]
+fn keeps_repeated_and_unnamed_attributes_in_order() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/two_ou_email.der")).unwrap();
+    let subject = cert.tbs_certificate.value.subject.parse().unwrap();
+
+    let types: Vec<&[u128]> = subject
+        .rdns
+        .iter()
+        .map(|rdn| {
+            assert_eq!(rdn.len(), 1);
+            rdn[0].attr_type.values()
+        })
+        .collect();
+    assert_eq!(
+        types,
+        [
+            &[2, 5, 4, 6][..],
+            &[2, 5, 4, 10],
+            &[2, 5, 4, 11],
+            &[2, 5, 4, 11],
+            &[2, 5, 4, 3],
+            &[1, 2, 840, 113_549, 1, 9, 1],
+            &[2, 5, 4, 5],
+        ]
+    );
+
+    // the convenience field only holds the first OU
+    assert_eq!(
+        subject.organizational_unit_name.as_deref(),
+        Some("Engineering")
+    );
+    assert_eq!(
+        subject.values_of(&[2, 5, 4, 11]).collect::<Vec<_>>(),
+        ["Engineering", "Firmware"]
+    );
+    assert_eq!(
+        subject
+            .values_of(&[1, 2, 840, 113_549, 1, 9, 1])
+            .collect::<Vec<_>>(),
+        ["ops@example.com"]
+    );
+    assert_eq!(
+        subject.values_of(&[2, 5, 4, 5]).collect::<Vec<_>>(),
+        ["SN-0042"]
+    );
+}
+
+#[test]
+fn keeps_the_encoding_of_attribute_values_of_unsupported_types() {
+    // RDNSequence contents: CN=a, then serialNumber as a UniversalString, then
+    // 1.2.3=INTEGER 5
+    let name = RelativeDistinguishedName::parse(
+        &[
+            0x31, 0x0A, 0x30, 0x08, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0C, 0x01, 0x61, 0x31, 0x0D,
+            0x30, 0x0B, 0x06, 0x03, 0x55, 0x04, 0x05, 0x1C, 0x04, 0x00, 0x00, 0x00, 0x31, 0x31,
+            0x09, 0x30, 0x07, 0x06, 0x02, 0x2A, 0x03, 0x02, 0x01, 0x05,
+        ],
+        ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(name.common_name.as_deref(), Some("a"));
+    assert_eq!(name.rdns.len(), 3);
+    assert_eq!(
+        name.rdns[1],
+        [Ava::new(
+            ASNObjectIdentifier::new(vec![2, 5, 4, 5]),
+            AvaValue::Der(&[0x1C, 0x04, 0x00, 0x00, 0x00, 0x31]),
+        )]
+    );
+    assert_eq!(
+        name.rdns[2],
+        [Ava::new(
+            ASNObjectIdentifier::new(vec![1, 2, 3]),
+            AvaValue::Der(&[0x02, 0x01, 0x05]),
+        )]
+    );
+    assert_eq!(name.values_of(&[2, 5, 4, 5]).count(), 0);
+    assert_eq!(
+        name.to_rfc4514_string(),
+        "1.2.3=#020105,2.5.4.5=#1C0400000031,CN=a"
+    );
+}
+
+#[test]
+fn parses_numeric_and_visible_string_attribute_values() {
+    // RDNSequence contents: CN=a as a VisibleString, then serialNumber=42 as a NumericString
+    let name = RelativeDistinguishedName::parse(
+        &[
+            0x31, 0x0A, 0x30, 0x08, 0x06, 0x03, 0x55, 0x04, 0x03, 0x1A, 0x01, 0x61, 0x31, 0x0B,
+            0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x05, 0x12, 0x02, 0x34, 0x32,
+        ],
+        ParseOptions::strict(),
+    )
+    .unwrap();
+    assert_eq!(name.common_name.as_deref(), Some("a"));
+    assert_eq!(name.values_of(&[2, 5, 4, 5]).collect::<Vec<_>>(), ["42"]);
+}
+
+#[test]
+fn strict_name_parsing_enforces_the_printable_string_character_set() {
+    let mut der = include_bytes!("../../../certs/printable_string_names.der").to_vec();
+    let cert = Certificate::parse(&der).unwrap();
+    let subject = &cert.tbs_certificate.value.subject;
+    let parsed = subject.parse_strict().unwrap();
+    assert_eq!(parsed.country_name.as_deref(), Some("US"));
+    assert_eq!(parsed.common_name.as_deref(), Some("host-name.example.com"));
+
+    // replace the '-' in the subject CN with an '_'
+    let dash = subject.der().iter().position(|x| *x == b'-').unwrap();
+    let offset = subject.der().as_ptr() as usize - der.as_ptr() as usize + dash;
+    der[offset] = b'_';
+
+    let cert = Certificate::parse(&der).unwrap();
+    let subject = &cert.tbs_certificate.value.subject;
+    assert_eq!(
+        subject.parse().unwrap().common_name.as_deref(),
+        Some("host_name.example.com")
+    );
+    assert_eq!(
+        subject.parse_strict().err().map(|err| err.variant),
+        Some(ASNErrorVariant::BadPrintableChar(b'_'))
+    );
+}
+
+#[test]
+fn parses_email_address_and_domain_components() {
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/domain_component_issuer.der")).unwrap();
+    let issuer = cert.tbs_certificate.value.issuer.parse().unwrap();
+
+    assert_eq!(issuer.domain_components, ["com", "example"]);
+    assert_eq!(issuer.email_address.as_deref(), Some("pki@example.com"));
+    assert_eq!(issuer.common_name.as_deref(), Some("Example Issuing CA"));
+    assert_eq!(
+        issuer.to_rfc4514_string(),
+        "1.2.840.113549.1.9.1=pki@example.com,CN=Example Issuing CA,DC=example,DC=com"
+    );
+}
+
+#[test]
+fn ca_with_path_length_zero_allows_no_intermediates() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/ca_pathlen_0.der")).unwrap();
+    let extensions = cert.tbs_certificate.value.extensions.as_ref().unwrap();
+    let constraints = extensions
+        .parse()
+        .unwrap()
+        .into_iter()
+        .find_map(|ext| match ext.content {
+            SpecificExtension::BasicConstraints(x) => Some(x),
+            _ => None,
+        })
+        .unwrap();
+
+    assert!(constraints.ca);
+    assert_eq!(constraints.max_intermediate_certs(), Some(0));
+}
+
+#[test]
+fn detects_must_staple() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/must_staple.der")).unwrap();
+    assert!(cert.has_must_staple().unwrap());
+    assert!(!cert.is_ocsp_nocheck().unwrap());
+}
+
+#[test]
+fn detects_ocsp_nocheck() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/ocsp_nocheck.der")).unwrap();
+    assert!(cert.is_ocsp_nocheck().unwrap());
+    assert!(!cert.has_must_staple().unwrap());
+}
+
+#[test]
+fn certificate_without_extensions_has_no_must_staple() {
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
+    assert!(!cert.has_must_staple().unwrap());
+    assert!(!cert.is_ocsp_nocheck().unwrap());
+}
+
+#[cfg(feature = "display")]
+mod printing {
+    use crate::der::{to_hex, ASNObjectIdentifier};
+    use crate::x509::ext::SpecificExtension;
+    use crate::x509::printer::{LinePrinter, Printable};
+    use crate::x509::Certificate;
+
+    #[test]
+    fn names_edwards_and_montgomery_curve_oids() {
+        let name = |arc: u128| ASNObjectIdentifier::new(vec![1, 3, 101, arc]).to_string();
+        assert_eq!(name(110), "X25519 Key Agreement");
+        assert_eq!(name(111), "X448 Key Agreement");
+        assert_eq!(name(112), "Ed25519 Signature");
+        assert_eq!(name(113), "Ed448 Signature");
+    }
+
+    #[test]
+    fn names_common_signature_and_key_algorithm_oids() {
+        let name = |arcs: &[u128]| ASNObjectIdentifier::new(arcs.to_vec()).to_string();
+        assert_eq!(
+            name(&[1, 2, 840, 113_549, 1, 1, 11]),
+            "SHA256 with RSA Signature"
+        );
+        assert_eq!(
+            name(&[1, 2, 840, 113_549, 1, 1, 13]),
+            "SHA512 with RSA Signature"
+        );
+        assert_eq!(
+            name(&[1, 2, 840, 10045, 4, 3, 3]),
+            "ECDSA with SHA384 Signature"
+        );
+        assert_eq!(name(&[1, 2, 840, 113_549, 1, 1, 1]), "RSA Encryption");
+        assert_eq!(name(&[1, 3, 132, 0, 34]), "secp384r1 (P-384)");
+        assert_eq!(name(&[1, 3, 132, 0, 35]), "secp521r1 (P-521)");
+        // ecdsa-with-SHA512 isn't in the table
+        assert_eq!(name(&[1, 2, 840, 10045, 4, 3, 4]), "1.2.840.10045.4.3.4");
+    }
+
+    #[derive(Default)]
+    struct CapturePrinter {
+        lines: Vec<String>,
+        current: String,
+    }
+
+    impl LinePrinter for CapturePrinter {
+        fn begin_type(&mut self) {}
+
+        fn begin_line(&mut self) {}
+
+        fn print_fmt(&mut self, fmt: &core::fmt::Arguments) {
+            self.current.push_str(&fmt.to_string());
+        }
+
+        fn print_str(&mut self, s: &str) {
+            self.current.push_str(s);
+        }
+
+        fn println_fmt(&mut self, fmt: &core::fmt::Arguments) {
+            self.print_fmt(fmt);
+            self.lines.push(std::mem::take(&mut self.current));
+        }
+
+        fn println_str(&mut self, line: &str) {
+            self.print_str(line);
+            self.lines.push(std::mem::take(&mut self.current));
+        }
+
+        fn end_type(&mut self) {}
+    }
+
+    fn print(printable: &dyn Printable) -> Vec<String> {
+        let mut printer = CapturePrinter::default();
+        printable.print(&mut printer);
+        printer.lines
+    }
+
+    #[test]
+    fn prints_subject_key_identifier_as_hex() {
+        let cert = Certificate::parse(include_bytes!("../../../certs/ca_pathlen_0.der")).unwrap();
+        let extensions = cert
+            .tbs_certificate
+            .value
+            .extensions
+            .as_ref()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let ski = extensions
+            .iter()
+            .find_map(|ext| match &ext.content {
+                SpecificExtension::SubjectKeyIdentifier(x) => Some(x),
+                _ => None,
+            })
+            .unwrap();
+
+        let hex = "83:6C:B7:F7:40:10:38:FE:56:98:85:AD:85:72:02:92:DB:CC:77:E4";
+        assert_eq!(to_hex(ski.key_identifier), hex);
+        // 16 bytes per line
+        assert_eq!(print(ski), ["key identifier:", &hex[..47], &hex[48..]]);
+    }
+
+    #[test]
+    fn prints_ec_curve_parameter() {
+        let cert = Certificate::parse(include_bytes!("../../../certs/ec_p256_cert.der")).unwrap();
+        let spki = &cert.tbs_certificate.value.subject_public_key_info;
+        assert_eq!(
+            print(&spki.algorithm),
+            ["algorithm: EC Public Key", "parameters: secp256r1 (P-256)"]
+        );
+        // ecdsa-with-SHA256 omits the parameters
+        assert_eq!(
+            print(&cert.signature_algorithm),
+            [
+                "algorithm: ECDSA with SHA256 Signature",
+                "parameters: (absent)"
+            ]
+        );
+    }
+
+    #[test]
+    fn prints_null_parameters() {
+        let cert =
+            Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
+        assert_eq!(
+            print(&cert.signature_algorithm),
+            ["algorithm: SHA1 with RSA Signature", "parameters: NULL"]
+        );
+    }
+}
+
+#[test]
+fn rejects_duplicate_extensions_unless_lenient() {
+    let cert =
+        Certificate::parse(include_bytes!("../../../certs/duplicate_key_usage.der")).unwrap();
+    let extensions = cert.tbs_certificate.value.extensions.as_ref().unwrap();
+
+    let err = extensions.parse().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The extension '2.5.29.15' appears more than once."
+    );
+
+    let extensions = extensions.parse_lenient().unwrap();
+    assert_eq!(extensions.len(), 2);
+    assert!(extensions
+        .iter()
+        .all(|ext| matches!(ext.content, SpecificExtension::KeyUsage(_))));
+}
+
+#[cfg(feature = "arena")]
+#[test]
+fn parses_certificates_and_extensions_into_an_arena() {
+    use crate::x509::ext::Extension;
+
+    fn ids<'a>(extensions: impl IntoIterator<Item = &'a Extension<'a>>) -> Vec<String> {
+        extensions
+            .into_iter()
+            .map(|ext| ext.extn_id.to_string())
+            .collect()
+    }
+
+    let mut arena = bumpalo::Bump::new();
+    for der in [
+        &include_bytes!("../../../certs/tls_server.der")[..],
+        include_bytes!("../../../certs/ca_pathlen_0.der"),
+        include_bytes!("../../../certs/aki_issuer_serial.der"),
+    ] {
+        let expected = Certificate::parse(der).unwrap();
+        let expected_extensions = expected.tbs_certificate.value.extensions.as_ref().unwrap();
+        {
+            let cert = Certificate::parse_in(der, &arena).unwrap();
+            assert_eq!(
+                cert.signature_algorithm.algorithm,
+                expected.signature_algorithm.algorithm
+            );
+            let spki = &cert.tbs_certificate.value.subject_public_key_info;
+            let expected_spki = &expected.tbs_certificate.value.subject_public_key_info;
+            assert_eq!(spki.algorithm.algorithm, expected_spki.algorithm.algorithm);
+            let extensions = cert.tbs_certificate.value.extensions.as_ref().unwrap();
+            assert_eq!(
+                ids(&extensions.parse_in(&arena).unwrap()),
+                ids(&expected_extensions.parse().unwrap())
+            );
+        }
+        // every value borrowing from the arena is gone, so its memory can be reused
+        arena.reset();
+    }
+
+    let cert = Certificate::parse_in(
+        include_bytes!("../../../certs/duplicate_key_usage.der"),
+        &arena,
+    )
+    .unwrap();
+    let err = cert
+        .tbs_certificate
+        .value
+        .extensions
+        .as_ref()
+        .unwrap()
+        .parse_in(&arena);
+    assert_eq!(
+        err.unwrap_err().variant,
+        ASNErrorVariant::DuplicateExtension(ASNObjectIdentifier::new(vec![2, 5, 29, 15]))
+    );
+
+    assert_eq!(
+        Certificate::parse_in(&[], &arena).unwrap_err().variant,
+        ASNErrorVariant::EmptyInput
+    );
+}
+
+#[test]
+fn recognizes_smartcard_logon_purpose() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/smartcard_logon.der")).unwrap();
+    let extensions = cert.tbs_certificate.value.extensions.as_ref().unwrap();
+    let usages = extensions
+        .parse()
+        .unwrap()
+        .into_iter()
+        .find_map(|ext| match ext.content {
+            SpecificExtension::ExtendedKeyUsage(x) => Some(x.ext_key_usages),
+            _ => None,
+        })
+        .unwrap();
+
+    assert!(matches!(
+        usages.as_slice(),
+        [
+            ExtendedKeyUsagePurpose::ClientAuth,
+            ExtendedKeyUsagePurpose::MicrosoftSmartcardLogon,
+            ExtendedKeyUsagePurpose::Other(oid),
+        ] if oid.values() == [1, 3, 6, 1, 4, 1, 99999, 1]
+    ));
+}
+
+#[test]
+fn parses_each_certificate_of_a_chain() {
+    fn chain(certs: &[&[u8]]) -> Vec<u8> {
+        let mut der = Vec::new();
+        encode(&Sequence::asn(&certs.concat()), &mut der).unwrap();
+        der
+    }
+
+    let rsa = include_bytes!("../../../certs/512b-rsa-example-cert.der");
+    let ed25519 = include_bytes!("../../../certs/ed25519-example-cert.der");
+
+    let der = chain(&[rsa, ed25519]);
+    let certs = parse_chain(&der).unwrap();
+    assert_eq!(certs.len(), 2);
+    assert_eq!(
+        certs[0].tbs_der(),
+        Certificate::parse(rsa).unwrap().tbs_der()
+    );
+    assert_eq!(
+        certs[1].tbs_der(),
+        Certificate::parse(ed25519).unwrap().tbs_der()
+    );
+
+    assert!(parse_chain(&chain(&[])).unwrap().is_empty());
+
+    // the second certificate has a SET instead of its TBSCertificate SEQUENCE
+    let mut der = chain(&[rsa, ed25519]);
+    let corrupted = der.len() - ed25519.len() + 4;
+    assert_eq!(der[corrupted], 0x30);
+    der[corrupted] = 0x31;
+    let err = parse_chain(&der).unwrap_err();
+    assert_eq!(
+        err.variant,
+        ASNErrorVariant::UnexpectedType(ASNTypeId::Sequence, ASNTypeId::Set)
+    );
+    assert_eq!(err.offset(), Some(corrupted));
+}
+
+#[test]
+fn splits_ecdsa_signature_into_r_and_s() {
+    let cert = Certificate::parse(include_bytes!("../../../certs/ec_p256_cert.der")).unwrap();
+    let signature = cert.ecdsa_signature().unwrap().unwrap();
+    // P-256 values, plus a leading zero byte when the top bit is set
+    for value in [&signature.r, &signature.s] {
+        assert!(!value.bytes.is_empty());
+        assert!(value.bytes.len() <= 33);
+    }
+
+    let rsa =
+        Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
+    assert!(rsa.ecdsa_signature().unwrap().is_none());
+
+    // SEQUENCE { INTEGER 1, INTEGER 2, INTEGER 3 }
+    let err = EcdsaSignature::parse(&[
+        0x30, 0x09, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02, 0x02, 0x01, 0x03,
+    ])
+    .unwrap_err();
+    assert_eq!(
+        err.variant,
+        ASNErrorVariant::ExpectedEnd(ASNTypeId::Integer)
+    );
+    assert_eq!(err.offset(), Some(8));
+}
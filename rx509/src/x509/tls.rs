@@ -0,0 +1,64 @@
+/// Outcome of each check performed by [`Certificate::is_valid_tls_server`](crate::x509::Certificate::is_valid_tls_server)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TlsServerCheck {
+    /// the hostname matches a SAN dNSName/iPAddress, or the subject CN if there are no DNS names
+    pub hostname: bool,
+    /// the time lies within the validity window
+    pub validity: bool,
+    /// KeyUsage is absent or asserts digitalSignature or keyEncipherment
+    pub key_usage: bool,
+    /// ExtendedKeyUsage is absent or contains serverAuth or anyExtendedKeyUsage
+    pub extended_key_usage: bool,
+}
+
+impl TlsServerCheck {
+    /// True if every check passed
+    pub fn is_valid(&self) -> bool {
+        self.hostname && self.validity && self.key_usage && self.extended_key_usage
+    }
+}
+
+/// Match a reference hostname against a DNS name from a certificate (RFC 6125)
+///
+/// Comparison is ASCII case-insensitive and ignores a trailing dot. A wildcard is only
+/// honored as the entire left-most label and matches exactly one label.
+pub(crate) fn dns_name_matches(pattern: &str, hostname: &str) -> bool {
+    let pattern = pattern.strip_suffix('.').unwrap_or(pattern);
+    let hostname = hostname.strip_suffix('.').unwrap_or(hostname);
+
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => match hostname.split_once('.') {
+            Some((label, rest)) => {
+                !label.is_empty()
+                    // don't let a wildcard stand in for a whole registrable domain
+                    && suffix.contains('.')
+                    && rest.eq_ignore_ascii_case(suffix)
+            }
+            None => false,
+        },
+        None => pattern.eq_ignore_ascii_case(hostname),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_exact_names_ignoring_case_and_trailing_dot() {
+        assert!(dns_name_matches("www.example.com", "WWW.Example.com"));
+        assert!(dns_name_matches("www.example.com.", "www.example.com"));
+        assert!(dns_name_matches("www.example.com", "www.example.com."));
+        assert!(!dns_name_matches("www.example.com", "example.com"));
+    }
+
+    #[test]
+    fn wildcard_matches_a_single_left_most_label() {
+        assert!(dns_name_matches("*.example.org", "a.example.org"));
+        assert!(!dns_name_matches("*.example.org", "example.org"));
+        assert!(!dns_name_matches("*.example.org", "a.b.example.org"));
+        assert!(!dns_name_matches("*.example.org", ".example.org"));
+        assert!(!dns_name_matches("*.org", "example.org"));
+        assert!(!dns_name_matches("w*.example.org", "www.example.org"));
+    }
+}
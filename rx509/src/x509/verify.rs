@@ -0,0 +1,106 @@
+use crate::der::ASNObjectIdentifier;
+use crate::x509::{AlgorithmIdentifier, Certificate, SubjectPublicKeyInfo};
+
+use ed25519_dalek::{Signature, VerifyingKey};
+
+/// Errors that can occur while verifying a certificate signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+    /// the signature or public key algorithm is not supported
+    UnsupportedAlgorithm(ASNObjectIdentifier<'static>),
+    /// the public key is malformed
+    BadPublicKey,
+    /// the signature is malformed or does not match
+    BadSignature,
+}
+
+impl core::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            VerifyError::UnsupportedAlgorithm(oid) => {
+                write!(f, "unsupported algorithm: {:?}", oid.values())
+            }
+            VerifyError::BadPublicKey => f.write_str("bad public key"),
+            VerifyError::BadSignature => f.write_str("bad signature"),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+pub(crate) const ED25519: [u128; 4] = [1, 3, 101, 112];
+
+fn expect_ed25519(algorithm: &AlgorithmIdentifier) -> Result<(), VerifyError> {
+    if algorithm.algorithm.values() == ED25519 {
+        Ok(())
+    } else {
+        Err(VerifyError::UnsupportedAlgorithm(
+            algorithm.algorithm.clone().into_owned(),
+        ))
+    }
+}
+
+impl<'a> Certificate<'a> {
+    /// Verify the signature over the TBSCertificate using the issuer's public key
+    ///
+    /// Only Ed25519 is currently supported.
+    pub fn verify_signature(&self, issuer: &SubjectPublicKeyInfo) -> Result<(), VerifyError> {
+        expect_ed25519(&self.signature_algorithm)?;
+        expect_ed25519(&issuer.algorithm)?;
+
+        let public_key: &[u8; 32] = issuer
+            .subject_public_key
+            .octets()
+            .and_then(|x| x.try_into().ok())
+            .ok_or(VerifyError::BadPublicKey)?;
+        let key = VerifyingKey::from_bytes(public_key).map_err(|_| VerifyError::BadPublicKey)?;
+
+        let signature = self
+            .signature_value
+            .octets()
+            .and_then(|x| Signature::from_slice(x).ok())
+            .ok_or(VerifyError::BadSignature)?;
+
+        key.verify_strict(self.tbs_der(), &signature)
+            .map_err(|_| VerifyError::BadSignature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_self_signed_ed25519_cert() {
+        let cert =
+            Certificate::parse(include_bytes!("../../../certs/ed25519-example-cert.der")).unwrap();
+        let spki = &cert.tbs_certificate.value.subject_public_key_info;
+        assert_eq!(cert.verify_signature(spki), Ok(()));
+    }
+
+    #[test]
+    fn rejects_tampered_tbs_certificate() {
+        let mut der = include_bytes!("../../../certs/ed25519-example-cert.der").to_vec();
+        // flip a bit in the last byte of the serial number
+        let cert = Certificate::parse(&der).unwrap();
+        let offset = cert.tbs_certificate.value.serial_number.bytes.as_ptr() as usize
+            - der.as_ptr() as usize;
+        let len = cert.tbs_certificate.value.serial_number.bytes.len();
+        der[offset + len - 1] ^= 0x01;
+
+        let cert = Certificate::parse(&der).unwrap();
+        let spki = &cert.tbs_certificate.value.subject_public_key_info;
+        assert_eq!(cert.verify_signature(spki), Err(VerifyError::BadSignature));
+    }
+
+    #[test]
+    fn rejects_rsa_signatures() {
+        let cert =
+            Certificate::parse(include_bytes!("../../../certs/512b-rsa-example-cert.der")).unwrap();
+        let spki = &cert.tbs_certificate.value.subject_public_key_info;
+        assert!(matches!(
+            cert.verify_signature(spki),
+            Err(VerifyError::UnsupportedAlgorithm(_))
+        ));
+    }
+}